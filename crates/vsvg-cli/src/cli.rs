@@ -27,12 +27,6 @@ impl Display for CliError {
 
 impl Error for CliError {}
 
-trait ArgCreator {
-    type T: CommandArg;
-
-    fn create_arg(&self, arg: &mut Arg);
-}
-
 #[derive(Debug)]
 pub(crate) struct State {
     pub document: Document,
@@ -41,7 +35,7 @@ pub(crate) struct State {
 }
 
 impl State {
-    pub(crate) fn draw(&mut self) -> LayerDrawer {
+    pub(crate) fn draw(&mut self) -> LayerDrawer<'_, '_> {
         LayerDrawer {
             state: &self.draw_state,
             layer: self.document.get_mut(self.draw_layer),
@@ -89,6 +83,11 @@ pub(crate) fn cli(command_descs: &HashMap<Id, CommandDesc>) -> Command {
                 .long("single")
                 .help("Single layer mode")
                 .num_args(0),
+            Arg::new("dedup-points")
+                .long("dedup-points")
+                .value_name("EPSILON")
+                .help("Remove consecutive points closer than EPSILON when loading")
+                .value_parser(value_parser!(f64)),
             Arg::new("no-show")
                 .long("no-show")
                 .help("Don't show the GUI")
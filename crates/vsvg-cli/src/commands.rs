@@ -146,6 +146,17 @@ pub(crate) fn command_list() -> HashMap<Id, CommandDesc<'static>> {
             LayerID, // hack
             |state, b| state.document.for_each(|layer| layer.sort_with_builder(IndexBuilder::new().flip(true)))
             ),
+        command_decl!(
+            arg!(--plotsort "Reorder paths for plotting and report the pen-up distance saved"),
+            bool,
+            |state, b| {
+                let (before, after) = state.document.sort_for_plotting();
+                eprintln!(
+                    "pen-up distance: {before:.2} -> {after:.2} ({:.2} saved)",
+                    before - after
+                );
+            }
+        ),
         command_decl!(
             arg!(--dlayer [X] "Set target layer for draw operations"),
             LayerID,
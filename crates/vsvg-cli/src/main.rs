@@ -9,7 +9,7 @@ use std::io::Read;
 use std::path::PathBuf;
 
 use crate::cli::State;
-use vsvg::Document;
+use vsvg::{Document, SvgLoadOptions};
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -28,7 +28,11 @@ fn main() -> Result<(), Box<dyn Error>> {
         .expect("PATH is a required arg");
     let no_show = matches.remove_one::<bool>("no-show").unwrap();
     let verbose = matches.remove_one::<bool>("verbose").unwrap();
-    let single_layer = matches.remove_one::<bool>("single-layer").unwrap();
+    let options = SvgLoadOptions {
+        single_layer: matches.remove_one::<bool>("single-layer").unwrap(),
+        dedup_points: matches.remove_one::<f64>("dedup-points"),
+        ..Default::default()
+    };
 
     if verbose {
         tracing_subscriber::fmt::init();
@@ -36,12 +40,12 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // create and process document
     let mut state = State {
-        document: if path == PathBuf::from("-") {
+        document: if path.as_os_str() == "-" {
             let mut s = String::new();
             std::io::stdin().read_to_string(&mut s)?;
-            Document::from_string(s.as_str(), single_layer)?
+            Document::from_string_with_options(s.as_str(), &options)?
         } else {
-            Document::from_svg(path, single_layer)?
+            Document::from_svg_with_options(path, &options)?
         },
         ..Default::default()
     };
@@ -0,0 +1,66 @@
+//! This example demonstrates how a [`DocumentWidget`] can be embedded in an existing eframe app,
+//! instead of letting [`vsvg_viewer::show`] take over the application.
+
+use std::sync::Arc;
+use vsvg::{Document, DocumentTrait};
+use vsvg_viewer::{DocumentData, DocumentWidget};
+
+struct HostApp {
+    document: Document,
+    document_widget: DocumentWidget,
+}
+
+impl HostApp {
+    fn new(cc: &eframe::CreationContext) -> Self {
+        let mut document = Document::new_with_page_size(vsvg::PageSize::A6V);
+        document.push_path(1, kurbo::Circle::new((200., 300.), 100.));
+
+        // the widget requires the wgpu renderer, which is eframe's only renderer here
+        let document_widget = DocumentWidget::new(cc, Arc::new(DocumentData::new(&document)))
+            .expect("the wgpu renderer is required");
+
+        Self {
+            document,
+            document_widget,
+        }
+    }
+}
+
+impl eframe::App for HostApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::TopBottomPanel::top("menu").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                self.document_widget.view_menu_ui(ui);
+                self.document_widget.layer_menu_ui(ui);
+            });
+        });
+
+        egui::SidePanel::left("controls").show(ctx, |ui| {
+            ui.heading("Host app");
+            if ui.button("Add circle").clicked() {
+                let count = self
+                    .document
+                    .try_get(1)
+                    .map_or(0, |layer| layer.paths.len());
+                self.document
+                    .push_path(1, kurbo::Circle::new((200., 300.), 10.0 * count as f64));
+                self.document_widget
+                    .set_document_data(DocumentData::new(&self.document));
+            }
+        });
+
+        self.document_widget.handle_shortcuts(ctx);
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none())
+            .show(ctx, |ui| self.document_widget.ui(ui));
+    }
+}
+
+fn main() -> eframe::Result<()> {
+    // the window's resources, including the widget's GPU buffers, are dropped when it is closed
+    eframe::run_native(
+        "vsvg-viewer embedded",
+        eframe::NativeOptions::default(),
+        Box::new(|cc| Box::new(HostApp::new(cc))),
+    )
+}
@@ -0,0 +1,107 @@
+use egui::Ui;
+use vsvg::{DocumentTrait, FlattenedDocument, LayerID, LayerTrait, PathTrait, PlotParams, Unit};
+
+/// Statistics about the visible part of a document, as displayed in the stats panel.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DocumentStats {
+    /// total number of layers
+    layer_count: usize,
+
+    /// number of visible layers
+    visible_layer_count: usize,
+
+    path_count: usize,
+    point_count: usize,
+    length: f64,
+    pen_up_distance: f64,
+    bounds: Option<kurbo::Rect>,
+}
+
+impl DocumentStats {
+    /// Compute statistics for the layers of `document` for which `visible` returns `true`.
+    pub(crate) fn new(document: &FlattenedDocument, visible: impl Fn(LayerID) -> bool) -> Self {
+        let mut stats = Self {
+            layer_count: document.layers.len(),
+            ..Default::default()
+        };
+
+        for layer in document
+            .layers
+            .iter()
+            .filter_map(|(lid, layer)| visible(*lid).then_some(layer))
+        {
+            stats.visible_layer_count += 1;
+            stats.path_count += layer.paths.len();
+            stats.point_count += layer
+                .paths
+                .iter()
+                .map(|path| path.data().points().len())
+                .sum::<usize>();
+            stats.length += layer.length();
+            stats.bounds = match (stats.bounds, layer.bounds()) {
+                (Some(a), Some(b)) => Some(a.union(b)),
+                (a, b) => a.or(b),
+            };
+        }
+        stats.pen_up_distance = document.pen_up_distance_filtered(|lid, _| visible(lid));
+
+        stats
+    }
+
+    pub(crate) fn ui(&self, ui: &mut Ui) {
+        let mm = |px: f64| format!("{:.1} mm", px / Unit::Mm.to_px());
+
+        egui::Grid::new("document_stats")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Layers:");
+                ui.label(format!(
+                    "{} / {}",
+                    self.visible_layer_count, self.layer_count
+                ));
+                ui.end_row();
+
+                ui.label("Paths:");
+                ui.label(self.path_count.to_string());
+                ui.end_row();
+
+                ui.label("Points:");
+                ui.label(self.point_count.to_string());
+                ui.end_row();
+
+                ui.label("Length:");
+                ui.label(mm(self.length));
+                ui.end_row();
+
+                ui.label("Pen-up distance:");
+                ui.label(mm(self.pen_up_distance));
+                ui.end_row();
+
+                ui.label("Plot time:");
+                let secs = PlotParams::default()
+                    .estimate(self.length, self.pen_up_distance, self.path_count)
+                    .as_secs();
+                ui.label(format!(
+                    "{}:{:02}:{:02}",
+                    secs / 3600,
+                    (secs / 60) % 60,
+                    secs % 60
+                ))
+                .on_hover_text("Estimated with default plotter speeds");
+                ui.end_row();
+
+                ui.label("Bounds:");
+                if let Some(bounds) = self.bounds {
+                    ui.label(format!("{} x {}", mm(bounds.width()), mm(bounds.height())));
+                    ui.end_row();
+
+                    ui.label("Origin:");
+                    ui.label(format!("{}, {}", mm(bounds.x0), mm(bounds.y0)));
+                } else {
+                    ui.label("n/a");
+                }
+                ui.end_row();
+            });
+    }
+}
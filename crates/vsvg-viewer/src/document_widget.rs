@@ -1,8 +1,49 @@
-use crate::engine::{DisplayMode, DocumentData, Engine, ViewerOptions};
+use crate::document_stats::DocumentStats;
+use crate::engine::{DisplayMode, DocumentData, Engine, LayerDisplay, ShowOptions, ViewerOptions};
+use crate::measure_tool::{snap_to_grid, MeasureTool};
 use eframe::egui_wgpu;
 use egui::{Pos2, Rect, Sense, Ui};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use vsvg::{DocumentTrait, LayerTrait};
+use vsvg::{Color, DocumentTrait, LayerID, LayerTrait, PathTrait, Unit};
+
+const VIEWER_OPTIONS_STORAGE_KEY: &str = "vsvg-viewer-options";
+
+const FIT_TO_VIEW_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num0);
+
+/// Shortcuts handled by [`DocumentWidget::handle_shortcuts`], with their description.
+pub(crate) const SHORTCUTS: [(&str, &str); 4] = [
+    ("G", "Toggle grid"),
+    ("P", "Toggle points"),
+    ("1–9", "Toggle the visibility of the first nine layers"),
+    ("F", "Fit to view"),
+];
+
+/// Margin around the content when fitting to view, as a fraction of the viewport.
+const FIT_TO_VIEW_MARGIN: f32 = 0.05;
+
+/// Delay after the last tolerance change before the document is flattened again, in seconds.
+const TOLERANCE_DEBOUNCE_DELAY: f64 = 0.3;
+
+/// Thickness of the rulers, in screen pixels.
+const RULER_SIZE: f32 = 20.0;
+
+/// Candidate spacings for ruler ticks, in millimeters.
+const RULER_STEPS_MM: [f64; 9] = [1., 5., 10., 50., 100., 500., 1000., 5000., 10000.];
+
+/// Minimum spacing between labelled ruler ticks, in screen pixels.
+const RULER_MIN_LABEL_SPACING: f64 = 50.0;
+
+/// Minimum spacing between unlabelled ruler ticks, in screen pixels.
+const RULER_MIN_TICK_SPACING: f64 = 5.0;
+
+/// Minimum spacing between grid lines, in screen pixels.
+const GRID_MIN_SPACING: f64 = 20.0;
+
+/// Value derived from the document data and layer visibility, along with the data and visibility
+/// it was computed for.
+type VisibilityCache<T> = Option<(Arc<DocumentData>, HashMap<LayerID, bool>, T)>;
 
 /// Widget to display a [`vsvg::Document`] in an egui application.
 ///
@@ -12,6 +53,10 @@ use vsvg::{DocumentTrait, LayerTrait};
 /// It supports multiple UI features:
 ///  - GPU-accelerated rendering of the document, typically in the central panel
 ///  - helper UI functions to act on the widget state (e.g. viewing options and layer visibility)
+///
+/// The widget can be hosted in any eframe app using the wgpu renderer (see the `embedded`
+/// example), or as part of a [`crate::Viewer`]. Only one widget is supported per app, since its
+/// renderer is stored in the app's wgpu callback resources.
 #[derive(Debug, Default)]
 pub struct DocumentWidget {
     /// polylines derived from the document
@@ -34,6 +79,18 @@ pub struct DocumentWidget {
 
     /// should fit to view flag
     must_fit_to_view: bool,
+
+    /// distance measurement tool
+    measure_tool: MeasureTool,
+
+    /// time of the last change of the tolerance slider
+    last_tolerance_change: f64,
+
+    /// cached statistics of the visible layers
+    stats_cache: VisibilityCache<DocumentStats>,
+
+    /// cached bounds of the visible layers
+    bounds_cache: VisibilityCache<Option<kurbo::Rect>>,
 }
 
 impl DocumentWidget {
@@ -49,7 +106,11 @@ impl DocumentWidget {
         let wgpu_render_state = cc.wgpu_render_state.as_ref()?;
 
         // prepare engine
-        let mut engine = Engine::new(wgpu_render_state, viewer_options.clone());
+        let mut engine = Engine::new(
+            wgpu_render_state,
+            cc.egui_ctx.clone(),
+            viewer_options.clone(),
+        );
         engine.set_document_data(&document_data);
 
         // Because the graphics pipeline must have the same lifetime as the egui render pass,
@@ -58,25 +119,204 @@ impl DocumentWidget {
         let callback_resources = &mut wgpu_render_state.renderer.write().paint_callback_resources;
         callback_resources.insert(engine);
 
-        Some(Self {
+        let widget = Self {
             document_data,
             viewer_options,
             new_document_data: None,
             offset: Pos2::ZERO,
             scale: 1.0,
             must_fit_to_view: true,
-        })
+            measure_tool: MeasureTool::default(),
+            last_tolerance_change: 0.0,
+            stats_cache: None,
+            bounds_cache: None,
+        };
+        widget.hide_hidden_layers();
+
+        Some(widget)
     }
 
     pub fn set_document_data(&mut self, doc_data: DocumentData) {
         self.new_document_data = Some(Arc::new(doc_data));
     }
 
+    /// Restore the viewer options (display mode, toggles, layer visibility, etc.) from storage.
+    pub fn load(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(viewer_options) =
+            eframe::get_value::<ViewerOptions>(storage, VIEWER_OPTIONS_STORAGE_KEY)
+        {
+            let mut current = self.viewer_options.lock().unwrap();
+
+            // a tolerance already set for the session is kept
+            let session_tolerance = current.persisted_tolerance.map(|_| current.tolerance);
+            *current = viewer_options;
+            if let Some(tolerance) = session_tolerance {
+                current.persisted_tolerance = Some(current.tolerance);
+                current.tolerance = tolerance;
+            }
+            current.restore_layer_options(self.document_source());
+        }
+        self.hide_hidden_layers();
+    }
+
+    /// Save the viewer options to storage.
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        let mut viewer_options = self.viewer_options.lock().unwrap();
+        viewer_options.save_layer_options(self.document_source());
+
+        // a tolerance set for the session is not persisted
+        let tolerance = viewer_options.tolerance;
+        if let Some(persisted_tolerance) = viewer_options.persisted_tolerance {
+            viewer_options.tolerance = persisted_tolerance;
+        }
+        eframe::set_value(storage, VIEWER_OPTIONS_STORAGE_KEY, &*viewer_options);
+        viewer_options.tolerance = tolerance;
+    }
+
+    /// Source of the current document, which identifies it across sessions.
+    fn document_source(&self) -> Option<&str> {
+        self.document_data.document.metadata().source.as_deref()
+    }
+
+    /// Export the visible layers and the page to a PNG file.
+    ///
+    /// The image covers the page (or the visible content, if the document has no page size) at
+    /// the given resolution, assuming 96 pixels per inch for SVG coordinates.
+    pub fn export_png(&self, path: impl AsRef<std::path::Path>, dpi: f64) -> anyhow::Result<()> {
+        let flattened_document = &self.document_data.flattened_document;
+        let viewer_options = self.viewer_options.lock().unwrap();
+        let visible =
+            |lid: usize, _: &_| *viewer_options.layer_visibility.get(&lid).unwrap_or(&true);
+
+        let bounds = flattened_document
+            .metadata()
+            .page_dimensions()
+            .filter(|(w, h)| *w != 0.0 && *h != 0.0)
+            .map(|(w, h)| kurbo::Rect::new(0., 0., w, h))
+            .or_else(|| flattened_document.bounds_filtered(visible))
+            .ok_or_else(|| anyhow::anyhow!("nothing to export"))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (width, height) = (
+            (bounds.width() * dpi / 96.0).ceil().max(1.0) as u32,
+            (bounds.height() * dpi / 96.0).ceil().max(1.0) as u32,
+        );
+
+        flattened_document
+            .render_filtered(width, height, visible)?
+            .save_png(path)?;
+        Ok(())
+    }
+
+    /// Export the visible layers to an SVG file.
+    ///
+    /// The source document is exported, so curves are kept regardless of the display tolerance.
+    pub fn export_svg(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let layer_visibility = self.viewer_options.lock().unwrap().layer_visibility.clone();
+        self.document_data
+            .document
+            .visible_subset(&layer_visibility)
+            .to_svg_file(path)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn show_stats(&self) -> bool {
+        self.viewer_options.lock().unwrap().show_stats
+    }
+
+    /// Display statistics about the visible layers (path count, length, bounds, etc.).
+    pub fn stats_ui(&mut self, ui: &mut Ui) {
+        let layer_visibility = self.viewer_options.lock().unwrap().layer_visibility.clone();
+
+        let up_to_date = self
+            .stats_cache
+            .as_ref()
+            .is_some_and(|(data, visibility, _)| {
+                Arc::ptr_eq(data, &self.document_data) && *visibility == layer_visibility
+            });
+        if !up_to_date {
+            let stats = DocumentStats::new(&self.document_data.flattened_document, |lid| {
+                *layer_visibility.get(&lid).unwrap_or(&true)
+            });
+            self.stats_cache = Some((self.document_data.clone(), layer_visibility, stats));
+        }
+
+        if let Some((_, _, stats)) = &self.stats_cache {
+            stats.ui(ui);
+        }
+    }
+
+    #[must_use]
+    pub fn dark_mode(&self) -> bool {
+        self.viewer_options.lock().unwrap().dark_mode
+    }
+
+    pub fn set_dark_mode(&mut self, dark_mode: bool) {
+        self.viewer_options.lock().unwrap().dark_mode = dark_mode;
+    }
+
+    /// Canvas and page colors used in light mode.
+    #[must_use]
+    pub fn show_options(&self) -> ShowOptions {
+        self.viewer_options.lock().unwrap().show_options.clone()
+    }
+
+    /// Set the tolerance used to flatten the document for display, for this session only.
+    ///
+    /// The tolerance previously loaded from storage (see [`DocumentWidget::load`]) is the one
+    /// saved, unless the user picks another one in the view menu.
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        let mut viewer_options = self.viewer_options.lock().unwrap();
+        if viewer_options.persisted_tolerance.is_none() {
+            viewer_options.persisted_tolerance = Some(viewer_options.tolerance);
+        }
+        viewer_options.tolerance = tolerance;
+    }
+
+    /// Set the window and display settings, e.g. the canvas and page colors.
+    ///
+    /// They are not persisted, see [`ShowOptions`].
+    pub fn set_show_options(&mut self, show_options: ShowOptions) {
+        self.viewer_options.lock().unwrap().show_options = show_options;
+    }
+
+    /// Fill color of the area around the page, accounting for the dark mode.
+    #[must_use]
+    pub fn canvas_color(&self) -> vsvg::Color {
+        self.viewer_options
+            .lock()
+            .unwrap()
+            .effective_show_options()
+            .canvas_color
+    }
+
     #[allow(clippy::missing_panics_doc)]
     pub fn ui(&mut self, ui: &mut Ui) {
         let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
 
+        self.update_tolerance(ui);
+
+        let new_doc_data = self.new_document_data.take();
+        if let Some(new_doc_data) = new_doc_data.clone() {
+            // re-flattening with another tolerance keeps the same document
+            let new_document = !Arc::ptr_eq(&new_doc_data.document, &self.document_data.document);
+            if new_document {
+                let mut viewer_options = self.viewer_options.lock().unwrap();
+                viewer_options.save_layer_options(self.document_source());
+                viewer_options
+                    .restore_layer_options(new_doc_data.document.metadata().source.as_deref());
+            }
+            self.document_data = new_doc_data;
+            if new_document {
+                self.hide_hidden_layers();
+            }
+        }
+
         // fit to view on request
+        if ui.input_mut(|i| i.consume_shortcut(&FIT_TO_VIEW_SHORTCUT)) {
+            self.must_fit_to_view = true;
+        }
         if self.must_fit_to_view {
             self.fit_to_view(&rect);
         }
@@ -85,7 +325,10 @@ impl DocumentWidget {
         let old_offset = self.offset;
         let old_scale = self.scale;
         response.ctx.input(|i| {
-            self.offset -= response.drag_delta() / self.scale;
+            // dragging is used by the measure tool when enabled
+            if !self.measure_tool.enabled {
+                self.offset -= response.drag_delta() / self.scale;
+            }
 
             if let Some(mut pos) = response.hover_pos() {
                 self.offset -= i.scroll_delta / self.scale;
@@ -105,6 +348,21 @@ impl DocumentWidget {
             self.must_fit_to_view = false;
         }
 
+        let (offset, scale) = (self.offset, self.scale);
+        let to_document = move |pos: Pos2| offset + (pos - rect.min) / scale;
+        let to_screen = move |pos: Pos2| rect.min + (pos - offset) * scale;
+
+        // measured points are optionally snapped to the grid
+        #[allow(clippy::cast_possible_truncation)]
+        let snap_spacing = if self.measure_tool.snap {
+            (self.viewer_options.lock().unwrap().grid_spacing * Unit::Mm.to_px()) as f32
+        } else {
+            0.0
+        };
+        let to_measured = move |pos: Pos2| snap_to_grid(to_document(pos), snap_spacing);
+        self.measure_tool
+            .handle_input(&response, self.scale, to_measured);
+
         // The callback function for WGPU is in two stages: prepare, and paint.
         //
         // The prepare callback is called every frame before paint and is given access to the wgpu
@@ -122,11 +380,6 @@ impl DocumentWidget {
         let scale = self.scale;
         let origin = cgmath::Point2::new(self.offset.x, self.offset.y);
 
-        let new_doc_data = self.new_document_data.take();
-        if let Some(new_doc_data) = new_doc_data.clone() {
-            self.document_data = new_doc_data;
-        }
-
         let cb = egui_wgpu::CallbackFn::new()
             .prepare(move |device, queue, _encoder, paint_callback_resources| {
                 let engine: &mut Engine = paint_callback_resources.get_mut().unwrap();
@@ -148,32 +401,447 @@ impl DocumentWidget {
         };
 
         ui.painter().add(callback);
+
+        if self.viewer_options.lock().unwrap().show_grid {
+            self.grid_ui(ui, rect);
+        }
+
+        if self.viewer_options.lock().unwrap().show_bounds {
+            let bounds = self.visible_bounds();
+            self.bounds_ui(ui, rect, bounds, to_screen);
+        }
+
+        if self.viewer_options.lock().unwrap().show_rulers {
+            self.rulers_ui(ui, rect);
+        }
+
+        let unit = self.document_data.document.metadata().unit;
+        self.measure_tool
+            .paint(ui, &response, to_measured, to_screen, unit);
+
+        if let Some(pos) = response.hover_pos() {
+            Self::cursor_ui(ui, rect, to_document(pos));
+        }
     }
 
-    pub fn view_menu_ui(&mut self, ui: &mut Ui) {
-        ui.menu_button("View", |ui| {
-            ui.menu_button("Display Mode", |ui| {
-                if ui
-                    .radio_value(
-                        &mut self.viewer_options.lock().unwrap().display_mode,
-                        DisplayMode::Preview,
-                        "Preview",
-                    )
-                    .clicked()
-                {
-                    ui.close_menu();
-                };
-                if ui
-                    .radio_value(
-                        &mut self.viewer_options.lock().unwrap().display_mode,
-                        DisplayMode::Outline,
-                        "Outline",
-                    )
-                    .clicked()
-                {
-                    ui.close_menu();
-                };
+    /// Show the document coordinates of the cursor, in millimeters, at the bottom of the view.
+    fn cursor_ui(ui: &Ui, rect: Rect, pos: Pos2) {
+        let mm = Unit::Mm.to_px();
+        let text = format!(
+            "x: {:.2} mm, y: {:.2} mm",
+            f64::from(pos.x) / mm,
+            f64::from(pos.y) / mm
+        );
+
+        let painter = ui.painter_at(rect);
+        let galley = painter.layout_no_wrap(
+            text,
+            egui::FontId::proportional(12.0),
+            ui.visuals().text_color(),
+        );
+        let anchor = rect.left_bottom() + egui::vec2(6.0, -6.0 - galley.size().y);
+        let background = egui::Rect::from_min_size(anchor, galley.size()).expand(3.0);
+        painter.rect_filled(background, 3.0, ui.visuals().extreme_bg_color);
+        painter.galley(anchor, galley);
+    }
+
+    /// Flatten the document right away if it was never flattened, or again if the tolerance
+    /// changed and the slider settled.
+    fn update_tolerance(&mut self, ui: &Ui) {
+        let tolerance = self.viewer_options.lock().unwrap().tolerance;
+        let current = self
+            .new_document_data
+            .as_ref()
+            .unwrap_or(&self.document_data);
+
+        let elapsed = ui.input(|i| i.time) - self.last_tolerance_change;
+        match current.tolerance {
+            #[allow(clippy::float_cmp)]
+            Some(current_tolerance) if current_tolerance == tolerance => {}
+            Some(_) if elapsed < TOLERANCE_DEBOUNCE_DELAY => {
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs_f64(
+                        TOLERANCE_DEBOUNCE_DELAY - elapsed,
+                    ));
+            }
+            _ => {
+                self.new_document_data = Some(Arc::new(DocumentData::with_tolerance(
+                    current.document.clone(),
+                    tolerance,
+                )));
+            }
+        }
+    }
+
+    /// Handle the single-key shortcuts listed in [`SHORTCUTS`].
+    ///
+    /// Nothing happens while a text field has keyboard focus.
+    pub fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        const LAYER_KEYS: [egui::Key; 9] = [
+            egui::Key::Num1,
+            egui::Key::Num2,
+            egui::Key::Num3,
+            egui::Key::Num4,
+            egui::Key::Num5,
+            egui::Key::Num6,
+            egui::Key::Num7,
+            egui::Key::Num8,
+            egui::Key::Num9,
+        ];
+
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let layer_ids: Vec<_> = self
+            .document_data
+            .flattened_document
+            .layers
+            .keys()
+            .copied()
+            .collect();
+
+        ctx.input(|i| {
+            if !i.modifiers.is_none() {
+                return;
+            }
+
+            let mut viewer_options = self.viewer_options.lock().unwrap();
+            if i.key_pressed(egui::Key::G) {
+                viewer_options.show_grid = !viewer_options.show_grid;
+            }
+            if i.key_pressed(egui::Key::P) {
+                viewer_options.show_point = !viewer_options.show_point;
+            }
+            for (key, lid) in LAYER_KEYS.iter().zip(&layer_ids) {
+                if i.key_pressed(*key) {
+                    let visibility = viewer_options.layer_visibility.entry(*lid).or_insert(true);
+                    *visibility = !*visibility;
+                }
+            }
+            if i.key_pressed(egui::Key::F) {
+                self.must_fit_to_view = true;
+            }
+        });
+    }
+
+    /// Bounds of the visible layers, recomputed when the document or the layer visibility change.
+    fn visible_bounds(&mut self) -> Option<kurbo::Rect> {
+        let layer_visibility = self.viewer_options.lock().unwrap().layer_visibility.clone();
+
+        let up_to_date = self
+            .bounds_cache
+            .as_ref()
+            .is_some_and(|(data, visibility, _)| {
+                Arc::ptr_eq(data, &self.document_data) && *visibility == layer_visibility
+            });
+        if !up_to_date {
+            let bounds = self
+                .document_data
+                .flattened_document
+                .bounds_filtered(|lid, _| *layer_visibility.get(&lid).unwrap_or(&true));
+            self.bounds_cache = Some((self.document_data.clone(), layer_visibility, bounds));
+        }
+
+        self.bounds_cache
+            .as_ref()
+            .and_then(|(_, _, bounds)| *bounds)
+    }
+
+    /// Draw `bounds` as a dashed rectangle labelled with its dimensions, and label the page with
+    /// its dimensions.
+    ///
+    /// `to_screen` converts document coordinates to screen coordinates.
+    #[allow(clippy::cast_possible_truncation)]
+    fn bounds_ui(
+        &self,
+        ui: &Ui,
+        rect: Rect,
+        bounds: Option<kurbo::Rect>,
+        to_screen: impl Fn(Pos2) -> Pos2,
+    ) {
+        let painter = ui.painter_at(rect);
+        let font = egui::FontId::proportional(11.0);
+        let color = ui.visuals().text_color();
+        let to_screen = |x: f64, y: f64| to_screen(Pos2::new(x as f32, y as f32));
+
+        // rounded to 0.1 mm, without trailing zeros
+        let size_label = |w: f64, h: f64| {
+            let mm = |v: f64| (v / Unit::Mm.to_px() * 10.0).round() / 10.0;
+            format!("{} × {} mm", mm(w), mm(h))
+        };
+
+        let page_size = self
+            .document_data
+            .flattened_document
+            .metadata()
+            .page_size
+            .filter(|page_size| page_size.w() != 0.0 && page_size.h() != 0.0);
+        if let Some(page_size) = page_size {
+            painter.text(
+                to_screen(0., 0.) - egui::vec2(0.0, 4.0),
+                egui::Align2::LEFT_BOTTOM,
+                size_label(page_size.w(), page_size.h()),
+                font.clone(),
+                color,
+            );
+        }
+
+        if let Some(bounds) = bounds {
+            let (min, max) = (
+                to_screen(bounds.x0, bounds.y0),
+                to_screen(bounds.x1, bounds.y1),
+            );
+            let stroke = egui::Stroke::new(1.0, color);
+            let corners = [
+                min,
+                egui::pos2(max.x, min.y),
+                max,
+                egui::pos2(min.x, max.y),
+                min,
+            ];
+            painter.extend(egui::Shape::dashed_line(&corners, stroke, 4.0, 4.0));
+            painter.text(
+                egui::pos2(min.x, max.y + 4.0),
+                egui::Align2::LEFT_TOP,
+                size_label(bounds.width(), bounds.height()),
+                font,
+                color,
+            );
+        }
+    }
+
+    /// Draw a millimeter grid aligned on the document origin, with the configured spacing.
+    ///
+    /// Every `grid_major_every` line is a major line, labelled with its coordinate. When zoomed
+    /// out, minor lines are skipped so that lines stay at least [`GRID_MIN_SPACING`] apart, but
+    /// they are always drawn at exact multiples of the spacing.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn grid_ui(&self, ui: &Ui, rect: Rect) {
+        let painter = ui.painter_at(rect);
+        let minor_stroke = egui::Stroke::new(
+            1.0,
+            egui::Color32::from_rgba_unmultiplied(128, 128, 128, 48),
+        );
+        let major_stroke = egui::Stroke::new(
+            1.0,
+            egui::Color32::from_rgba_unmultiplied(128, 128, 128, 112),
+        );
+        let font = egui::FontId::proportional(9.0);
+        let text_color = ui.visuals().weak_text_color();
+
+        let (spacing, major_every) = {
+            let viewer_options = self.viewer_options.lock().unwrap();
+            (
+                viewer_options.grid_spacing,
+                i64::from(viewer_options.grid_major_every.max(1)),
+            )
+        };
+        if spacing <= 0.0 || !spacing.is_finite() {
+            return;
+        }
+
+        // skip lines (by multiples of the major interval) until they are far enough apart
+        let mm_scale = Unit::Mm.to_px() * f64::from(self.scale);
+        let mut every = 1;
+        while spacing * mm_scale * (every as f64) < GRID_MIN_SPACING && every < i64::MAX / 1000 {
+            every *= if every == 1 { major_every.max(2) } else { 10 };
+        }
+        let step = spacing * Unit::Mm.to_px() * every as f64;
+
+        // yields `(screen_pos, mm, is_major)` for each line within the viewport, with
+        // `screen_pos` relative to the viewport's edge
+        let lines = |offset: f32, size: f32| {
+            let first = (f64::from(offset) / step).floor();
+            let last = (f64::from(offset + size / self.scale) / step).ceil();
+            (first as i64..=last as i64).map(move |k| {
+                let index = k * every;
+                (
+                    (k as f64 * step - f64::from(offset)) as f32 * self.scale,
+                    // avoid displaying rounding errors in labels
+                    (index as f64 * spacing * 1000.0).round() / 1000.0,
+                    index % major_every == 0,
+                )
+            })
+        };
+
+        // keep labels clear of the rulers
+        let label_margin = if self.viewer_options.lock().unwrap().show_rulers {
+            RULER_SIZE + 2.0
+        } else {
+            2.0
+        };
+
+        for (x, mm, major) in lines(self.offset.x, rect.width()) {
+            let x = rect.min.x + x;
+            painter.line_segment(
+                [egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)],
+                if major { major_stroke } else { minor_stroke },
+            );
+            if major {
+                painter.text(
+                    egui::pos2(x + 2.0, rect.min.y + label_margin),
+                    egui::Align2::LEFT_TOP,
+                    format!("{mm}"),
+                    font.clone(),
+                    text_color,
+                );
+            }
+        }
+        for (y, mm, major) in lines(self.offset.y, rect.height()) {
+            let y = rect.min.y + y;
+            painter.line_segment(
+                [egui::pos2(rect.min.x, y), egui::pos2(rect.max.x, y)],
+                if major { major_stroke } else { minor_stroke },
+            );
+            if major {
+                painter.text(
+                    egui::pos2(rect.min.x + label_margin, y - 2.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!("{mm}"),
+                    font.clone(),
+                    text_color,
+                );
+            }
+        }
+    }
+
+    /// Draw millimeter rulers along the top and left edges of the viewport.
+    #[allow(clippy::cast_possible_truncation)]
+    fn rulers_ui(&self, ui: &Ui, rect: Rect) {
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        let stroke = visuals.widgets.noninteractive.fg_stroke;
+        let font = egui::FontId::proportional(9.0);
+
+        // screen pixels per millimeter
+        let mm_scale = f64::from(self.scale) * Unit::Mm.to_px();
+        let label_idx = RULER_STEPS_MM
+            .iter()
+            .position(|step| step * mm_scale >= RULER_MIN_LABEL_SPACING)
+            .unwrap_or(RULER_STEPS_MM.len() - 1);
+        let label_step = RULER_STEPS_MM[label_idx];
+        let tick_step = label_idx
+            .checked_sub(1)
+            .map(|idx| RULER_STEPS_MM[idx])
+            .filter(|step| step * mm_scale >= RULER_MIN_TICK_SPACING)
+            .unwrap_or(label_step);
+
+        let top = Rect::from_min_size(rect.min, egui::vec2(rect.width(), RULER_SIZE));
+        let left = Rect::from_min_size(rect.min, egui::vec2(RULER_SIZE, rect.height()));
+        painter.rect_filled(top, 0.0, visuals.extreme_bg_color);
+        painter.rect_filled(left, 0.0, visuals.extreme_bg_color);
+
+        // yields `(screen_pos, mm, is_labelled)` for each tick within the viewport, with
+        // `screen_pos` relative to the viewport's edge
+        #[allow(clippy::cast_precision_loss)]
+        let ticks = |offset: f32, len: f32| {
+            let start_mm = f64::from(offset) / Unit::Mm.to_px();
+            let end_mm = start_mm + f64::from(len) / mm_scale;
+            let label_every = (label_step / tick_step).round() as i64;
+
+            ((start_mm / tick_step).ceil() as i64..=(end_mm / tick_step).floor() as i64).map(
+                move |k| {
+                    let mm = k as f64 * tick_step;
+                    let pos = ((mm - start_mm) * mm_scale) as f32;
+                    (pos, mm, k % label_every == 0)
+                },
+            )
+        };
+
+        for (x, mm, is_labelled) in ticks(self.offset.x, rect.width()) {
+            let x = rect.min.x + x;
+            let len = if is_labelled { 0.6 } else { 0.3 } * RULER_SIZE;
+            painter.line_segment(
+                [egui::pos2(x, top.max.y - len), egui::pos2(x, top.max.y)],
+                stroke,
+            );
+            if is_labelled {
+                painter.text(
+                    egui::pos2(x + 2.0, top.min.y + 1.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("{mm:.0}"),
+                    font.clone(),
+                    stroke.color,
+                );
+            }
+        }
+
+        for (y, mm, is_labelled) in ticks(self.offset.y, rect.height()) {
+            let y = rect.min.y + y;
+            if y < top.max.y {
+                continue;
+            }
+            let len = if is_labelled { 0.6 } else { 0.3 } * RULER_SIZE;
+            painter.line_segment(
+                [egui::pos2(left.max.x - len, y), egui::pos2(left.max.x, y)],
+                stroke,
+            );
+            if is_labelled {
+                painter.text(
+                    egui::pos2(left.min.x + 1.0, y + 1.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("{mm:.0}"),
+                    font.clone(),
+                    stroke.color,
+                );
+            }
+        }
+    }
+
+    fn display_mode_menu_ui(&self, ui: &mut Ui) {
+        ui.menu_button("Display Mode", |ui| {
+            if ui
+                .radio_value(
+                    &mut self.viewer_options.lock().unwrap().display_mode,
+                    DisplayMode::Preview,
+                    "Preview",
+                )
+                .clicked()
+            {
+                ui.close_menu();
+            }
+            if ui
+                .radio_value(
+                    &mut self.viewer_options.lock().unwrap().display_mode,
+                    DisplayMode::Outline,
+                    "Outline",
+                )
+                .clicked()
+            {
+                ui.close_menu();
+            }
+        });
+    }
+
+    fn grid_menu_ui(&mut self, ui: &mut Ui) {
+        let mut viewer_options = self.viewer_options.lock().unwrap();
+        ui.checkbox(&mut viewer_options.show_grid, "Show grid");
+        ui.add_enabled_ui(viewer_options.show_grid, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Grid spacing:");
+                ui.add(
+                    egui::DragValue::new(&mut viewer_options.grid_spacing)
+                        .clamp_range(0.1..=1000.0)
+                        .speed(0.1)
+                        .suffix(" mm"),
+                );
             });
+            ui.horizontal(|ui| {
+                ui.label("Major line every:");
+                ui.add(
+                    egui::DragValue::new(&mut viewer_options.grid_major_every)
+                        .clamp_range(1..=100)
+                        .suffix(" lines"),
+                );
+            });
+        });
+    }
+
+    /// Returns `true` if the menu is open.
+    pub fn view_menu_ui(&mut self, ui: &mut Ui) -> bool {
+        ui.menu_button("View", |ui| {
+            self.display_mode_menu_ui(ui);
             ui.separator();
             ui.checkbox(
                 &mut self.viewer_options.lock().unwrap().show_point,
@@ -181,19 +849,78 @@ impl DocumentWidget {
             );
             ui.checkbox(
                 &mut self.viewer_options.lock().unwrap().show_pen_up,
-                "Show pen-up trajectories",
-            );
+                "Show travel",
+            )
+            .on_hover_text("Show pen-up moves between consecutive paths, in plot order");
             ui.checkbox(
                 &mut self.viewer_options.lock().unwrap().show_control_points,
                 "Show control points",
             );
+            self.grid_menu_ui(ui);
+            let fill_changed = ui
+                .checkbox(
+                    &mut self.viewer_options.lock().unwrap().fill_closed_paths,
+                    "Fill closed paths",
+                )
+                .on_hover_text("Fill closed paths with a translucent version of their color")
+                .changed();
+            if fill_changed {
+                // the fill is baked in the painter data, which must be rebuilt
+                self.new_document_data
+                    .get_or_insert_with(|| self.document_data.clone());
+            }
+            ui.checkbox(
+                &mut self.viewer_options.lock().unwrap().show_rulers,
+                "Show rulers",
+            );
+            ui.checkbox(
+                &mut self.viewer_options.lock().unwrap().show_bounds,
+                "Show bounds",
+            )
+            .on_hover_text("Show the bounding box of the visible layers and the page dimensions");
+            ui.checkbox(
+                &mut self.viewer_options.lock().unwrap().show_stats,
+                "Show statistics",
+            );
+            ui.checkbox(&mut self.measure_tool.enabled, "Measure tool")
+                .on_hover_text("Click two points (or drag) to measure distance and angle");
+            ui.add_enabled(
+                self.measure_tool.enabled,
+                egui::Checkbox::new(&mut self.measure_tool.snap, "Snap to grid"),
+            )
+            .on_hover_text("Snap measured points to the nearest grid intersection");
+            ui.checkbox(
+                &mut self.viewer_options.lock().unwrap().dark_mode,
+                "Dark mode",
+            );
             ui.separator();
-            if ui.button("Fit to view").clicked() {
+            if ui
+                .add(
+                    egui::Button::new("Fit to view")
+                        .shortcut_text(ui.ctx().format_shortcut(&FIT_TO_VIEW_SHORTCUT)),
+                )
+                .clicked()
+            {
                 self.must_fit_to_view = true;
                 ui.close_menu();
             }
 
             ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Tolerance:");
+                let mut viewer_options = self.viewer_options.lock().unwrap();
+                let changed = ui
+                    .add(
+                        egui::Slider::new(&mut viewer_options.tolerance, 0.001..=1.0)
+                            .logarithmic(true),
+                    )
+                    .changed();
+                if changed {
+                    // the user's choice is persisted
+                    viewer_options.persisted_tolerance = None;
+                    self.last_tolerance_change = ui.input(|i| i.time);
+                }
+            });
             ui.horizontal(|ui| {
                 ui.label("AA:");
                 ui.add(egui::Slider::new(
@@ -201,51 +928,121 @@ impl DocumentWidget {
                     0.0..=2.0,
                 ));
             })
-        });
+        })
+        .inner
+        .is_some()
     }
 
+    /// Returns `true` if the menu is open.
     #[allow(clippy::missing_panics_doc)]
-    pub fn layer_menu_ui(&mut self, ui: &mut Ui) {
+    pub fn layer_menu_ui(&mut self, ui: &mut Ui) -> bool {
         ui.menu_button("Layer", |ui| {
+            let mut display_changed = false;
             for (lid, layer) in &self.document_data.flattened_document.layers {
                 let mut viewer_options = self.viewer_options.lock().unwrap();
+                let viewer_options = &mut *viewer_options;
                 let visibility = viewer_options.layer_visibility.entry(*lid).or_insert(true);
                 let mut label = format!("Layer {lid}");
                 if !layer.metadata().name.is_empty() {
-                    label.push_str(&format!(": {}", layer.metadata().name));
+                    label.push_str(": ");
+                    label.push_str(&layer.metadata().name);
                 }
 
-                ui.checkbox(visibility, label);
+                ui.horizontal(|ui| {
+                    ui.checkbox(visibility, label);
+
+                    let mut display = viewer_options
+                        .layer_display
+                        .get(lid)
+                        .copied()
+                        .unwrap_or_default();
+                    let own_color = layer
+                        .paths
+                        .first()
+                        .map_or(Color::BLACK, |path| path.metadata().color);
+                    let color = display.color.unwrap_or(own_color);
+                    let mut rgb = [color.r, color.g, color.b];
+
+                    let mut changed = egui::color_picker::color_edit_button_srgb(ui, &mut rgb)
+                        .on_hover_text("Display color of the layer")
+                        .changed();
+                    if changed {
+                        display.color = Some(Color::rgb(rgb[0], rgb[1], rgb[2]));
+                    }
+                    changed |= ui
+                        .add(egui::Slider::new(&mut display.opacity, 0.0..=1.0).text("opacity"))
+                        .changed();
+                    if ui
+                        .add_enabled(
+                            display != LayerDisplay::default(),
+                            egui::Button::new("Reset"),
+                        )
+                        .on_hover_text("Display the paths with their own color")
+                        .clicked()
+                    {
+                        display = LayerDisplay::default();
+                        changed = true;
+                    }
+
+                    if changed {
+                        viewer_options.layer_display.insert(*lid, display);
+                        display_changed = true;
+                    }
+                });
             }
-        });
+
+            // the layer colors are baked in the painter data, which must be rebuilt
+            if display_changed {
+                self.new_document_data
+                    .get_or_insert_with(|| self.document_data.clone());
+            }
+        })
+        .inner
+        .is_some()
+    }
+
+    /// Uncheck the layers of the current document which are marked as hidden, unless their
+    /// visibility was restored from a previous session.
+    fn hide_hidden_layers(&self) {
+        let mut viewer_options = self.viewer_options.lock().unwrap();
+        for (lid, layer) in self.document_data.document.layers() {
+            if layer.metadata().hidden {
+                viewer_options.layer_visibility.entry(*lid).or_insert(false);
+            }
+        }
     }
 
+    /// Adjust scale and offset such that the page and the visible content are entirely visible.
     fn fit_to_view(&mut self, viewport: &Rect) {
-        let bounds =
-            if let Some(page_size) = self.document_data.flattened_document.metadata().page_size {
-                if page_size.w() != 0.0 && page_size.h() != 0.0 {
-                    Some(kurbo::Rect::from_points(
-                        (0., 0.),
-                        (page_size.w(), page_size.h()),
-                    ))
-                } else {
-                    self.document_data.flattened_document.bounds()
-                }
-            } else {
-                self.document_data.flattened_document.bounds()
-            };
+        let flattened_document = &self.document_data.flattened_document;
+        let content_bounds = {
+            let viewer_options = self.viewer_options.lock().unwrap();
+            flattened_document.bounds_filtered(|lid, _| {
+                *viewer_options.layer_visibility.get(&lid).unwrap_or(&true)
+            })
+        };
+
+        let page_bounds = flattened_document
+            .metadata()
+            .page_dimensions()
+            .filter(|(w, h)| *w != 0.0 && *h != 0.0)
+            .map(|(w, h)| kurbo::Rect::new(0., 0., w, h));
 
-        if bounds.is_none() {
+        let bounds = match (content_bounds, page_bounds) {
+            (Some(content), Some(page)) => content.union(page),
+            (Some(bounds), None) | (None, Some(bounds)) => bounds,
+            (None, None) => return,
+        };
+        if bounds.width() == 0.0 && bounds.height() == 0.0 {
             return;
         }
-        let bounds = bounds.expect("bounds is not none");
 
         #[allow(clippy::cast_possible_truncation)]
         {
             let (w, h) = (bounds.width() as f32, bounds.height() as f32);
             let (view_w, view_h) = (viewport.width(), viewport.height());
 
-            self.scale = 0.95 * f32::min(view_w / w, view_h / h);
+            self.scale = (1.0 - FIT_TO_VIEW_MARGIN) * f32::min(view_w / w, view_h / h);
 
             self.offset = Pos2::new(
                 bounds.x0 as f32 - (view_w / self.scale - w) / 2.0,
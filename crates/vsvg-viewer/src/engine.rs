@@ -1,27 +1,44 @@
+use crate::lod::{level_for_scale, LodCache};
 use crate::painters::{
-    BasicPainter, BasicPainterData, LinePainter, LinePainterData, PageSizePainter,
-    PageSizePainterData, Painter, PointPainter, PointPainterData,
+    BasicPainter, BasicPainterData, ColorVertex, DashedLinePainter, DashedLinePainterData,
+    LinePainter, LinePainterData, PageSizePainter, PageSizePainterData, Painter, PointPainter,
+    PointPainterData,
 };
 use eframe::egui_wgpu::RenderState;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
 
 use vsvg::{
-    Color, Document, DocumentTrait, FlattenedDocument, LayerID, LayerTrait, PageSize, PathTrait,
-    Point,
+    Color, Document, DocumentTrait, FlattenedDocument, Layer, LayerID, LayerTrait, PageSize,
+    PathTrait, Point,
 };
 use wgpu::util::DeviceExt;
 use wgpu::{Buffer, Device, PrimitiveTopology, TextureFormat};
 
 pub(crate) const PEN_UP_TRAJECTORY_COLOR: u32 = Color::gray(168).to_rgba();
-pub(crate) const PAGE_SHADOW_COLOR: u32 = Color::gray(180).to_rgba();
-pub(crate) const PAGE_BACKGROUND_COLOR: u32 = Color::WHITE.to_rgba();
+/// Length of the pen-up trajectory dashes and gaps, in screen pixels.
+pub(crate) const PEN_UP_TRAJECTORY_DASH: f32 = 4.0;
+pub(crate) const CANVAS_COLOR: Color = Color::gray(242);
+pub(crate) const PAGE_SHADOW_COLOR: Color = Color::gray(180);
+pub(crate) const PAGE_BACKGROUND_COLOR: Color = Color::WHITE;
 pub(crate) const PAGE_BORDER_COLOR: u32 = Color::gray(168).to_rgba();
 pub(crate) const PAGE_SHADOW_SIZE: f32 = 7.;
+pub(crate) const DARK_CANVAS_COLOR: Color = Color::gray(32);
+pub(crate) const DARK_PAGE_SHADOW_COLOR: Color = Color::gray(16);
+pub(crate) const DARK_PAGE_BACKGROUND_COLOR: Color = Color::gray(72);
+pub(crate) const DARK_PAGE_BORDER_COLOR: u32 = Color::gray(110).to_rgba();
 pub(crate) const POINTS_COLOR: u32 = Color::BLACK.to_rgba();
 pub(crate) const POINTS_SIZE: f32 = 2.0;
 pub(crate) const CONTROL_POINTS_COLOR: u32 = Color::gray(128).to_rgba();
 pub(crate) const CONTROL_POINTS_SIZE: f32 = 2.0;
+pub(crate) const DEFAULT_TOLERANCE: f64 = 0.01;
+pub(crate) const FILL_OPACITY: f32 = 0.3;
+
+/// Simplification tolerance for level-of-detail rendering, in screen pixels.
+pub(crate) const LOD_TOLERANCE: f64 = 0.25;
+
+/// Margin around the viewport for culling, in screen pixels.
+pub(crate) const VIEWPORT_MARGIN: f32 = 4.0;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub(crate) enum DisplayMode {
@@ -30,7 +47,89 @@ pub(crate) enum DisplayMode {
     Outline,
 }
 
+/// Window and display settings, see [`show_with`](crate::show_with).
+///
+/// The colors apply in light mode, while dark mode uses its own colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowOptions {
+    /// window title, "vsvg" if not set
+    pub title: Option<String>,
+
+    /// initial inner size of the window, in points, or the platform default if not set
+    pub window_size: Option<(f32, f32)>,
+
+    /// fill of the area around the page
+    pub canvas_color: Color,
+
+    /// fill of the page
+    pub page_color: Color,
+
+    /// color of the page's drop shadow
+    pub shadow_color: Color,
+
+    /// offset of the page's drop shadow, in pixels
+    pub shadow_offset: f32,
+}
+
+impl Default for ShowOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            window_size: None,
+            canvas_color: CANVAS_COLOR,
+            page_color: PAGE_BACKGROUND_COLOR,
+            shadow_color: PAGE_SHADOW_COLOR,
+            shadow_offset: PAGE_SHADOW_SIZE,
+        }
+    }
+}
+
+impl ShowOptions {
+    /// The same options with the dark mode colors.
+    pub(crate) fn dark(self) -> Self {
+        Self {
+            canvas_color: DARK_CANVAS_COLOR,
+            page_color: DARK_PAGE_BACKGROUND_COLOR,
+            shadow_color: DARK_PAGE_SHADOW_COLOR,
+            ..self
+        }
+    }
+}
+
+/// Display override for the lines of a layer, which leaves the document untouched.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub(crate) struct LayerDisplay {
+    /// color replacing the paths' own color, keeping their opacity
+    pub color: Option<Color>,
+
+    /// factor applied to the paths' opacity
+    pub opacity: f32,
+}
+
+impl Default for LayerDisplay {
+    fn default() -> Self {
+        Self {
+            color: None,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl LayerDisplay {
+    /// Color to render a path of the layer with, given its own color.
+    pub(crate) fn apply(&self, color: Color) -> Color {
+        let base = self.color.map_or(color, |override_color| Color {
+            a: color.a,
+            ..override_color
+        });
+        base.with_opacity(base.opacity() * self.opacity.clamp(0.0, 1.0))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)] // if we add new fields, give them default values when deserializing old state
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct ViewerOptions {
     /// display mode
     pub display_mode: DisplayMode,
@@ -44,19 +143,65 @@ pub(crate) struct ViewerOptions {
     /// show control points
     pub show_control_points: bool,
 
+    /// show a millimeter grid
+    pub show_grid: bool,
+
+    /// spacing between grid lines, in millimeters
+    pub grid_spacing: f64,
+
+    /// number of grid lines between major grid lines
+    pub grid_major_every: u32,
+
+    /// fill closed paths with a translucent version of their color
+    pub fill_closed_paths: bool,
+
+    /// show rulers along the top and left edges
+    pub show_rulers: bool,
+
+    /// show the bounding box of the visible layers and the page dimensions
+    pub show_bounds: bool,
+
+    /// show the statistics panel
+    pub show_stats: bool,
+
     /// override width
     pub override_width: Option<f32>,
 
     /// override opacity
     pub override_opacity: Option<f32>,
 
-    /// layer visibility
+    /// layer visibility of the current document
     #[serde(skip)]
     pub layer_visibility: HashMap<LayerID, bool>,
 
+    /// layer visibility of the documents viewed so far, keyed by source
+    saved_layer_visibility: HashMap<String, HashMap<LayerID, bool>>,
+
+    /// per-layer color and opacity overrides of the current document
+    #[serde(skip)]
+    pub layer_display: HashMap<LayerID, LayerDisplay>,
+
+    /// per-layer color and opacity overrides of the documents viewed so far, keyed by source
+    saved_layer_display: HashMap<String, HashMap<LayerID, LayerDisplay>>,
+
     /// anti alias parameter
     #[serde(skip)]
     pub anti_alias: f32,
+
+    /// use dark colors for the page
+    pub dark_mode: bool,
+
+    /// canvas and page colors, set by the application
+    #[serde(skip)]
+    pub show_options: ShowOptions,
+
+    /// tolerance used to flatten the document for display
+    pub tolerance: f64,
+
+    /// tolerance to persist instead of `tolerance`, while the latter is set for this session
+    /// only, see [`DocumentWidget::set_tolerance`](crate::DocumentWidget::set_tolerance)
+    #[serde(skip)]
+    pub persisted_tolerance: Option<f64>,
 }
 
 impl Default for ViewerOptions {
@@ -66,10 +211,62 @@ impl Default for ViewerOptions {
             show_point: false,
             show_pen_up: false,
             show_control_points: false,
+            show_grid: false,
+            grid_spacing: 10.0,
+            grid_major_every: 5,
+            fill_closed_paths: false,
+            show_rulers: false,
+            show_bounds: false,
+            show_stats: false,
             override_width: None,
             override_opacity: None,
             layer_visibility: HashMap::default(),
+            saved_layer_visibility: HashMap::default(),
+            layer_display: HashMap::default(),
+            saved_layer_display: HashMap::default(),
             anti_alias: 0.5,
+            dark_mode: false,
+            show_options: ShowOptions::default(),
+            tolerance: DEFAULT_TOLERANCE,
+            persisted_tolerance: None,
+        }
+    }
+}
+
+impl ViewerOptions {
+    /// Save the current document's layer visibility and display overrides under its source, such
+    /// that [`ViewerOptions::restore_layer_options`] restores them when the same document is
+    /// viewed again.
+    ///
+    /// Documents without source are not saved.
+    pub(crate) fn save_layer_options(&mut self, source: Option<&str>) {
+        if let Some(source) = source {
+            self.saved_layer_visibility
+                .insert(source.to_owned(), self.layer_visibility.clone());
+            self.saved_layer_display
+                .insert(source.to_owned(), self.layer_display.clone());
+        }
+    }
+
+    /// Replace the layer visibility and display overrides with those saved for `source`, if any,
+    /// or make all layers visible without overrides otherwise.
+    pub(crate) fn restore_layer_options(&mut self, source: Option<&str>) {
+        self.layer_visibility = source
+            .and_then(|source| self.saved_layer_visibility.get(source))
+            .cloned()
+            .unwrap_or_default();
+        self.layer_display = source
+            .and_then(|source| self.saved_layer_display.get(source))
+            .cloned()
+            .unwrap_or_default();
+    }
+
+    /// Canvas and page colors, accounting for the dark mode.
+    pub(crate) fn effective_show_options(&self) -> ShowOptions {
+        if self.dark_mode {
+            self.show_options.clone().dark()
+        } else {
+            self.show_options.clone()
         }
     }
 }
@@ -102,26 +299,132 @@ impl CameraUniform {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct DocumentData {
-    pub(crate) flattened_document: FlattenedDocument,
+    /// source document, kept so it can be flattened again with another tolerance
+    pub(crate) document: Arc<Document>,
+
+    /// tolerance the document was flattened with, or `None` if it is yet to be flattened
+    pub(crate) tolerance: Option<f64>,
+    pub(crate) flattened_document: Arc<FlattenedDocument>,
     pub(crate) control_points: FlattenedDocument,
     pub(crate) display_vertices: HashMap<LayerID, Vec<Point>>,
 }
 
+impl Default for DocumentData {
+    fn default() -> Self {
+        Self::new(&Document::default())
+    }
+}
+
 impl DocumentData {
+    /// Document data to be flattened with the viewer's tolerance when first displayed.
+    ///
+    /// This avoids flattening the document twice when the viewer's tolerance was restored from
+    /// storage.
     #[must_use]
     pub fn new(document: &Document) -> Self {
         Self {
-            flattened_document: document.flatten(0.01), //TODO: magic number
+            document: Arc::new(document.clone()),
+            tolerance: None,
+            flattened_document: Arc::default(),
+            control_points: FlattenedDocument::default(),
+            display_vertices: HashMap::default(),
+        }
+    }
+
+    /// Document data flattened with `tolerance`.
+    ///
+    /// The viewer flattens it again if its own tolerance differs, see
+    /// [`DocumentWidget::set_tolerance`](crate::DocumentWidget::set_tolerance).
+    #[must_use]
+    pub fn with_tolerance(document: Arc<Document>, tolerance: f64) -> Self {
+        Self {
+            tolerance: Some(tolerance),
+            flattened_document: document.flatten_shared(tolerance),
             control_points: document.control_points(),
             display_vertices: document
-                .layers
+                .layers()
                 .iter()
                 .map(|(&lid, layer)| (lid, layer.display_vertices()))
                 .collect(),
+            document,
+        }
+    }
+}
+
+/// Whether `pt` is inside the closed polyline `ring`, using the crossing number rule.
+fn ring_contains(ring: &[Point], pt: Point) -> bool {
+    let mut inside = false;
+    for w in ring.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y() > pt.y()) != (b.y() > pt.y()) {
+            let x = a.x() + (pt.y() - a.y()) / (b.y() - a.y()) * (b.x() - a.x());
+            if pt.x() < x {
+                inside = !inside;
+            }
         }
     }
+    inside
+}
+
+/// Triangulate the closed paths of a layer, as colored vertices for
+/// [`PrimitiveTopology::TriangleList`].
+///
+/// The closed sub-paths of each path are filled together with the even-odd rule, so inner
+/// sub-paths form holes (e.g. for the letter "O"). Distinct paths are filled on their own, so
+/// overlapping paths don't form holes. Curves are flattened with `tolerance`.
+fn fill_vertices(layer: &Layer, tolerance: f64, display: LayerDisplay) -> Vec<ColorVertex> {
+    use vsvg::geo::TriangulateEarcut;
+
+    let to_line_string = |ring: &[Point]| -> vsvg::geo::LineString {
+        ring.iter().map(|pt| (pt.x(), pt.y())).collect()
+    };
+
+    let mut vertices = vec![];
+    for path in &layer.paths {
+        let subpaths = path.flatten(tolerance);
+        let rings: Vec<&[Point]> = subpaths
+            .iter()
+            .map(|subpath| subpath.data().points())
+            .filter(|points| points.len() > 3 && points.first() == points.last())
+            .collect();
+        if rings.is_empty() {
+            continue;
+        }
+
+        // nesting depth of each ring: even depths are filled, odd depths are holes
+        let contains = |i: usize, j: usize| i != j && ring_contains(rings[i], rings[j][0]);
+        let depths: Vec<usize> = (0..rings.len())
+            .map(|j| (0..rings.len()).filter(|&i| contains(i, j)).count())
+            .collect();
+
+        let color = display.apply(path.metadata().color);
+        let color = color.with_opacity(color.opacity() * FILL_OPACITY).to_rgba();
+        for (i, ring) in rings.iter().enumerate() {
+            if !depths[i].is_multiple_of(2) {
+                continue;
+            }
+
+            let holes = (0..rings.len())
+                .filter(|&j| depths[j] == depths[i] + 1 && contains(i, j))
+                .map(|j| to_line_string(rings[j]))
+                .collect();
+
+            vertices.extend(
+                vsvg::geo::Polygon::new(to_line_string(ring), holes)
+                    .earcut_triangles_iter()
+                    .flat_map(|triangle| triangle.to_array())
+                    .map(|coord| ColorVertex {
+                        #[allow(clippy::cast_possible_truncation)]
+                        position: [coord.x as f32, coord.y as f32],
+                        color,
+                    }),
+            );
+        }
+    }
+
+    vertices
 }
 
 /// Painters contains shaders and render pipelines needed for drawing, but not any actually
@@ -130,11 +433,14 @@ struct LayerPainters {
     /// lines are always displayed
     line_painter: LinePainter,
 
+    /// painter for the fill of closed paths
+    fill_painter: BasicPainter,
+
     /// painter for points
     point_painter: PointPainter,
 
     /// painter for pen-up trajectories
-    pen_up_painter: BasicPainter,
+    pen_up_painter: DashedLinePainter,
 
     /// painter for control points
     control_points_painter: PointPainter,
@@ -147,8 +453,9 @@ impl LayerPainters {
     fn new(render_objects: &EngineRenderObjects) -> Self {
         Self {
             line_painter: LinePainter::new(render_objects),
+            fill_painter: BasicPainter::new(render_objects, PrimitiveTopology::TriangleList),
             point_painter: PointPainter::new(render_objects),
-            pen_up_painter: BasicPainter::new(render_objects, PrimitiveTopology::LineList),
+            pen_up_painter: DashedLinePainter::new(render_objects),
             control_points_painter: PointPainter::new(render_objects),
             control_lines_painter: BasicPainter::new(render_objects, PrimitiveTopology::LineList),
         }
@@ -158,8 +465,9 @@ impl LayerPainters {
 /// Data needed for drawing a single layer, to be provided to the layer painters.
 struct LayerPainterData {
     line_painter_data: LinePainterData,
+    fill_painter_data: Option<BasicPainterData>,
     point_painter_data: PointPainterData,
-    pen_up_painter_data: BasicPainterData,
+    pen_up_painter_data: DashedLinePainterData,
     control_points_painter_data: PointPainterData,
     control_lines_painter_data: BasicPainterData,
 }
@@ -168,8 +476,10 @@ impl LayerPainterData {
     fn build(
         render_objects: &EngineRenderObjects,
         document_data: &DocumentData,
-    ) -> HashMap<LayerID, LayerPainterData> {
-        let mut layers = HashMap::new();
+        layer_display: &HashMap<LayerID, LayerDisplay>,
+        fill_closed_paths: bool,
+    ) -> BTreeMap<LayerID, LayerPainterData> {
+        let mut layers = BTreeMap::new();
 
         for (lid, flattened_layer) in &document_data.flattened_document.layers {
             let points = document_data
@@ -179,11 +489,7 @@ impl LayerPainterData {
                 .iter()
                 .map(Into::into);
 
-            let pen_up_trajectories = flattened_layer
-                .pen_up_trajectories()
-                .iter()
-                .flat_map(|(start, end)| [start.into(), end.into()])
-                .collect::<Vec<[f32; 2]>>();
+            let pen_up_trajectories = flattened_layer.pen_up_trajectories();
 
             let control_points: Vec<_> = document_data.control_points.layers[lid]
                 .paths
@@ -192,17 +498,38 @@ impl LayerPainterData {
                 .map(Into::into)
                 .collect();
 
+            let display = layer_display.get(lid).copied().unwrap_or_default();
             let layer_data = LayerPainterData {
-                line_painter_data: LinePainterData::new(render_objects, &flattened_layer.paths),
+                line_painter_data: LinePainterData::new(
+                    render_objects,
+                    &flattened_layer.paths,
+                    display,
+                ),
+                fill_painter_data: fill_closed_paths.then(|| {
+                    BasicPainterData::from_colored_vertices(
+                        render_objects,
+                        &document_data
+                            .document
+                            .layers
+                            .get(lid)
+                            .map(|layer| {
+                                let tolerance =
+                                    document_data.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+                                fill_vertices(layer, tolerance, display)
+                            })
+                            .unwrap_or_default(),
+                    )
+                }),
                 point_painter_data: PointPainterData::new(
                     render_objects,
                     points,
                     POINTS_COLOR,
                     POINTS_SIZE,
                 ),
-                pen_up_painter_data: BasicPainterData::new(
+                pen_up_painter_data: DashedLinePainterData::new(
                     render_objects,
-                    pen_up_trajectories,
+                    &pen_up_trajectories,
+                    PEN_UP_TRAJECTORY_DASH,
                     PEN_UP_TRAJECTORY_COLOR,
                 ),
                 control_points_painter_data: PointPainterData::new(
@@ -243,12 +570,27 @@ pub(super) struct Engine {
     viewer_options: Arc<Mutex<ViewerOptions>>,
 
     last_page_size: Option<PageSize>,
+    last_dark_mode: bool,
+    last_show_options: ShowOptions,
 
     // painters
     layer_painters: LayerPainters,
 
-    /// per-layer painter data
-    layer_painter_data: HashMap<LayerID, LayerPainterData>,
+    /// per-layer painter data, ordered such that layers are drawn in ID order
+    layer_painter_data: BTreeMap<LayerID, LayerPainterData>,
+
+    /// document and layer display overrides the painter data was built from
+    document_data: Option<Arc<DocumentData>>,
+    layer_display: HashMap<LayerID, LayerDisplay>,
+
+    /// simplified per-layer line painter data for the current level of detail and its neighbors
+    lod_cache: LodCache<BTreeMap<LayerID, LinePainterData>>,
+
+    /// level of detail used for the current frame, or `None` for full detail
+    current_lod: Option<i32>,
+
+    /// visible area of the current frame, in document coordinates
+    viewport: kurbo::Rect,
 
     page_size_painter: PageSizePainter,
     page_size_painter_data: Option<PageSizePainterData>,
@@ -273,6 +615,7 @@ fn projection(
 impl Engine {
     pub(crate) fn new(
         wgpu_render_state: &RenderState,
+        egui_ctx: egui::Context,
         viewer_options: Arc<Mutex<ViewerOptions>>,
     ) -> Self {
         let device = wgpu_render_state.device.clone();
@@ -325,25 +668,106 @@ impl Engine {
 
             viewer_options,
             last_page_size: None,
+            last_dark_mode: false,
+            last_show_options: ShowOptions::default(),
 
             layer_painters,
-            layer_painter_data: HashMap::default(),
+            layer_painter_data: BTreeMap::default(),
+            document_data: None,
+            layer_display: HashMap::default(),
+            lod_cache: LodCache::new(move || egui_ctx.request_repaint()),
+            current_lod: None,
+            viewport: kurbo::Rect::ZERO,
             page_size_painter,
             page_size_painter_data: None,
         }
     }
 
-    pub fn set_document_data(&mut self, document_data: &DocumentData) {
+    pub fn set_document_data(&mut self, document_data: &Arc<DocumentData>) {
         // in most cases the page size won't change from a frame to the next, so we only rebuild
         // if needed
-        let new_page_size = document_data.flattened_document.metadata().page_size;
+        let new_page_size = document_data
+            .flattened_document
+            .metadata()
+            .page_dimensions()
+            .map(|(w, h)| PageSize::new(w, h));
         if self.last_page_size != new_page_size {
-            self.page_size_painter_data = new_page_size
-                .map(|page_size| PageSizePainterData::new(&self.render_objects, page_size));
             self.last_page_size = new_page_size;
+            self.rebuild_page_size_painter_data();
+        }
+
+        let (layer_display, fill_closed_paths) = {
+            let viewer_options = self.viewer_options.lock().unwrap();
+            (
+                viewer_options.layer_display.clone(),
+                viewer_options.fill_closed_paths,
+            )
+        };
+        self.layer_painter_data = LayerPainterData::build(
+            &self.render_objects,
+            document_data,
+            &layer_display,
+            fill_closed_paths,
+        );
+
+        self.document_data = Some(document_data.clone());
+        self.layer_display = layer_display;
+        self.lod_cache.clear();
+    }
+
+    /// Select the level of detail for `scale`, see [`level_for_scale`].
+    ///
+    /// Full detail is used until the level's painter data is ready, since it is simplified in the
+    /// background.
+    fn update_lod(&mut self, scale: f32) {
+        self.current_lod = None;
+        let Some(document_data) = &self.document_data else {
+            return;
+        };
+        let Some(min_tolerance) = document_data.tolerance else {
+            return;
+        };
+        let Some(lod) = level_for_scale(scale, LOD_TOLERANCE, min_tolerance) else {
+            return;
+        };
+
+        let render_objects = &self.render_objects;
+        let layer_display = &self.layer_display;
+        let ready = self
+            .lod_cache
+            .update(
+                lod,
+                &document_data.flattened_document,
+                min_tolerance,
+                |layers| {
+                    layers
+                        .iter()
+                        .map(|(lid, layer)| {
+                            let data = LinePainterData::new(
+                                render_objects,
+                                &layer.paths,
+                                layer_display.get(lid).copied().unwrap_or_default(),
+                            );
+                            (*lid, data)
+                        })
+                        .collect()
+                },
+            )
+            .is_some();
+        if ready {
+            self.current_lod = Some(lod);
         }
+    }
 
-        self.layer_painter_data = LayerPainterData::build(&self.render_objects, document_data);
+    fn rebuild_page_size_painter_data(&mut self) {
+        self.page_size_painter_data = self.last_page_size.map(|page_size| {
+            PageSizePainterData::new(
+                &self.render_objects,
+                page_size,
+                &self.last_show_options,
+                self.last_dark_mode,
+            )
+        });
     }
 
     pub(super) fn prepare(
@@ -354,13 +778,41 @@ impl Engine {
         scale: f32,
         origin: cgmath::Point2<f32>,
     ) {
+        let (anti_alias, dark_mode, show_options) = {
+            let viewer_options = self.viewer_options.lock().unwrap();
+            (
+                viewer_options.anti_alias,
+                viewer_options.dark_mode,
+                viewer_options.effective_show_options(),
+            )
+        };
+
+        // page colors depend on the dark mode
+        if self.last_dark_mode != dark_mode || self.last_show_options != show_options {
+            self.last_dark_mode = dark_mode;
+            self.last_show_options = show_options;
+            self.rebuild_page_size_painter_data();
+        }
+
+        self.update_lod(scale);
+
+        // include a few pixels of margin for anti-aliasing
+        let margin = f64::from(VIEWPORT_MARGIN / scale);
+        self.viewport = kurbo::Rect::new(
+            f64::from(origin.x),
+            f64::from(origin.y),
+            f64::from(origin.x + rect.width() / scale),
+            f64::from(origin.y + rect.height() / scale),
+        )
+        .inflate(margin, margin);
+
         // Update our uniform buffer with the angle from the UI
         let mut camera_uniform = CameraUniform::default();
         camera_uniform.update(
             projection(origin, scale, rect.width(), rect.height()),
             scale,
             (rect.width(), rect.height()),
-            self.viewer_options.lock().unwrap().anti_alias,
+            anti_alias,
         );
 
         queue.write_buffer(
@@ -383,10 +835,23 @@ impl Engine {
         for (lid, layer_painter_data) in &self.layer_painter_data {
             if *viewer_options.layer_visibility.get(lid).unwrap_or(&true) {
                 //TODO: move that to LayerPainters
-                self.layer_painters.line_painter.draw(
+                if let Some(fill_painter_data) = &layer_painter_data.fill_painter_data {
+                    self.layer_painters.fill_painter.draw(
+                        render_pass,
+                        &self.render_objects.camera_bind_group,
+                        fill_painter_data,
+                    );
+                }
+
+                let line_painter_data = self
+                    .current_lod
+                    .and_then(|lod| self.lod_cache.get(lod)?.get(lid))
+                    .unwrap_or(&layer_painter_data.line_painter_data);
+                self.layer_painters.line_painter.draw_culled(
                     render_pass,
                     &self.render_objects.camera_bind_group,
-                    &layer_painter_data.line_painter_data,
+                    line_painter_data,
+                    self.viewport,
                 );
 
                 if viewer_options.show_control_points {
@@ -3,10 +3,14 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::let_underscore_untyped)]
+#![allow(clippy::struct_field_names)]
 
+mod document_stats;
 mod document_widget;
 mod engine;
 mod frame_history;
+mod lod;
+mod measure_tool;
 mod painters;
 pub mod viewer;
 #[cfg(target_arch = "wasm32")]
@@ -15,21 +19,75 @@ pub mod web_handle;
 pub use viewer::Viewer;
 
 pub use crate::document_widget::DocumentWidget;
-pub use crate::engine::DocumentData;
+pub use crate::engine::{DocumentData, ShowOptions};
 
-/// Empty viewer app for [`show()`]
-struct EmptyViewerApp;
+/// Viewer app for [`show()`] and [`show_with()`], which only applies the display settings.
+struct ShowViewerApp {
+    tolerance: Option<f64>,
+    options: ShowOptions,
+}
 
-impl ViewerApp for EmptyViewerApp {}
+impl ViewerApp for ShowViewerApp {
+    fn setup(
+        &mut self,
+        _cc: &eframe::CreationContext,
+        document_widget: &mut DocumentWidget,
+    ) -> anyhow::Result<()> {
+        if let Some(tolerance) = self.tolerance {
+            document_widget.set_tolerance(tolerance);
+        }
+        document_widget.set_show_options(self.options.clone());
+        Ok(())
+    }
+
+    fn title(&self) -> String {
+        self.options
+            .title
+            .clone()
+            .unwrap_or_else(|| "vsvg".to_owned())
+    }
+}
 
 /// Show a document in a window.
 ///
 /// For native use only.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn show(document: &vsvg::Document) -> anyhow::Result<()> {
-    let native_options = eframe::NativeOptions::default();
-    let document_data = std::sync::Arc::new(DocumentData::new(document));
+    // the document is flattened with the tolerance last set in the viewer
+    run_show_viewer_app(document, None, ShowOptions::default())
+}
 
+/// Show a document in a window, flattened with `tolerance` and with custom window and display
+/// settings.
+///
+/// For native use only.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn show_with(
+    document: &vsvg::Document,
+    tolerance: f64,
+    options: ShowOptions,
+) -> anyhow::Result<()> {
+    run_show_viewer_app(document, Some(tolerance), options)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_show_viewer_app(
+    document: &vsvg::Document,
+    tolerance: Option<f64>,
+    options: ShowOptions,
+) -> anyhow::Result<()> {
+    let native_options = eframe::NativeOptions {
+        initial_window_size: options.window_size.map(|(w, h)| egui::vec2(w, h)),
+        ..eframe::NativeOptions::default()
+    };
+    let document_data = std::sync::Arc::new(match tolerance {
+        Some(tolerance) => {
+            DocumentData::with_tolerance(std::sync::Arc::new(document.clone()), tolerance)
+        }
+        None => DocumentData::new(document),
+    });
+
+    // the app name also locates the persisted settings, so the title is set by the viewer instead
     eframe::run_native(
         "vsvg-viewer",
         native_options,
@@ -41,8 +99,12 @@ pub fn show(document: &vsvg::Document) -> anyhow::Result<()> {
             cc.egui_ctx.set_style(style);
 
             Box::new(
-                Viewer::new(cc, document_data, Box::new(EmptyViewerApp {}))
-                    .expect("viewer requires wgpu backend"),
+                Viewer::new(
+                    cc,
+                    document_data,
+                    Box::new(ShowViewerApp { tolerance, options }),
+                )
+                .expect("viewer requires wgpu backend"),
             )
         }),
     )?;
@@ -76,6 +138,9 @@ pub trait ViewerApp {
     fn options(&self, _native_option: &mut eframe::NativeOptions) {}
 
     /// Window title
+    ///
+    /// It is applied when the viewer starts. With [`show_with_viewer_app`], it is also the app
+    /// name, which determines where the settings are persisted.
     fn title(&self) -> String {
         "vsvg ViewerApp".to_owned()
     }
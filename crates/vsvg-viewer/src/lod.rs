@@ -0,0 +1,204 @@
+//! Level-of-detail geometry for the line painters.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use vsvg::{FlattenedDocument, FlattenedLayer, LayerID, LayerTrait};
+
+/// Layers of a document simplified for one level of detail.
+pub(crate) type SimplifiedLayers = BTreeMap<LayerID, FlattenedLayer>;
+
+/// Simplification tolerance of a level, in document units.
+pub(crate) fn level_tolerance(level: i32) -> f64 {
+    2f64.powi(level)
+}
+
+/// Level of detail to draw at `scale`, with a simplification tolerance of about `tolerance`
+/// screen pixels.
+///
+/// Levels are powers of two of the simplification tolerance, such that zooming within a level
+/// reuses the same geometry. Returns `None` for full detail, when the level's tolerance is not
+/// above `min_tolerance`, i.e. the one used to flatten the document.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn level_for_scale(scale: f32, tolerance: f64, min_tolerance: f64) -> Option<i32> {
+    let level = (tolerance / f64::from(scale)).log2().floor();
+    if !level.is_finite() {
+        return None;
+    }
+
+    let level = level.clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32;
+    (level_tolerance(level) > min_tolerance).then_some(level)
+}
+
+/// Run `f` in the background, or right away where threads are not available.
+fn spawn(f: impl FnOnce() + Send + 'static) {
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::spawn(f);
+
+    #[cfg(target_arch = "wasm32")]
+    f();
+}
+
+/// Per-level data built from a document, limited to the current level and its neighbors.
+///
+/// Simplification runs in the background, and its result is converted (e.g. uploaded to the GPU)
+/// once ready, the next time the cache is updated.
+pub(crate) struct LodCache<T> {
+    ready: HashMap<i32, T>,
+    /// levels being simplified, filled in by the background task
+    pending: HashMap<i32, Arc<Mutex<Option<SimplifiedLayers>>>>,
+
+    /// called from the background when a level is ready, e.g. to request a repaint
+    notify: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl<T> LodCache<T> {
+    pub(crate) fn new(notify: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            ready: HashMap::default(),
+            pending: HashMap::default(),
+            notify: Arc::new(notify),
+        }
+    }
+
+    /// Drop all levels, e.g. when the document changes.
+    ///
+    /// Levels still being simplified are discarded when done.
+    pub(crate) fn clear(&mut self) {
+        self.ready.clear();
+        self.pending.clear();
+    }
+
+    /// Data for `level`, if it is ready.
+    pub(crate) fn get(&self, level: i32) -> Option<&T> {
+        self.ready.get(&level)
+    }
+
+    /// Return the data for `level` if it is ready.
+    ///
+    /// Levels other than `level` and its neighbors are dropped, and the missing ones are
+    /// simplified from `document` in the background, except those at full detail (see
+    /// [`level_for_scale`]). Finished levels are converted with `convert`.
+    pub(crate) fn update(
+        &mut self,
+        level: i32,
+        document: &Arc<FlattenedDocument>,
+        min_tolerance: f64,
+        mut convert: impl FnMut(SimplifiedLayers) -> T,
+    ) -> Option<&T> {
+        let levels = level.saturating_sub(1)..=level.saturating_add(1);
+        self.ready.retain(|lvl, _| levels.contains(lvl));
+        self.pending.retain(|lvl, _| levels.contains(lvl));
+
+        self.pending
+            .retain(|lvl, slot| match slot.lock().unwrap().take() {
+                Some(layers) => {
+                    self.ready.insert(*lvl, convert(layers));
+                    false
+                }
+                None => true,
+            });
+
+        for lvl in levels {
+            let tolerance = level_tolerance(lvl);
+            if tolerance <= min_tolerance
+                || self.ready.contains_key(&lvl)
+                || self.pending.contains_key(&lvl)
+            {
+                continue;
+            }
+
+            let slot = Arc::new(Mutex::new(None));
+            self.pending.insert(lvl, slot.clone());
+
+            let document = document.clone();
+            let notify = self.notify.clone();
+            spawn(move || {
+                let layers = document
+                    .layers
+                    .iter()
+                    .map(|(lid, layer)| {
+                        let mut layer = layer.clone();
+                        layer.simplify(tolerance);
+                        (*lid, layer)
+                    })
+                    .collect();
+
+                *slot.lock().unwrap() = Some(layers);
+
+                // the level may have been dropped in the meantime
+                if Arc::strong_count(&slot) > 1 {
+                    notify();
+                }
+            });
+        }
+
+        self.ready.get(&level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use vsvg::{DocumentTrait, Path, PathTrait};
+
+    #[test]
+    fn test_level_for_scale() {
+        assert_eq!(level_for_scale(1.0, 0.25, 0.01), Some(-2));
+        assert_eq!(level_for_scale(1.0 / 16.0, 0.25, 0.01), Some(2));
+        assert_eq!(level_for_scale(1.0, 0.25, 0.25), None);
+        assert_eq!(level_for_scale(100.0, 0.25, 0.01), None);
+        assert_eq!(level_for_scale(0.0, 0.25, 0.01), None);
+    }
+
+    #[test]
+    fn test_lod_cache() {
+        let mut document = vsvg::Document::default();
+        let path = Path::from_points((0..=100).map(|i| (f64::from(i), 0.0)));
+        document.push_path(1, path);
+        let document = Arc::new(document.flatten(0.01));
+
+        let notified = Arc::new(AtomicUsize::new(0));
+        let mut cache = LodCache::new({
+            let notified = notified.clone();
+            move || {
+                notified.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // levels are built in the background, along with their neighbors
+        let mut point_count = None;
+        for _ in 0..1000 {
+            point_count = cache
+                .update(3, &document, 0.01, |layers| {
+                    layers[&1].paths[0].data().points().len()
+                })
+                .copied();
+            if point_count.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(point_count, Some(2));
+        assert!(cache
+            .ready
+            .keys()
+            .chain(cache.pending.keys())
+            .all(|lvl| (2..=4).contains(lvl)));
+        assert_eq!(cache.ready.len() + cache.pending.len(), 3);
+        assert!(notified.load(Ordering::SeqCst) >= 1);
+
+        // farther levels are dropped, and full-detail ones are not built
+        let _ = cache.update(-6, &document, 0.01, |_| 0);
+        assert!(!cache.ready.contains_key(&3));
+        assert!(cache
+            .ready
+            .keys()
+            .chain(cache.pending.keys())
+            .all(|lvl| *lvl == -6 || *lvl == -5));
+
+        cache.clear();
+        assert!(cache.ready.is_empty() && cache.pending.is_empty());
+    }
+}
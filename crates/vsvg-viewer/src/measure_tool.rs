@@ -0,0 +1,186 @@
+use egui::{Color32, Pos2, Response, Stroke, Ui};
+use vsvg::Unit;
+
+/// Minimum distance between the two points of a measurement, in screen pixels.
+const MIN_MEASURE_DISTANCE: f32 = 3.0;
+
+const MEASURE_COLOR: Color32 = Color32::from_rgb(230, 0, 120);
+
+/// Round `point` to the nearest intersection of a grid with `spacing`, aligned on the origin.
+///
+/// This is a no-op if `spacing` is not positive.
+pub(crate) fn snap_to_grid(point: Pos2, spacing: f32) -> Pos2 {
+    if spacing > 0.0 && spacing.is_finite() {
+        Pos2::new(
+            (point.x / spacing).round() * spacing,
+            (point.y / spacing).round() * spacing,
+        )
+    } else {
+        point
+    }
+}
+
+/// Format a distance in the document's `unit`, followed by its value in millimeters unless the
+/// document is already in millimeters.
+fn distance_readout(length: f64, unit: Unit) -> String {
+    let text = format!("{length:.2} {}", unit.to_str());
+    if unit == Unit::Mm {
+        text
+    } else {
+        format!(
+            "{text} ({:.2} mm)",
+            length * unit.to_px() / Unit::Mm.to_px()
+        )
+    }
+}
+
+/// Interactive tool to measure the distance and angle between two points of the document.
+///
+/// A measurement is made either by clicking two points, or by dragging from the first point to
+/// the second one. It is kept until the next click. All coordinates are in document units.
+#[derive(Debug, Default)]
+pub(crate) struct MeasureTool {
+    /// whether the tool is active (mouse drag no longer pans the view)
+    pub(crate) enabled: bool,
+
+    /// whether clicked points are snapped to the grid intersections
+    pub(crate) snap: bool,
+
+    /// first point of the measurement
+    start: Option<Pos2>,
+
+    /// second point of the measurement, `None` while the measurement is in progress
+    end: Option<Pos2>,
+}
+
+impl MeasureTool {
+    /// Update the measurement based on mouse input.
+    ///
+    /// `to_document` converts screen coordinates to document coordinates.
+    pub(crate) fn handle_input(
+        &mut self,
+        response: &Response,
+        scale: f32,
+        to_document: impl Fn(Pos2) -> Pos2,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        response.ctx.input(|i| {
+            if i.key_pressed(egui::Key::Escape) {
+                self.start = None;
+                self.end = None;
+            }
+
+            let Some(pos) = i.pointer.interact_pos().map(&to_document) else {
+                return;
+            };
+
+            if i.pointer.primary_pressed()
+                && response.hovered()
+                && (self.start.is_none() || self.end.is_some())
+            {
+                self.start = Some(pos);
+                self.end = None;
+            }
+
+            if i.pointer.primary_released() && self.end.is_none() {
+                if let Some(start) = self.start {
+                    if start.distance(pos) * scale > MIN_MEASURE_DISTANCE {
+                        self.end = Some(pos);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Draw the current measurement, if any, with a distance and angle readout.
+    ///
+    /// `to_screen` converts document coordinates to screen coordinates, and `unit` is the
+    /// document unit, in which the distance is shown.
+    pub(crate) fn paint(
+        &self,
+        ui: &Ui,
+        response: &Response,
+        to_document: impl Fn(Pos2) -> Pos2,
+        to_screen: impl Fn(Pos2) -> Pos2,
+        unit: Unit,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let Some(start) = self.start else {
+            return;
+        };
+        let Some(end) = self.end.or_else(|| response.hover_pos().map(&to_document)) else {
+            return;
+        };
+
+        let painter = ui.painter_at(response.rect);
+        let stroke = Stroke::new(1.5, MEASURE_COLOR);
+        let (screen_start, screen_end) = (to_screen(start), to_screen(end));
+        painter.line_segment([screen_start, screen_end], stroke);
+        painter.circle_filled(screen_start, 3.0, MEASURE_COLOR);
+        painter.circle_filled(screen_end, 3.0, MEASURE_COLOR);
+
+        let delta = end - start;
+        let text = format!(
+            "{}, {:.1}°",
+            distance_readout(f64::from(delta.length()), unit),
+            delta.y.atan2(delta.x).to_degrees()
+        );
+        let galley = painter.layout_no_wrap(
+            text,
+            egui::FontId::proportional(12.0),
+            ui.visuals().text_color(),
+        );
+
+        let anchor = response.hover_pos().unwrap_or(screen_end) + egui::vec2(12.0, 12.0);
+        let background = egui::Rect::from_min_size(anchor, galley.size()).expand(3.0);
+        painter.rect(
+            background,
+            3.0,
+            ui.visuals().extreme_bg_color,
+            Stroke::new(1.0, MEASURE_COLOR),
+        );
+        painter.galley(anchor, galley);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_grid() {
+        assert_eq!(
+            snap_to_grid(Pos2::new(4.9, 5.1), 10.0),
+            Pos2::new(0.0, 10.0)
+        );
+        assert_eq!(
+            snap_to_grid(Pos2::new(14.0, 26.0), 5.0),
+            Pos2::new(15.0, 25.0)
+        );
+        assert_eq!(
+            snap_to_grid(Pos2::new(-4.0, -6.0), 10.0),
+            Pos2::new(0.0, -10.0)
+        );
+        assert_eq!(
+            snap_to_grid(Pos2::new(-12.6, 7.0), 2.5),
+            Pos2::new(-12.5, 7.5)
+        );
+
+        let point = Pos2::new(1.234, -5.678);
+        assert_eq!(snap_to_grid(point, 0.0), point);
+        assert_eq!(snap_to_grid(point, -1.0), point);
+        assert_eq!(snap_to_grid(point, f32::NAN), point);
+    }
+
+    #[test]
+    fn test_distance_readout() {
+        assert_eq!(distance_readout(10.0, Unit::Mm), "10.00 mm");
+        assert_eq!(distance_readout(96.0, Unit::Px), "96.00 px (25.40 mm)");
+        assert_eq!(distance_readout(1.5, Unit::In), "1.50 in (38.10 mm)");
+    }
+}
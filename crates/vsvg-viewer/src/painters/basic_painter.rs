@@ -30,12 +30,20 @@ impl BasicPainterData {
             .map(|v| ColorVertex { position: v, color })
             .collect::<Vec<_>>();
 
+        Self::from_colored_vertices(render_objects, &vertices)
+    }
+
+    /// Create painter data from vertices with individual colors.
+    pub fn from_colored_vertices(
+        render_objects: &EngineRenderObjects,
+        vertices: &[ColorVertex],
+    ) -> Self {
         let vertex_buffer =
             render_objects
                 .device
                 .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                     label: Some("Vertex buffer"),
-                    contents: bytemuck::cast_slice(vertices.as_slice()),
+                    contents: bytemuck::cast_slice(vertices),
                     usage: wgpu::BufferUsages::VERTEX,
                 });
 
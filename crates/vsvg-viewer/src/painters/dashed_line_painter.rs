@@ -0,0 +1,170 @@
+use crate::engine::EngineRenderObjects;
+use crate::painters::Painter;
+use std::mem;
+use vsvg::Point;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    include_wgsl, vertex_attr_array, Buffer, ColorTargetState, PrimitiveTopology, RenderPass,
+    RenderPipeline,
+};
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct DashedVertex {
+    position: [f32; 2],
+
+    /// distance from the start of the line, in document units
+    distance: f32,
+
+    /// length of the dashes and gaps, in screen pixels
+    dash: f32,
+
+    color: u32,
+}
+
+pub(crate) struct DashedLinePainterData {
+    vertex_buffer: Buffer,
+    vertex_count: u32,
+}
+
+impl DashedLinePainterData {
+    /// Create painter data for straight lines given by their `(start, end)` points.
+    ///
+    /// The lines are dashed by the shader with dashes and gaps of `dash` screen pixels, such
+    /// that each line needs two vertices regardless of its length or the zoom level.
+    pub fn new<'b>(
+        render_objects: &EngineRenderObjects,
+        lines: impl IntoIterator<Item = &'b (Point, Point)>,
+        dash: f32,
+        color: u32,
+    ) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let vertices = lines
+            .into_iter()
+            .flat_map(|(start, end)| {
+                [
+                    DashedVertex {
+                        position: start.into(),
+                        distance: 0.0,
+                        dash,
+                        color,
+                    },
+                    DashedVertex {
+                        position: end.into(),
+                        distance: start.distance(end) as f32,
+                        dash,
+                        color,
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let vertex_buffer =
+            render_objects
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Dashed line vertex buffer"),
+                    contents: bytemuck::cast_slice(vertices.as_slice()),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+
+        Self {
+            vertex_buffer,
+            #[allow(clippy::cast_possible_truncation)]
+            vertex_count: vertices.len() as u32,
+        }
+    }
+}
+
+/// Painter for straight dashed lines, with dashes of constant length on screen.
+pub(crate) struct DashedLinePainter {
+    render_pipeline: RenderPipeline,
+}
+
+impl DashedLinePainter {
+    pub(crate) fn new(render_objects: &EngineRenderObjects) -> Self {
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<DashedVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &vertex_attr_array![
+                0 => Float32x2,
+                1 => Float32,
+                2 => Float32,
+                3 => Uint32,
+            ],
+        };
+
+        let shader = render_objects
+            .device
+            .create_shader_module(include_wgsl!("../shaders/dashed_line.wgsl"));
+
+        let pipeline_layout =
+            render_objects
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: None,
+                    bind_group_layouts: &[&render_objects.camera_bind_group_layout],
+                    push_constant_ranges: &[],
+                });
+
+        // enable alpha blending
+        let target = ColorTargetState {
+            format: render_objects.target_format,
+            blend: Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                    dst_factor: wgpu::BlendFactor::DstAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            }),
+            write_mask: wgpu::ColorWrites::ALL,
+        };
+
+        let render_pipeline =
+            render_objects
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some("dashed line pipeline"),
+                    layout: Some(&pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_main",
+                        buffers: &[vertex_buffer_layout],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: "fs_main",
+                        targets: &[Some(target)],
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        topology: PrimitiveTopology::LineList,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview: None,
+                });
+
+        Self { render_pipeline }
+    }
+}
+
+impl Painter for DashedLinePainter {
+    type Data = DashedLinePainterData;
+    fn draw<'a>(
+        &'a self,
+        rpass: &mut RenderPass<'a>,
+        camera_bind_group: &'a wgpu::BindGroup,
+        data: &'a Self::Data,
+    ) {
+        rpass.set_pipeline(&self.render_pipeline);
+        rpass.set_bind_group(0, camera_bind_group, &[]);
+        rpass.set_vertex_buffer(0, data.vertex_buffer.slice(..));
+        rpass.draw(0..data.vertex_count, 0..1);
+    }
+}
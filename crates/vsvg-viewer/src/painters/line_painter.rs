@@ -1,6 +1,7 @@
-use crate::engine::EngineRenderObjects;
+use crate::engine::{EngineRenderObjects, LayerDisplay};
 use crate::painters::{Painter, Vertex};
 use std::mem;
+use std::ops::Range;
 use vsvg::{FlattenedPath, PathTrait};
 use wgpu::util::DeviceExt;
 use wgpu::{
@@ -24,18 +25,29 @@ impl Attribute {
     }
 }
 
+/// Maximum number of instances grouped in a [`Chunk`].
+const CHUNK_INSTANCES: u32 = 1024;
+
+/// Consecutive paths drawn together, with their bounds for viewport culling.
+struct Chunk {
+    /// bounds of the paths, including their stroke width
+    bounds: kurbo::Rect,
+    instances: Range<u32>,
+}
+
 pub(crate) struct LinePainterData {
     points_buffer: Buffer,
     attributes_buffer: Buffer,
     instance_count: u32,
+    chunks: Vec<Chunk>,
 }
 
 impl LinePainterData {
-    pub fn new<'b, I>(render_objects: &EngineRenderObjects, paths: I) -> Self
+    pub fn new<'b, I>(render_objects: &EngineRenderObjects, paths: I, display: LayerDisplay) -> Self
     where
         I: IntoIterator<Item = &'b FlattenedPath>,
     {
-        let (vertices, attribs) = Self::build_buffers(paths);
+        let (vertices, attribs, chunks) = Self::build_buffers(paths, display);
 
         // prepare point buffer
         let points_buffer =
@@ -62,15 +74,41 @@ impl LinePainterData {
             attributes_buffer,
             #[allow(clippy::cast_possible_truncation)]
             instance_count: attribs.len() as u32,
+            chunks,
         }
     }
 
-    fn build_buffers<'b, I>(paths: I) -> (Vec<Vertex>, Vec<Attribute>)
+    /// Add the instances of `path` to the last chunk, or to a new one if it is full.
+    fn extend_chunks(chunks: &mut Vec<Chunk>, path: &FlattenedPath, instances: Range<u32>) {
+        if instances.is_empty() {
+            return;
+        }
+        let Some(bounds) = path.bounds() else {
+            return;
+        };
+        let half_width = path.metadata().stroke_width / 2.0;
+        let bounds = bounds.inflate(half_width, half_width);
+
+        match chunks.last_mut() {
+            Some(chunk) if chunk.instances.len() < CHUNK_INSTANCES as usize => {
+                chunk.bounds = chunk.bounds.union(bounds);
+                chunk.instances.end = instances.end;
+            }
+            _ => chunks.push(Chunk { bounds, instances }),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn build_buffers<'b, I>(
+        paths: I,
+        display: LayerDisplay,
+    ) -> (Vec<Vertex>, Vec<Attribute>, Vec<Chunk>)
     where
         I: IntoIterator<Item = &'b FlattenedPath>,
     {
         fn add_path(
             path: &FlattenedPath,
+            display: LayerDisplay,
             vertices: &mut Vec<Vertex>,
             attribs: &mut Vec<Attribute>,
         ) {
@@ -87,7 +125,7 @@ impl LinePainterData {
                 }
 
                 let attr = Attribute {
-                    color: path.metadata().color.to_rgba(),
+                    color: display.apply(path.metadata().color).to_rgba(),
                     #[allow(clippy::cast_possible_truncation)]
                     width: path.metadata().stroke_width as f32,
                 };
@@ -98,24 +136,27 @@ impl LinePainterData {
             }
         }
 
-        let mut iter = paths.into_iter();
+        let iter = paths.into_iter();
         let min_size = 1000.min(iter.size_hint().0 * 4);
 
         // build the data buffers
         let mut vertices: Vec<Vertex> = Vec::with_capacity(min_size);
         let mut attribs = Vec::with_capacity(min_size);
+        let mut chunks = vec![];
 
-        if let Some(path) = iter.next() {
-            add_path(path, &mut vertices, &mut attribs);
-            for path in iter {
+        #[allow(clippy::cast_possible_truncation)]
+        for (i, path) in iter.enumerate() {
+            if i > 0 {
                 attribs.push(Attribute::empty());
                 attribs.push(Attribute::empty());
                 attribs.push(Attribute::empty());
-                add_path(path, &mut vertices, &mut attribs);
             }
+            let start = attribs.len() as u32;
+            add_path(path, display, &mut vertices, &mut attribs);
+            Self::extend_chunks(&mut chunks, path, start..attribs.len() as u32);
         }
 
-        (vertices, attribs)
+        (vertices, attribs, chunks)
     }
 }
 
@@ -225,3 +266,42 @@ impl Painter for LinePainter {
         rpass.draw(0..4, 0..data.instance_count);
     }
 }
+
+impl LinePainter {
+    /// Draw only the chunks of paths which overlap `viewport`, in document coordinates.
+    pub(crate) fn draw_culled<'a>(
+        &'a self,
+        rpass: &mut RenderPass<'a>,
+        camera_bind_group: &'a wgpu::BindGroup,
+        data: &'a LinePainterData,
+        viewport: kurbo::Rect,
+    ) {
+        rpass.set_pipeline(&self.render_pipeline);
+        rpass.set_bind_group(0, camera_bind_group, &[]);
+        rpass.set_vertex_buffer(0, data.points_buffer.slice(..));
+        rpass.set_vertex_buffer(1, data.attributes_buffer.slice(..));
+
+        // consecutive visible chunks are only separated by empty instances and are drawn at once
+        let mut pending: Option<Range<u32>> = None;
+        for chunk in data.chunks.iter().filter(|chunk| {
+            chunk.bounds.x0 <= viewport.x1
+                && viewport.x0 <= chunk.bounds.x1
+                && chunk.bounds.y0 <= viewport.y1
+                && viewport.y0 <= chunk.bounds.y1
+        }) {
+            match &mut pending {
+                Some(range) if chunk.instances.start <= range.end + 3 => {
+                    range.end = chunk.instances.end;
+                }
+                _ => {
+                    if let Some(range) = pending.replace(chunk.instances.clone()) {
+                        rpass.draw(0..4, range);
+                    }
+                }
+            }
+        }
+        if let Some(range) = pending {
+            rpass.draw(0..4, range);
+        }
+    }
+}
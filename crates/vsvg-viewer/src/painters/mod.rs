@@ -1,4 +1,5 @@
 mod basic_painter;
+mod dashed_line_painter;
 mod line_painter;
 mod page_size_painter;
 mod point_painter;
@@ -6,7 +7,8 @@ mod point_painter;
 use vsvg::Point;
 use wgpu::RenderPass;
 
-pub(crate) use basic_painter::{BasicPainter, BasicPainterData};
+pub(crate) use basic_painter::{BasicPainter, BasicPainterData, ColorVertex};
+pub(crate) use dashed_line_painter::{DashedLinePainter, DashedLinePainterData};
 pub(crate) use line_painter::{LinePainter, LinePainterData};
 pub(crate) use page_size_painter::{PageSizePainter, PageSizePainterData};
 pub(crate) use point_painter::{PointPainter, PointPainterData};
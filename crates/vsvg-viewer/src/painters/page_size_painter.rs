@@ -1,7 +1,4 @@
-use crate::engine::{
-    EngineRenderObjects, PAGE_BACKGROUND_COLOR, PAGE_BORDER_COLOR, PAGE_SHADOW_COLOR,
-    PAGE_SHADOW_SIZE,
-};
+use crate::engine::{EngineRenderObjects, ShowOptions, DARK_PAGE_BORDER_COLOR, PAGE_BORDER_COLOR};
 use crate::painters::{BasicPainter, BasicPainterData, Painter};
 use vsvg::PageSize;
 use wgpu::{BindGroup, RenderPass};
@@ -13,30 +10,44 @@ pub(crate) struct PageSizePainterData {
 }
 
 impl PageSizePainterData {
-    pub(crate) fn new(render_objects: &EngineRenderObjects, page_size: PageSize) -> Self {
+    pub(crate) fn new(
+        render_objects: &EngineRenderObjects,
+        page_size: PageSize,
+        options: &ShowOptions,
+        dark_mode: bool,
+    ) -> Self {
         #[allow(clippy::cast_possible_truncation)]
         let (w, h) = (page_size.w() as f32, page_size.h() as f32);
 
         // shadow
+        let offset = options.shadow_offset;
         let shadow_vertices = [
-            [PAGE_SHADOW_SIZE, h + PAGE_SHADOW_SIZE],
-            [PAGE_SHADOW_SIZE, h],
-            [w + PAGE_SHADOW_SIZE, h + PAGE_SHADOW_SIZE],
+            [offset, h + offset],
+            [offset, h],
+            [w + offset, h + offset],
             [w, h],
-            [w + PAGE_SHADOW_SIZE, PAGE_SHADOW_SIZE],
-            [w, PAGE_SHADOW_SIZE],
+            [w + offset, offset],
+            [w, offset],
         ];
         let background_vertices = [[0.0, 0.0], [w, 0.0], [0.0, h], [w, h]];
         let border_vertices = [[0., 0.], [w, 0.], [w, h], [0., h], [0., 0.]];
 
+        let shadow_color = options.shadow_color.to_rgba();
+        let background_color = options.page_color.to_rgba();
+        let border_color = if dark_mode {
+            DARK_PAGE_BORDER_COLOR
+        } else {
+            PAGE_BORDER_COLOR
+        };
+
         Self {
-            shadow: BasicPainterData::new(render_objects, shadow_vertices, PAGE_SHADOW_COLOR),
+            shadow: BasicPainterData::new(render_objects, shadow_vertices, shadow_color),
             background: BasicPainterData::new(
                 render_objects,
                 background_vertices,
-                PAGE_BACKGROUND_COLOR,
+                background_color,
             ),
-            border: BasicPainterData::new(render_objects, border_vertices, PAGE_BORDER_COLOR),
+            border: BasicPainterData::new(render_objects, border_vertices, border_color),
         }
     }
 }
@@ -0,0 +1,52 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    scale: f32,
+    anti_alias: f32,
+    screen_size: vec2<f32>,
+};
+
+@group(0) @binding(0) var<uniform> camera: CameraUniform;
+
+struct VertexInput {
+    @location(0) point: vec2<f32>,
+    @location(1) distance: f32,
+    @location(2) dash: f32,
+    @location(3) color: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) distance: f32,
+    @location(1) @interpolate(flat) dash: f32,
+    @location(2) @interpolate(flat) color: vec4<f32>,
+}
+
+fn unpack_color(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32(color & 255u),
+        f32((color >> 8u) & 255u),
+        f32((color >> 16u) & 255u),
+        f32((color >> 24u) & 255u),
+    ) / 255.0;
+}
+
+@vertex
+fn vs_main(
+    in_vertex: VertexInput
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = camera.view_proj * vec4<f32>(in_vertex.point, 0.0, 1.0);
+    out.distance = in_vertex.distance;
+    out.dash = in_vertex.dash;
+    out.color = unpack_color(in_vertex.color);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // dashes and gaps have the same length on screen, regardless of the zoom level
+    if fract(in.distance * camera.scale / (2.0 * in.dash)) >= 0.5 {
+        discard;
+    }
+    return in.color;
+}
@@ -3,14 +3,42 @@ use crate::frame_history::FrameHistory;
 use eframe::Frame;
 use egui::{Color32, Ui};
 
-use crate::document_widget::DocumentWidget;
+use crate::document_widget::{DocumentWidget, SHORTCUTS};
 use crate::ViewerApp;
 use std::sync::Arc;
 
 const VSVG_VIEWER_STORAGE_KEY: &str = "vsvg-viewer-state";
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+/// Keyboard shortcuts handled by the viewer window, as `(key, description)`, see also
+/// [`SHORTCUTS`].
+#[cfg(not(target_arch = "wasm32"))]
+const WINDOW_SHORTCUTS: [(&str, &str); 2] = [
+    ("F11", "Toggle fullscreen"),
+    ("F12", "Save a screenshot to the current directory"),
+];
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct PngExportOptions {
+    /// Destination file.
+    path: String,
+
+    /// Output resolution.
+    dpi: f64,
+}
+
+impl Default for PngExportOptions {
+    fn default() -> Self {
+        Self {
+            path: String::from("export.png"),
+            dpi: 150.0,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
+#[allow(clippy::struct_excessive_bools)]
 struct ViewerState {
     /// Show settings window.
     show_settings: bool,
@@ -20,6 +48,36 @@ struct ViewerState {
 
     /// Show memory window.
     show_memory: bool,
+
+    /// Use dark visuals.
+    dark_mode: bool,
+
+    /// Show PNG export window.
+    show_png_export: bool,
+
+    /// PNG export options.
+    png_export: PngExportOptions,
+
+    /// Show SVG export window.
+    show_svg_export: bool,
+
+    /// Destination file for SVG export.
+    svg_export_path: String,
+}
+
+impl Default for ViewerState {
+    fn default() -> Self {
+        Self {
+            show_settings: false,
+            show_inspection: false,
+            show_memory: false,
+            dark_mode: false,
+            show_png_export: false,
+            png_export: PngExportOptions::default(),
+            show_svg_export: false,
+            svg_export_path: String::from("export.svg"),
+        }
+    }
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -32,7 +90,17 @@ pub struct Viewer {
     /// Record frame performance
     frame_history: FrameHistory,
 
+    /// Result of the last PNG export, if any.
+    png_export_status: Option<anyhow::Result<String>>,
+
+    /// Result of the last SVG export, if any.
+    svg_export_status: Option<anyhow::Result<String>>,
+
     viewer_app: Box<dyn ViewerApp>,
+
+    /// window title to apply on the next frame
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_title: Option<String>,
 }
 
 impl Viewer {
@@ -50,6 +118,11 @@ impl Viewer {
         };
 
         let mut document_widget = DocumentWidget::new(cc, document_data)?;
+        if let Some(storage) = cc.storage {
+            document_widget.load(storage);
+        }
+        document_widget.set_dark_mode(state.dark_mode);
+        cc.egui_ctx.set_visuals(visuals(state.dark_mode));
 
         //TODO: better error handling
         viewer_app
@@ -60,21 +133,125 @@ impl Viewer {
             state,
             document_widget,
             frame_history: FrameHistory::default(),
+            png_export_status: None,
+            svg_export_status: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_title: Some(viewer_app.title()),
             viewer_app,
         })
     }
 
-    #[allow(clippy::unused_self)]
+    /// Handle the window-level keyboard shortcuts (see [`WINDOW_SHORTCUTS`]).
     #[cfg(not(target_arch = "wasm32"))]
-    fn menu_file(&self, frame: &mut Frame, ui: &mut Ui) {
+    fn handle_window_shortcuts(ctx: &egui::Context, frame: &mut Frame) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (fullscreen, screenshot) =
+            ctx.input(|i| (i.key_pressed(egui::Key::F11), i.key_pressed(egui::Key::F12)));
+        if fullscreen {
+            frame.set_fullscreen(!frame.info().window_info.fullscreen);
+        }
+        if screenshot {
+            frame.request_screenshot();
+        }
+    }
+
+    /// Apply the dark mode setting from the view menu, if it changed.
+    fn update_dark_mode(&mut self, ctx: &egui::Context) {
+        let dark_mode = self.document_widget.dark_mode();
+        if dark_mode != self.state.dark_mode {
+            self.state.dark_mode = dark_mode;
+            ctx.set_visuals(visuals(dark_mode));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn menu_file(&mut self, frame: &mut Frame, ui: &mut Ui) -> bool {
         ui.menu_button("File", |ui| {
+            if ui.button("Export SVG…").clicked() {
+                self.state.show_svg_export = true;
+                ui.close_menu();
+            }
+            if ui.button("Export PNG…").clicked() {
+                self.state.show_png_export = true;
+                ui.close_menu();
+            }
+            ui.separator();
             if ui.button("Quit").clicked() {
                 frame.close();
             }
-        });
+        })
+        .inner
+        .is_some()
     }
 
-    fn menu_debug(&mut self, ui: &mut Ui) {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn png_export_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.state.show_png_export;
+        egui::Window::new("Export PNG")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("png_export_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("File:");
+                        ui.text_edit_singleline(&mut self.state.png_export.path);
+                        ui.end_row();
+
+                        ui.label("DPI:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.state.png_export.dpi)
+                                .clamp_range(10.0..=2400.0)
+                                .speed(1.0),
+                        );
+                        ui.end_row();
+                    });
+
+                if ui.button("Export").clicked() {
+                    let options = &self.state.png_export;
+                    self.png_export_status = Some(
+                        self.document_widget
+                            .export_png(&options.path, options.dpi)
+                            .map(|()| format!("exported to {}", options.path)),
+                    );
+                }
+
+                export_status_ui(ui, self.png_export_status.as_ref());
+            });
+        self.state.show_png_export = open;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn svg_export_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.state.show_svg_export;
+        egui::Window::new("Export SVG")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.text_edit_singleline(&mut self.state.svg_export_path);
+                });
+                ui.label("Hidden layers are not exported.");
+
+                if ui.button("Export").clicked() {
+                    let path = &self.state.svg_export_path;
+                    self.svg_export_status = Some(
+                        self.document_widget
+                            .export_svg(path)
+                            .map(|()| format!("exported to {path}")),
+                    );
+                }
+
+                export_status_ui(ui, self.svg_export_status.as_ref());
+            });
+        self.state.show_svg_export = open;
+    }
+
+    fn menu_debug(&mut self, ui: &mut Ui) -> bool {
         ui.menu_button("Debug", |ui| {
             if ui.button("Show settings window").clicked() {
                 self.state.show_settings = true;
@@ -91,7 +268,32 @@ impl Viewer {
 
             ui.separator();
             Self::egui_debug_options_ui(ui);
-        });
+        })
+        .inner
+        .is_some()
+    }
+
+    fn menu_help(ui: &mut Ui) -> bool {
+        ui.menu_button("Help", |ui| {
+            ui.label("Keyboard shortcuts");
+            ui.separator();
+            egui::Grid::new("shortcuts_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let shortcuts = SHORTCUTS.iter().chain(&WINDOW_SHORTCUTS);
+                    #[cfg(target_arch = "wasm32")]
+                    let shortcuts = SHORTCUTS.iter();
+
+                    for (key, description) in shortcuts {
+                        ui.strong(*key);
+                        ui.label(*description);
+                        ui.end_row();
+                    }
+                });
+        })
+        .inner
+        .is_some()
     }
 
     fn egui_debug_options_ui(ui: &mut Ui) {
@@ -133,25 +335,86 @@ impl Viewer {
     }
 }
 
+/// Save a screenshot to a PNG file.
+#[cfg(not(target_arch = "wasm32"))]
+fn save_screenshot(image: &egui::ColorImage, path: &str) -> anyhow::Result<()> {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut pixmap = vsvg::tiny_skia::Pixmap::new(image.width() as u32, image.height() as u32)
+        .ok_or_else(|| anyhow::anyhow!("empty screenshot"))?;
+
+    // both use premultiplied RGBA
+    for (dst, src) in pixmap.data_mut().chunks_exact_mut(4).zip(&image.pixels) {
+        dst.copy_from_slice(&src.to_array());
+    }
+    pixmap.save_png(path)?;
+    Ok(())
+}
+
+/// Display the result of the last export, if any.
+#[cfg(not(target_arch = "wasm32"))]
+fn export_status_ui(ui: &mut Ui, status: Option<&anyhow::Result<String>>) {
+    match status {
+        Some(Ok(msg)) => {
+            ui.label(msg);
+        }
+        Some(Err(err)) => {
+            ui.colored_label(egui::Color32::RED, err.to_string());
+        }
+        None => {}
+    }
+}
+
+fn visuals(dark_mode: bool) -> egui::Visuals {
+    if dark_mode {
+        egui::Visuals::dark()
+    } else {
+        egui::Visuals::light()
+    }
+}
+
 impl eframe::App for Viewer {
     fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            // The top panel is often a good place for a menu bar:
-            egui::menu::bar(ui, |ui| {
-                #[cfg(not(target_arch = "wasm32"))]
-                self.menu_file(frame, ui);
-
-                self.document_widget.view_menu_ui(ui);
-                self.document_widget.layer_menu_ui(ui);
-                self.menu_debug(ui);
-                self.frame_history.ui(ui);
-                egui::warn_if_debug_build(ui);
-            });
-        });
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(title) = self.pending_title.take() {
+            frame.set_window_title(&title);
+        }
+
+        let menu_open = egui::TopBottomPanel::top("top_panel")
+            .show(ctx, |ui| {
+                // The top panel is often a good place for a menu bar:
+                egui::menu::bar(ui, |ui| {
+                    let mut menu_open = false;
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        menu_open |= self.menu_file(frame, ui);
+                    }
+
+                    menu_open |= self.document_widget.view_menu_ui(ui);
+                    menu_open |= self.document_widget.layer_menu_ui(ui);
+                    menu_open |= self.menu_debug(ui);
+                    menu_open |= Self::menu_help(ui);
+                    self.frame_history.ui(ui);
+                    egui::warn_if_debug_build(ui);
+                    menu_open
+                })
+                .inner
+            })
+            .inner;
+
+        // keys are meant for the open menu, if any
+        if !menu_open {
+            self.document_widget.handle_shortcuts(ctx);
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::handle_window_shortcuts(ctx, frame);
+        }
+        self.update_dark_mode(ctx);
 
         let panel_frame = egui::Frame::central_panel(&ctx.style())
             .inner_margin(egui::style::Margin::same(0.))
-            .fill(Color32::from_rgb(242, 242, 242));
+            .fill({
+                let color = self.document_widget.canvas_color();
+                Color32::from_rgba_unmultiplied(color.r, color.g, color.b, color.a)
+            });
 
         // hook for creating side panels
         //TODO: better error management
@@ -159,6 +422,14 @@ impl eframe::App for Viewer {
             .update(ctx, &mut self.document_widget)
             .expect("ViewerApp failed!!!");
 
+        if self.document_widget.show_stats() {
+            egui::SidePanel::right("stats_panel").show(ctx, |ui| {
+                ui.heading("Statistics");
+                ui.separator();
+                self.document_widget.stats_ui(ui);
+            });
+        }
+
         egui::CentralPanel::default()
             .frame(panel_frame)
             .show(ctx, |ui| self.document_widget.ui(ui));
@@ -177,6 +448,11 @@ impl eframe::App for Viewer {
                 ctx.inspection_ui(ui);
             });
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.png_export_window(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.svg_export_window(ctx);
+
         egui::Window::new("📝 Memory")
             .open(&mut self.state.show_memory)
             .resizable(false)
@@ -188,9 +464,26 @@ impl eframe::App for Viewer {
             .on_new_frame(ctx.input(|i| i.time), frame.info().cpu_usage);
     }
 
+    /// Save the screenshot requested with F12, if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn post_rendering(&mut self, _window_size_px: [u32; 2], frame: &Frame) {
+        if let Some(screenshot) = frame.screenshot() {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let path = format!("vsvg-screenshot-{timestamp}.png");
+            match save_screenshot(&screenshot, &path) {
+                Ok(()) => log::info!("screenshot saved to {path}"),
+                Err(err) => log::error!("failed to save screenshot to {path}: {err}"),
+            }
+        }
+    }
+
     /// Called by the framework to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, VSVG_VIEWER_STORAGE_KEY, &self.state);
+        self.document_widget.save(storage);
         self.viewer_app.save(storage);
     }
 }
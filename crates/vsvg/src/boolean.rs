@@ -0,0 +1,248 @@
+//! Boolean operations on closed paths, based on [`geo`]'s polygon clipping.
+
+use crate::{Document, DocumentTrait, LayerID, Path, PathMetadata, PathTrait, DEFAULT_TOLERANCE};
+use geo::{BooleanOps, BoundingRect, LineString, MultiLineString, MultiPolygon, Polygon};
+
+/// Boolean operation to apply with [`Document::boolean`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoolOp {
+    Union,
+    Intersection,
+    /// Region covered by the first operand but not by the second.
+    Difference,
+    /// Region covered by exactly one of the operands.
+    Xor,
+}
+
+/// Compute the region filled by the closed sub-paths of `paths`, according to the even-odd rule.
+///
+/// Open sub-paths and sub-paths with less than three distinct points are ignored.
+fn filled_region<'a>(paths: impl IntoIterator<Item = &'a Path>) -> MultiPolygon<f64> {
+    paths
+        .into_iter()
+        .flat_map(|path| path.flatten(DEFAULT_TOLERANCE))
+        .filter(|line| {
+            let points = line.data().points();
+            points.len() > 3 && points.first() == points.last()
+        })
+        .map(|line| {
+            let ring: LineString<f64> = line
+                .data()
+                .points()
+                .iter()
+                .map(|pt| (pt.x(), pt.y()))
+                .collect();
+            MultiPolygon::new(vec![Polygon::new(ring, vec![])])
+        })
+        .fold(MultiPolygon::new(vec![]), |region, ring| region.xor(&ring))
+}
+
+impl Document {
+    /// Apply a boolean operation to the regions filled by the closed paths of layers `a` and `b`.
+    ///
+    /// Each layer's region is determined with the even-odd rule, so overlapping paths within a
+    /// layer cancel out. Curves are flattened with [`DEFAULT_TOLERANCE`] and open paths are
+    /// ignored. A missing layer is treated as empty.
+    ///
+    /// Returns one closed path per resulting polygon, with its holes as additional sub-paths and
+    /// default metadata.
+    #[must_use]
+    pub fn boolean(&self, op: BoolOp, a: LayerID, b: LayerID) -> Vec<Path> {
+        let region = |id| {
+            filled_region(
+                self.try_get(id)
+                    .map(|layer| layer.paths.as_slice())
+                    .unwrap_or_default(),
+            )
+        };
+        let (a, b) = (region(a), region(b));
+
+        let result = match op {
+            BoolOp::Union => a.union(&b),
+            BoolOp::Intersection => a.intersection(&b),
+            BoolOp::Difference => a.difference(&b),
+            BoolOp::Xor => a.xor(&b),
+        };
+
+        result
+            .into_iter()
+            .map(|polygon| Path::from_metadata(polygon, PathMetadata::default()))
+            .collect()
+    }
+
+    /// Remove the portions of paths which are hidden behind the closed paths of layer `mask`.
+    ///
+    /// The mask's region is determined like for [`Document::boolean`]. Paths of all other layers
+    /// are split at the region's boundary and their inner portions are removed. Paths which
+    /// overlap the region's bounding box are flattened with [`DEFAULT_TOLERANCE`], others are
+    /// left untouched. The mask layer itself is not modified.
+    pub fn occlude(&mut self, mask: LayerID) {
+        let region = filled_region(
+            self.try_get(mask)
+                .map(|layer| layer.paths.as_slice())
+                .unwrap_or_default(),
+        );
+        let Some(region_bounds) = region.bounding_rect() else {
+            return;
+        };
+        let region_bounds = kurbo::Rect::new(
+            region_bounds.min().x,
+            region_bounds.min().y,
+            region_bounds.max().x,
+            region_bounds.max().y,
+        );
+
+        for (_, layer) in self.layers_mut().iter_mut().filter(|(id, _)| **id != mask) {
+            layer.paths = std::mem::take(&mut layer.paths)
+                .into_iter()
+                .flat_map(|path| {
+                    let overlaps = path.bounds().is_some_and(|bounds| {
+                        bounds.x0 <= region_bounds.x1
+                            && region_bounds.x0 <= bounds.x1
+                            && bounds.y0 <= region_bounds.y1
+                            && region_bounds.y0 <= bounds.y1
+                    });
+                    if !overlaps {
+                        return vec![path];
+                    }
+
+                    let lines: MultiLineString<f64> = path
+                        .flatten(DEFAULT_TOLERANCE)
+                        .iter()
+                        .map(|line| {
+                            line.data()
+                                .points()
+                                .iter()
+                                .map(|pt| (pt.x(), pt.y()))
+                                .collect::<LineString<f64>>()
+                        })
+                        .collect();
+
+                    region
+                        .clip(&lines, true)
+                        .into_iter()
+                        .map(|line| Path::from_metadata(line, path.metadata().clone()))
+                        .collect()
+                })
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use geo::Area;
+
+    fn area(paths: &[Path]) -> f64 {
+        paths
+            .iter()
+            .flat_map(|path| path.flatten(DEFAULT_TOLERANCE))
+            .map(|line| {
+                let ring: LineString<f64> = line
+                    .data()
+                    .points()
+                    .iter()
+                    .map(|pt| (pt.x(), pt.y()))
+                    .collect();
+                Polygon::new(ring, vec![]).signed_area()
+            })
+            .sum::<f64>()
+            .abs()
+    }
+
+    fn squares() -> Document {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Rect::new(0., 0., 10., 10.));
+        doc.push_path(2, kurbo::Rect::new(5., 5., 15., 15.));
+        doc
+    }
+
+    #[test]
+    fn test_boolean_difference() {
+        let doc = squares();
+        let result = doc.boolean(BoolOp::Difference, 1, 2);
+        assert_eq!(result.len(), 1);
+        assert_abs_diff_eq!(area(&result), 75.0, epsilon = 1e-9);
+
+        // L-shape: six distinct corners, closed
+        let lines = result[0].flatten(DEFAULT_TOLERANCE);
+        assert_eq!(lines.len(), 1);
+        let points = lines[0].data().points();
+        assert_eq!(points.len(), 7);
+        assert_eq!(points.first(), points.last());
+        assert_eq!(result[0].bounds(), Some(kurbo::Rect::new(0., 0., 10., 10.)));
+        assert!(lines[0]
+            .data()
+            .points()
+            .contains(&crate::Point::new(5., 5.)));
+    }
+
+    #[test]
+    fn test_boolean_ops() {
+        let doc = squares();
+        assert_abs_diff_eq!(
+            area(&doc.boolean(BoolOp::Union, 1, 2)),
+            175.0,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            area(&doc.boolean(BoolOp::Intersection, 1, 2)),
+            25.0,
+            epsilon = 1e-9
+        );
+        assert_eq!(doc.boolean(BoolOp::Xor, 1, 2).len(), 2);
+
+        // missing layers are empty
+        assert_eq!(doc.boolean(BoolOp::Union, 1, 3).len(), 1);
+        assert!(doc.boolean(BoolOp::Intersection, 1, 3).is_empty());
+    }
+
+    #[test]
+    fn test_boolean_even_odd() {
+        // a square with a hole, made of two paths
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Rect::new(0., 0., 10., 10.));
+        doc.push_path(1, kurbo::Rect::new(2., 2., 8., 8.));
+        doc.push_path(1, &[(0., 0.), (10., 10.)][..]); // open paths are ignored
+
+        let result = doc.boolean(BoolOp::Union, 1, 2);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].flatten(DEFAULT_TOLERANCE).len(), 2);
+    }
+
+    #[test]
+    fn test_occlude() {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Rect::new(0., 0., 10., 10.));
+        doc.push_path(2, &[(-5., 5.), (15., 5.)][..]);
+        doc.push_path(2, &[(2., 2.), (8., 8.)][..]);
+        doc.push_path(4, &[(-5., -5.), (-5., 15.), (15., 15.), (15., 20.)][..]);
+        let far = Path::from(kurbo::Circle::new((50., 50.), 5.));
+        doc.push_path(3, far.clone());
+        doc.get_mut(2).paths[0].metadata_mut().color = crate::Color::RED;
+
+        doc.occlude(1);
+
+        // the line passing under the square is split, the one inside is removed
+        let paths = &doc.try_get(2).unwrap().paths;
+        assert_eq!(paths.len(), 2);
+        let mut bounds: Vec<_> = paths.iter().map(|path| path.bounds().unwrap()).collect();
+        bounds.sort_by(|a, b| a.x0.total_cmp(&b.x0));
+        assert_eq!(bounds[0], kurbo::Rect::new(-5., 5., 0., 5.));
+        assert_eq!(bounds[1], kurbo::Rect::new(10., 5., 15., 5.));
+        assert!(paths
+            .iter()
+            .all(|path| path.metadata().color == crate::Color::RED));
+
+        // paths away from the mask and the mask itself are untouched
+        assert_eq!(doc.try_get(3).unwrap().paths, vec![far]);
+        assert_eq!(doc.try_get(1).unwrap().paths.len(), 1);
+
+        // visible polylines are kept in one piece
+        let paths = &doc.try_get(4).unwrap().paths;
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].data().elements().len(), 4);
+    }
+}
@@ -1,6 +1,16 @@
 use std::fmt;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    #[error("invalid hex color length {0} (expected 3, 4, 6 or 8 digits)")]
+    InvalidLength(usize),
+
+    #[error("invalid hex digit in color {0:?}")]
+    InvalidDigit(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -61,6 +71,161 @@ impl Color {
         }
     }
 
+    /// Parse a color from a hex string of the form `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa`.
+    ///
+    /// The leading `#` is optional.
+    pub fn from_hex(s: &str) -> Result<Self, ColorParseError> {
+        let hex = s.trim().strip_prefix('#').unwrap_or(s.trim());
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ColorParseError::InvalidDigit(s.to_owned()));
+        }
+        let len = hex.len();
+        if !matches!(len, 3 | 4 | 6 | 8) {
+            return Err(ColorParseError::InvalidLength(len));
+        }
+        let value = u32::from_str_radix(hex, 16)
+            .map_err(|_| ColorParseError::InvalidDigit(s.to_owned()))?;
+
+        // extract the n-th component (from the most significant) of `bits` bits each
+        #[allow(clippy::cast_possible_truncation)]
+        let component = |n: u32, count: u32, bits: u32| {
+            let mask = (1 << bits) - 1;
+            let c = ((value >> ((count - 1 - n) * bits)) & mask) as u8;
+            if bits == 4 {
+                c * 17
+            } else {
+                c
+            }
+        };
+
+        Ok(match len {
+            3 => Self::rgb(component(0, 3, 4), component(1, 3, 4), component(2, 3, 4)),
+            4 => Self::new(
+                component(0, 4, 4),
+                component(1, 4, 4),
+                component(2, 4, 4),
+                component(3, 4, 4),
+            ),
+            6 => Self::rgb(component(0, 3, 8), component(1, 3, 8), component(2, 3, 8)),
+            _ => Self::new(
+                component(0, 4, 8),
+                component(1, 4, 8),
+                component(2, 4, 8),
+                component(3, 4, 8),
+            ),
+        })
+    }
+
+    /// Build a color from hue (in degrees), saturation and lightness (both in `0.0..=1.0`).
+    ///
+    /// The hue wraps around, and saturation and lightness are clamped.
+    #[must_use]
+    pub fn from_hsl(h: f64, s: f64, l: f64, a: u8) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_hue_chroma(h, chroma, l - chroma / 2.0, a)
+    }
+
+    /// Build a color from hue (in degrees), saturation and value (both in `0.0..=1.0`).
+    ///
+    /// The hue wraps around, and saturation and value are clamped.
+    #[must_use]
+    pub fn from_hsv(h: f64, s: f64, v: f64, a: u8) -> Self {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let chroma = v * s;
+        Self::from_hue_chroma(h, chroma, v - chroma, a)
+    }
+
+    /// Common part of the HSL/HSV to RGB conversion.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn from_hue_chroma(hue: f64, chroma: f64, offset: f64, alpha: u8) -> Self {
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let second = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (red, green, blue) = match sector {
+            s if s < 1.0 => (chroma, second, 0.0),
+            s if s < 2.0 => (second, chroma, 0.0),
+            s if s < 3.0 => (0.0, chroma, second),
+            s if s < 4.0 => (0.0, second, chroma),
+            s if s < 5.0 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+
+        let to_u8 = |c: f64| ((c + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self::new(to_u8(red), to_u8(green), to_u8(blue), alpha)
+    }
+
+    /// Returns the `(hue, max, min)` of the color, with hue in degrees and max/min in `0.0..=1.0`.
+    fn hue_max_min(self) -> (f64, f64, f64) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = f64::from(max - min);
+        let (r, g, b) = (f64::from(self.r), f64::from(self.g), f64::from(self.b));
+
+        let hue = if max == min {
+            0.0
+        } else if max == self.r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+
+        (hue, f64::from(max) / 255.0, f64::from(min) / 255.0)
+    }
+
+    /// Convert the color to `(hue, saturation, lightness)`, with hue in degrees (`0.0..360.0`) and
+    /// saturation and lightness in `0.0..=1.0`. The alpha channel is ignored.
+    #[must_use]
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (h, max, min) = self.hue_max_min();
+        let l = f64::midpoint(max, min);
+        let s = if self.r == self.g && self.g == self.b {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+
+        (h, s, l)
+    }
+
+    /// Convert the color to `(hue, saturation, value)`, with hue in degrees (`0.0..360.0`) and
+    /// saturation and value in `0.0..=1.0`. The alpha channel is ignored.
+    #[must_use]
+    pub fn to_hsv(&self) -> (f64, f64, f64) {
+        let (h, max, min) = self.hue_max_min();
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        (h, s, max)
+    }
+
+    /// Look up a color by its CSS name (e.g. `"tomato"`), ignoring case.
+    ///
+    /// This covers the CSS named-color table, as well as `transparent` (black with zero alpha).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Self::new(0, 0, 0, 0));
+        }
+
+        NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |(n, _)| n)
+            .ok()
+            .map(|idx| NAMED_COLORS[idx].1)
+    }
+
+    /// Format the color as `#rrggbb`, or `#rrggbbaa` if it is not fully opaque.
+    #[must_use]
+    pub fn to_hex_string(&self) -> String {
+        if self.a == 0xFF {
+            self.to_rgb_string()
+        } else {
+            self.to_string()
+        }
+    }
+
     #[must_use]
     pub const fn to_rgba(&self) -> u32 {
         self.r as u32 | (self.g as u32) << 8 | (self.b as u32) << 16 | (self.a as u32) << 24
@@ -97,3 +262,291 @@ impl fmt::Display for Color {
         )
     }
 }
+
+/// CSS named colors, sorted by name.
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("aliceblue", Color::rgb(0xF0, 0xF8, 0xFF)),
+    ("antiquewhite", Color::rgb(0xFA, 0xEB, 0xD7)),
+    ("aqua", Color::rgb(0x00, 0xFF, 0xFF)),
+    ("aquamarine", Color::rgb(0x7F, 0xFF, 0xD4)),
+    ("azure", Color::rgb(0xF0, 0xFF, 0xFF)),
+    ("beige", Color::rgb(0xF5, 0xF5, 0xDC)),
+    ("bisque", Color::rgb(0xFF, 0xE4, 0xC4)),
+    ("black", Color::rgb(0x00, 0x00, 0x00)),
+    ("blanchedalmond", Color::rgb(0xFF, 0xEB, 0xCD)),
+    ("blue", Color::rgb(0x00, 0x00, 0xFF)),
+    ("blueviolet", Color::rgb(0x8A, 0x2B, 0xE2)),
+    ("brown", Color::rgb(0xA5, 0x2A, 0x2A)),
+    ("burlywood", Color::rgb(0xDE, 0xB8, 0x87)),
+    ("cadetblue", Color::rgb(0x5F, 0x9E, 0xA0)),
+    ("chartreuse", Color::rgb(0x7F, 0xFF, 0x00)),
+    ("chocolate", Color::rgb(0xD2, 0x69, 0x1E)),
+    ("coral", Color::rgb(0xFF, 0x7F, 0x50)),
+    ("cornflowerblue", Color::rgb(0x64, 0x95, 0xED)),
+    ("cornsilk", Color::rgb(0xFF, 0xF8, 0xDC)),
+    ("crimson", Color::rgb(0xDC, 0x14, 0x3C)),
+    ("cyan", Color::rgb(0x00, 0xFF, 0xFF)),
+    ("darkblue", Color::rgb(0x00, 0x00, 0x8B)),
+    ("darkcyan", Color::rgb(0x00, 0x8B, 0x8B)),
+    ("darkgoldenrod", Color::rgb(0xB8, 0x86, 0x0B)),
+    ("darkgray", Color::rgb(0xA9, 0xA9, 0xA9)),
+    ("darkgreen", Color::rgb(0x00, 0x64, 0x00)),
+    ("darkgrey", Color::rgb(0xA9, 0xA9, 0xA9)),
+    ("darkkhaki", Color::rgb(0xBD, 0xB7, 0x6B)),
+    ("darkmagenta", Color::rgb(0x8B, 0x00, 0x8B)),
+    ("darkolivegreen", Color::rgb(0x55, 0x6B, 0x2F)),
+    ("darkorange", Color::rgb(0xFF, 0x8C, 0x00)),
+    ("darkorchid", Color::rgb(0x99, 0x32, 0xCC)),
+    ("darkred", Color::rgb(0x8B, 0x00, 0x00)),
+    ("darksalmon", Color::rgb(0xE9, 0x96, 0x7A)),
+    ("darkseagreen", Color::rgb(0x8F, 0xBC, 0x8F)),
+    ("darkslateblue", Color::rgb(0x48, 0x3D, 0x8B)),
+    ("darkslategray", Color::rgb(0x2F, 0x4F, 0x4F)),
+    ("darkslategrey", Color::rgb(0x2F, 0x4F, 0x4F)),
+    ("darkturquoise", Color::rgb(0x00, 0xCE, 0xD1)),
+    ("darkviolet", Color::rgb(0x94, 0x00, 0xD3)),
+    ("deeppink", Color::rgb(0xFF, 0x14, 0x93)),
+    ("deepskyblue", Color::rgb(0x00, 0xBF, 0xFF)),
+    ("dimgray", Color::rgb(0x69, 0x69, 0x69)),
+    ("dimgrey", Color::rgb(0x69, 0x69, 0x69)),
+    ("dodgerblue", Color::rgb(0x1E, 0x90, 0xFF)),
+    ("firebrick", Color::rgb(0xB2, 0x22, 0x22)),
+    ("floralwhite", Color::rgb(0xFF, 0xFA, 0xF0)),
+    ("forestgreen", Color::rgb(0x22, 0x8B, 0x22)),
+    ("fuchsia", Color::rgb(0xFF, 0x00, 0xFF)),
+    ("gainsboro", Color::rgb(0xDC, 0xDC, 0xDC)),
+    ("ghostwhite", Color::rgb(0xF8, 0xF8, 0xFF)),
+    ("gold", Color::rgb(0xFF, 0xD7, 0x00)),
+    ("goldenrod", Color::rgb(0xDA, 0xA5, 0x20)),
+    ("gray", Color::rgb(0x80, 0x80, 0x80)),
+    ("green", Color::rgb(0x00, 0x80, 0x00)),
+    ("greenyellow", Color::rgb(0xAD, 0xFF, 0x2F)),
+    ("grey", Color::rgb(0x80, 0x80, 0x80)),
+    ("honeydew", Color::rgb(0xF0, 0xFF, 0xF0)),
+    ("hotpink", Color::rgb(0xFF, 0x69, 0xB4)),
+    ("indianred", Color::rgb(0xCD, 0x5C, 0x5C)),
+    ("indigo", Color::rgb(0x4B, 0x00, 0x82)),
+    ("ivory", Color::rgb(0xFF, 0xFF, 0xF0)),
+    ("khaki", Color::rgb(0xF0, 0xE6, 0x8C)),
+    ("lavender", Color::rgb(0xE6, 0xE6, 0xFA)),
+    ("lavenderblush", Color::rgb(0xFF, 0xF0, 0xF5)),
+    ("lawngreen", Color::rgb(0x7C, 0xFC, 0x00)),
+    ("lemonchiffon", Color::rgb(0xFF, 0xFA, 0xCD)),
+    ("lightblue", Color::rgb(0xAD, 0xD8, 0xE6)),
+    ("lightcoral", Color::rgb(0xF0, 0x80, 0x80)),
+    ("lightcyan", Color::rgb(0xE0, 0xFF, 0xFF)),
+    ("lightgoldenrodyellow", Color::rgb(0xFA, 0xFA, 0xD2)),
+    ("lightgray", Color::rgb(0xD3, 0xD3, 0xD3)),
+    ("lightgreen", Color::rgb(0x90, 0xEE, 0x90)),
+    ("lightgrey", Color::rgb(0xD3, 0xD3, 0xD3)),
+    ("lightpink", Color::rgb(0xFF, 0xB6, 0xC1)),
+    ("lightsalmon", Color::rgb(0xFF, 0xA0, 0x7A)),
+    ("lightseagreen", Color::rgb(0x20, 0xB2, 0xAA)),
+    ("lightskyblue", Color::rgb(0x87, 0xCE, 0xFA)),
+    ("lightslategray", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightslategrey", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightsteelblue", Color::rgb(0xB0, 0xC4, 0xDE)),
+    ("lightyellow", Color::rgb(0xFF, 0xFF, 0xE0)),
+    ("lime", Color::rgb(0x00, 0xFF, 0x00)),
+    ("limegreen", Color::rgb(0x32, 0xCD, 0x32)),
+    ("linen", Color::rgb(0xFA, 0xF0, 0xE6)),
+    ("magenta", Color::rgb(0xFF, 0x00, 0xFF)),
+    ("maroon", Color::rgb(0x80, 0x00, 0x00)),
+    ("mediumaquamarine", Color::rgb(0x66, 0xCD, 0xAA)),
+    ("mediumblue", Color::rgb(0x00, 0x00, 0xCD)),
+    ("mediumorchid", Color::rgb(0xBA, 0x55, 0xD3)),
+    ("mediumpurple", Color::rgb(0x93, 0x70, 0xDB)),
+    ("mediumseagreen", Color::rgb(0x3C, 0xB3, 0x71)),
+    ("mediumslateblue", Color::rgb(0x7B, 0x68, 0xEE)),
+    ("mediumspringgreen", Color::rgb(0x00, 0xFA, 0x9A)),
+    ("mediumturquoise", Color::rgb(0x48, 0xD1, 0xCC)),
+    ("mediumvioletred", Color::rgb(0xC7, 0x15, 0x85)),
+    ("midnightblue", Color::rgb(0x19, 0x19, 0x70)),
+    ("mintcream", Color::rgb(0xF5, 0xFF, 0xFA)),
+    ("mistyrose", Color::rgb(0xFF, 0xE4, 0xE1)),
+    ("moccasin", Color::rgb(0xFF, 0xE4, 0xB5)),
+    ("navajowhite", Color::rgb(0xFF, 0xDE, 0xAD)),
+    ("navy", Color::rgb(0x00, 0x00, 0x80)),
+    ("oldlace", Color::rgb(0xFD, 0xF5, 0xE6)),
+    ("olive", Color::rgb(0x80, 0x80, 0x00)),
+    ("olivedrab", Color::rgb(0x6B, 0x8E, 0x23)),
+    ("orange", Color::rgb(0xFF, 0xA5, 0x00)),
+    ("orangered", Color::rgb(0xFF, 0x45, 0x00)),
+    ("orchid", Color::rgb(0xDA, 0x70, 0xD6)),
+    ("palegoldenrod", Color::rgb(0xEE, 0xE8, 0xAA)),
+    ("palegreen", Color::rgb(0x98, 0xFB, 0x98)),
+    ("paleturquoise", Color::rgb(0xAF, 0xEE, 0xEE)),
+    ("palevioletred", Color::rgb(0xDB, 0x70, 0x93)),
+    ("papayawhip", Color::rgb(0xFF, 0xEF, 0xD5)),
+    ("peachpuff", Color::rgb(0xFF, 0xDA, 0xB9)),
+    ("peru", Color::rgb(0xCD, 0x85, 0x3F)),
+    ("pink", Color::rgb(0xFF, 0xC0, 0xCB)),
+    ("plum", Color::rgb(0xDD, 0xA0, 0xDD)),
+    ("powderblue", Color::rgb(0xB0, 0xE0, 0xE6)),
+    ("purple", Color::rgb(0x80, 0x00, 0x80)),
+    ("rebeccapurple", Color::rgb(0x66, 0x33, 0x99)),
+    ("red", Color::rgb(0xFF, 0x00, 0x00)),
+    ("rosybrown", Color::rgb(0xBC, 0x8F, 0x8F)),
+    ("royalblue", Color::rgb(0x41, 0x69, 0xE1)),
+    ("saddlebrown", Color::rgb(0x8B, 0x45, 0x13)),
+    ("salmon", Color::rgb(0xFA, 0x80, 0x72)),
+    ("sandybrown", Color::rgb(0xF4, 0xA4, 0x60)),
+    ("seagreen", Color::rgb(0x2E, 0x8B, 0x57)),
+    ("seashell", Color::rgb(0xFF, 0xF5, 0xEE)),
+    ("sienna", Color::rgb(0xA0, 0x52, 0x2D)),
+    ("silver", Color::rgb(0xC0, 0xC0, 0xC0)),
+    ("skyblue", Color::rgb(0x87, 0xCE, 0xEB)),
+    ("slateblue", Color::rgb(0x6A, 0x5A, 0xCD)),
+    ("slategray", Color::rgb(0x70, 0x80, 0x90)),
+    ("slategrey", Color::rgb(0x70, 0x80, 0x90)),
+    ("snow", Color::rgb(0xFF, 0xFA, 0xFA)),
+    ("springgreen", Color::rgb(0x00, 0xFF, 0x7F)),
+    ("steelblue", Color::rgb(0x46, 0x82, 0xB4)),
+    ("tan", Color::rgb(0xD2, 0xB4, 0x8C)),
+    ("teal", Color::rgb(0x00, 0x80, 0x80)),
+    ("thistle", Color::rgb(0xD8, 0xBF, 0xD8)),
+    ("tomato", Color::rgb(0xFF, 0x63, 0x47)),
+    ("turquoise", Color::rgb(0x40, 0xE0, 0xD0)),
+    ("violet", Color::rgb(0xEE, 0x82, 0xEE)),
+    ("wheat", Color::rgb(0xF5, 0xDE, 0xB3)),
+    ("white", Color::rgb(0xFF, 0xFF, 0xFF)),
+    ("whitesmoke", Color::rgb(0xF5, 0xF5, 0xF5)),
+    ("yellow", Color::rgb(0xFF, 0xFF, 0x00)),
+    ("yellowgreen", Color::rgb(0x9A, 0xCD, 0x32)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(Color::from_hex("#ff0000"), Ok(Color::RED));
+        assert_eq!(Color::from_hex("00ff00"), Ok(Color::GREEN));
+        assert_eq!(Color::from_hex("#00F"), Ok(Color::BLUE));
+        assert_eq!(Color::from_hex("#0000"), Ok(Color::new(0, 0, 0, 0)));
+        assert_eq!(
+            Color::from_hex("#12345678"),
+            Ok(Color::new(0x12, 0x34, 0x56, 0x78))
+        );
+        assert_eq!(
+            Color::from_hex(" #AbCdEf "),
+            Ok(Color::rgb(0xAB, 0xCD, 0xEF))
+        );
+    }
+
+    #[test]
+    fn test_color_from_hex_invalid() {
+        assert_eq!(Color::from_hex(""), Err(ColorParseError::InvalidLength(0)));
+        assert_eq!(
+            Color::from_hex("#12345"),
+            Err(ColorParseError::InvalidLength(5))
+        );
+        assert!(matches!(
+            Color::from_hex("#ggg"),
+            Err(ColorParseError::InvalidDigit(_))
+        ));
+        assert!(matches!(
+            Color::from_hex("#+ff"),
+            Err(ColorParseError::InvalidDigit(_))
+        ));
+        assert!(matches!(
+            Color::from_hex("#ff00é"),
+            Err(ColorParseError::InvalidDigit(_))
+        ));
+    }
+
+    #[test]
+    fn test_color_from_name() {
+        assert_eq!(Color::from_name("red"), Some(Color::RED));
+        assert_eq!(Color::from_name("lime"), Some(Color::GREEN));
+        assert_eq!(Color::from_name("Tomato"), Color::from_hex("#ff6347").ok());
+        assert_eq!(
+            Color::from_name("REBECCAPURPLE"),
+            Some(Color::rgb(0x66, 0x33, 0x99))
+        );
+        assert_eq!(
+            Color::from_name("cornflowerblue"),
+            Some(Color::rgb(0x64, 0x95, 0xED))
+        );
+        assert_eq!(Color::from_name("transparent").map(|c| c.a), Some(0));
+        assert_eq!(Color::from_name("notacolor"), None);
+        assert_eq!(Color::from_name(""), None);
+    }
+
+    #[test]
+    fn test_color_from_hsl_hsv() {
+        assert_eq!(Color::from_hsl(0.0, 1.0, 0.5, 255), Color::RED);
+        assert_eq!(Color::from_hsl(120.0, 1.0, 0.5, 255), Color::GREEN);
+        assert_eq!(
+            Color::from_hsl(-120.0, 1.0, 0.5, 128),
+            Color::new(0, 0, 255, 128)
+        );
+        assert_eq!(Color::from_hsl(480.0, 1.0, 0.5, 255), Color::GREEN);
+        assert_eq!(Color::from_hsl(42.0, 0.0, 1.0, 255), Color::WHITE);
+        assert_eq!(
+            Color::from_hsl(270.0, 0.5, 0.4, 255),
+            Color::rgb(0x66, 0x33, 0x99)
+        );
+
+        assert_eq!(Color::from_hsv(60.0, 1.0, 1.0, 255), Color::YELLOW);
+        assert_eq!(Color::from_hsv(0.0, 0.0, 0.0, 255), Color::BLACK);
+        assert_eq!(Color::from_hsv(0.0, 1.0, 0.545, 255), Color::DARK_RED);
+        assert_eq!(
+            Color::from_hsv(300.0, 2.0, 1.0, 255),
+            Color::rgb(255, 0, 255)
+        );
+    }
+
+    #[test]
+    fn test_color_to_hsl_hsv() {
+        let (h, s, l) = Color::rgb(0x66, 0x33, 0x99).to_hsl();
+        assert_abs_diff_eq!(h, 270.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(s, 0.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(l, 0.4, epsilon = 1e-9);
+
+        let (h, s, v) = Color::rgb(0x66, 0x33, 0x99).to_hsv();
+        assert_abs_diff_eq!(h, 270.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(s, 2.0 / 3.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(v, 0.6, epsilon = 1e-9);
+
+        let (h, s, l) = Color::GRAY.to_hsl();
+        assert_abs_diff_eq!(h, 0.0);
+        assert_abs_diff_eq!(s, 0.0);
+        assert_abs_diff_eq!(l, 160.0 / 255.0);
+    }
+
+    #[test]
+    fn test_color_hsl_hsv_round_trip() {
+        for color in [
+            Color::RED,
+            Color::GOLD,
+            Color::KHAKI,
+            Color::DARK_GREEN,
+            Color::LIGHT_BLUE,
+            Color::rgb(0xDC, 0x14, 0x3C),
+            Color::rgb(0x1E, 0x90, 0xFF),
+        ] {
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Color::from_hsl(h, s, l, 255), color);
+            let (h, s, v) = color.to_hsv();
+            assert_eq!(Color::from_hsv(h, s, v, 255), color);
+        }
+    }
+
+    #[test]
+    fn test_named_colors_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_color_to_hex_string() {
+        assert_eq!(Color::rgb(0xAB, 0xCD, 0xEF).to_hex_string(), "#abcdef");
+        assert_eq!(Color::new(1, 2, 3, 4).to_hex_string(), "#01020304");
+
+        for color in [Color::KHAKI, Color::new(1, 2, 3, 4), Color::DARK_RED] {
+            assert_eq!(Color::from_hex(&color.to_hex_string()), Ok(color));
+        }
+    }
+}
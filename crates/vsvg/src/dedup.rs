@@ -0,0 +1,83 @@
+//! Detection of coincident segments.
+
+use kurbo::{ParamCurve, PathSeg};
+
+type KdTree = kdtree::KdTree<f64, usize, [f64; 2]>;
+
+/// Spatial index of the segments drawn so far, used to detect duplicates.
+///
+/// Two segments are considered coincident if their end points and mid-points are within
+/// `tolerance` of each other, regardless of direction.
+pub(crate) struct SegmentIndex {
+    tree: KdTree,
+    segments: Vec<PathSeg>,
+    tolerance: f64,
+}
+
+impl SegmentIndex {
+    pub(crate) fn new(tolerance: f64) -> Self {
+        Self {
+            tree: KdTree::new(2),
+            segments: vec![],
+            tolerance: tolerance.max(0.0),
+        }
+    }
+
+    /// Add `seg` to the index, unless it is coincident with an already indexed segment.
+    ///
+    /// Returns `true` if the segment was added.
+    pub(crate) fn insert(&mut self, seg: PathSeg) -> bool {
+        let mid = seg.eval(0.5);
+        let (start, end) = (seg.start(), seg.end());
+        let close = |a: kurbo::Point, b: kurbo::Point| a.distance(b) <= self.tolerance;
+
+        let duplicate = self
+            .tree
+            .within(
+                &[mid.x, mid.y],
+                self.tolerance * self.tolerance,
+                &kdtree::distance::squared_euclidean,
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .any(|(_, idx)| {
+                let other = &self.segments[*idx];
+                (close(start, other.start()) && close(end, other.end()))
+                    || (close(start, other.end()) && close(end, other.start()))
+            });
+
+        if !duplicate {
+            // only fails for non-finite coordinates, which cannot be matched anyway
+            let _ = self.tree.add([mid.x, mid.y], self.segments.len());
+            self.segments.push(seg);
+        }
+
+        !duplicate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::{CubicBez, Line};
+
+    #[test]
+    fn test_segment_index() {
+        let mut index = SegmentIndex::new(0.1);
+        assert!(index.insert(PathSeg::Line(Line::new((0., 0.), (10., 0.)))));
+
+        // exact, reversed and near duplicates
+        assert!(!index.insert(PathSeg::Line(Line::new((0., 0.), (10., 0.)))));
+        assert!(!index.insert(PathSeg::Line(Line::new((10., 0.), (0., 0.)))));
+        assert!(!index.insert(PathSeg::Line(Line::new((0., 0.05), (10., 0.05)))));
+
+        // overlapping but not coincident
+        assert!(index.insert(PathSeg::Line(Line::new((0., 0.), (5., 0.)))));
+        assert!(index.insert(PathSeg::Line(Line::new((0., 0.2), (10., 0.2)))));
+
+        // same end points but different shape
+        let curve = CubicBez::new((0., 0.), (0., 5.), (10., 5.), (10., 0.));
+        assert!(index.insert(PathSeg::Cubic(curve)));
+        assert!(!index.insert(PathSeg::Cubic(curve.subsegment(1.0..0.0))));
+    }
+}
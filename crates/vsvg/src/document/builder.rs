@@ -0,0 +1,171 @@
+use super::{Document, DocumentTrait, LayerID};
+use crate::{Color, Layer, LayerTrait, PageSize, Path, PathMetadata, PathTrait};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BuilderError {
+    #[error("layer {0} is defined more than once")]
+    DuplicateLayer(LayerID),
+}
+
+/// Fluent builder for [`Document`], created with [`Document::builder`].
+///
+/// ```
+/// use vsvg::{Color, Document, DocumentTrait, PageSize};
+///
+/// let doc = Document::builder()
+///     .page_size(PageSize::A4V)
+///     .layer(1)
+///     .color(Color::RED)
+///     .stroke_width(2.0)
+///     .path([[0., 0.], [100., 0.], [50., 80.], [0., 0.]])
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(doc.layers().len(), 1);
+/// assert_eq!(doc.metadata().page_size, Some(PageSize::A4V));
+/// ```
+#[derive(Debug, Default)]
+pub struct DocumentBuilder {
+    page_size: Option<PageSize>,
+    layers: Vec<(LayerID, Layer)>,
+}
+
+impl DocumentBuilder {
+    #[must_use]
+    pub fn page_size(mut self, page_size: PageSize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Start a new layer with the given ID.
+    #[must_use]
+    pub fn layer(self, id: LayerID) -> LayerBuilder {
+        LayerBuilder {
+            document: self,
+            id,
+            layer: Layer::default(),
+            metadata: PathMetadata::default(),
+        }
+    }
+
+    /// Build the document, failing if the same layer ID was used more than once.
+    pub fn build(self) -> Result<Document, BuilderError> {
+        let mut doc = Document::default();
+        doc.metadata_mut().page_size = self.page_size;
+
+        for (id, layer) in self.layers {
+            if doc.layers().contains_key(&id) {
+                return Err(BuilderError::DuplicateLayer(id));
+            }
+            doc.layers_mut().insert(id, layer);
+        }
+
+        Ok(doc)
+    }
+}
+
+/// Builder for a single layer of a [`DocumentBuilder`].
+///
+/// The color and stroke width apply to the paths added after they are set.
+#[derive(Debug)]
+pub struct LayerBuilder {
+    document: DocumentBuilder,
+    id: LayerID,
+    layer: Layer,
+    metadata: PathMetadata,
+}
+
+impl LayerBuilder {
+    /// Add a polyline path made of the given points.
+    #[must_use]
+    pub fn path(mut self, points: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        let mut path = Path::from_points(points);
+        *path.metadata_mut() = self.metadata.clone();
+        self.layer.push_path(path);
+        self
+    }
+
+    #[must_use]
+    pub fn color(mut self, color: Color) -> Self {
+        self.metadata.color = color;
+        self
+    }
+
+    #[must_use]
+    pub fn stroke_width(mut self, stroke_width: f64) -> Self {
+        self.metadata.stroke_width = stroke_width;
+        self
+    }
+
+    /// Finish this layer and start a new one with the given ID.
+    #[must_use]
+    pub fn layer(self, id: LayerID) -> LayerBuilder {
+        self.finish().layer(id)
+    }
+
+    /// Finish this layer and build the document.
+    ///
+    /// See [`DocumentBuilder::build`].
+    pub fn build(self) -> Result<Document, BuilderError> {
+        self.finish().build()
+    }
+
+    /// Finish this layer and return to the document builder.
+    #[must_use]
+    pub fn finish(mut self) -> DocumentBuilder {
+        self.document.layers.push((self.id, self.layer));
+        self.document
+    }
+}
+
+impl Document {
+    #[must_use]
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_builder() {
+        let doc = Document::builder()
+            .page_size(PageSize::A5H)
+            .layer(3)
+            .path([[0., 0.], [10., 0.]])
+            .color(Color::BLUE)
+            .stroke_width(0.5)
+            .path([[0., 0.], [0., 10.], [10., 10.]])
+            .layer(1)
+            .finish()
+            .build()
+            .unwrap();
+
+        assert_eq!(doc.metadata().page_size, Some(PageSize::A5H));
+        assert!(doc.layers().keys().eq([1, 3].iter()));
+        assert!(doc.try_get(1).unwrap().paths.is_empty());
+
+        let paths = &doc.try_get(3).unwrap().paths;
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].metadata(), &PathMetadata::default());
+        assert_eq!(paths[1].metadata().color, Color::BLUE);
+        assert_abs_diff_eq!(paths[1].metadata().stroke_width, 0.5);
+        assert_eq!(paths[1].data().elements().len(), 3);
+    }
+
+    #[test]
+    fn test_builder_duplicate_layer() {
+        assert_eq!(
+            Document::builder()
+                .layer(1)
+                .layer(2)
+                .layer(1)
+                .build()
+                .unwrap_err(),
+            BuilderError::DuplicateLayer(1)
+        );
+    }
+}
@@ -1,16 +1,119 @@
 use super::{DocumentMetadata, DocumentTrait, FlattenedDocument, LayerID};
-use crate::{Layer, PageSize, Path, Transforms};
-use std::collections::BTreeMap;
+use crate::{
+    Color, Layer, LayerTrait, Margins, PageSize, Path, PathTrait, Point, Transforms, Unit,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
 
-#[derive(Default, Clone, Debug)]
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FitToPageError {
+    #[error("the document has no page size")]
+    NoPageSize,
+
+    #[error("the margins leave no room on the page")]
+    MarginsTooLarge,
+}
+
+/// Version of the JSON format produced by [`Document::to_json`].
+const JSON_FORMAT_VERSION: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum JsonError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported JSON format version {0} (expected at most {JSON_FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+}
+
+/// Versioned envelope for the JSON representation of a [`Document`].
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JsonDocument<D> {
+    #[serde(default)]
+    version: u32,
+    document: D,
+}
+
+/// Last result of [`Document::flatten_shared`], along with its tolerance and the document
+/// generation it was computed for.
+#[derive(Debug, Clone)]
+struct CachedFlatten {
+    generation: u64,
+    tolerance: f64,
+    flattened: Arc<FlattenedDocument>,
+}
+
+/// Cache for [`Document::flatten_shared`].
+///
+/// The cache is not part of the document's value: it is ignored for comparison and
+/// serialization.
+#[derive(Default, Debug)]
+struct FlattenCache(Mutex<Option<CachedFlatten>>);
+
+impl FlattenCache {
+    fn get(&self, generation: u64, tolerance: f64) -> Option<Arc<FlattenedDocument>> {
+        let cache = self.0.lock().ok()?;
+        cache
+            .as_ref()
+            .filter(|cached| {
+                cached.generation == generation && cached.tolerance.to_bits() == tolerance.to_bits()
+            })
+            .map(|cached| cached.flattened.clone())
+    }
+
+    fn set(&self, generation: u64, tolerance: f64, flattened: Arc<FlattenedDocument>) {
+        if let Ok(mut cache) = self.0.lock() {
+            *cache = Some(CachedFlatten {
+                generation,
+                tolerance,
+                flattened,
+            });
+        }
+    }
+
+    fn clear(&mut self) {
+        if let Ok(cache) = self.0.get_mut() {
+            *cache = None;
+        }
+    }
+}
+
+impl Clone for FlattenCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(
+            self.0.lock().ok().and_then(|cache| cache.clone()),
+        ))
+    }
+}
+
+#[derive(Default, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)] // if we add new fields, give them default values when deserializing old files
 pub struct Document {
+    /// Mutating the layers directly bypasses the flatten cache, use
+    /// [`DocumentTrait::layers_mut`] or call [`Document::clear_flatten_cache`] afterwards.
     pub layers: BTreeMap<LayerID, Layer>,
     metadata: DocumentMetadata,
+
+    /// Bumped by every mutable access to the layers or metadata, to invalidate the flatten cache.
+    #[serde(skip)]
+    generation: u64,
+
+    #[serde(skip)]
+    flatten_cache: FlattenCache,
+}
+
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        // The flatten cache and generation are deliberately ignored: they only track a result
+        // derived from the layers and metadata, and depend on which calls were made before, so
+        // two documents with the same content are equal regardless of their cache.
+        self.layers == other.layers && self.metadata == other.metadata
+    }
 }
 
 impl Transforms for Document {
     fn transform(&mut self, affine: &kurbo::Affine) -> &mut Self {
-        self.layers.iter_mut().for_each(|(_, layer)| {
+        self.layers_mut().iter_mut().for_each(|(_, layer)| {
             layer.transform(affine);
         });
         self
@@ -23,6 +126,7 @@ impl DocumentTrait<Layer, Path, kurbo::BezPath> for Document {
     }
 
     fn layers_mut(&mut self) -> &mut BTreeMap<LayerID, Layer> {
+        self.generation += 1;
         &mut self.layers
     }
 
@@ -31,6 +135,7 @@ impl DocumentTrait<Layer, Path, kurbo::BezPath> for Document {
     }
 
     fn metadata_mut(&mut self) -> &mut DocumentMetadata {
+        self.generation += 1;
         &mut self.metadata
     }
 }
@@ -48,15 +153,207 @@ impl Document {
         }
     }
 
+    /// Flatten the document's curves into polylines, with the given tolerance.
+    ///
+    /// With the `parallel` feature, layers (and paths within layers) are flattened in parallel.
+    /// The output is identical regardless of the number of threads.
+    ///
+    /// If [`Document::flatten_shared`] was called with the same tolerance since the document was
+    /// last mutated, its cached result is copied. Otherwise, the document is flattened and the
+    /// result is returned as is, without being cached.
     #[must_use]
     pub fn flatten(&self, tolerance: f64) -> FlattenedDocument {
-        FlattenedDocument::new(
+        match self.flatten_cache.get(self.generation, tolerance) {
+            Some(flattened) => FlattenedDocument::clone(&flattened),
+            None => self.flatten_uncached(tolerance),
+        }
+    }
+
+    /// Flatten the document like [`Document::flatten`], sharing the result with the cache.
+    ///
+    /// The result is cached, such that flattening again with the same tolerance is free. The
+    /// cache is invalidated by any mutable access to the document's layers or metadata through
+    /// [`DocumentTrait::layers_mut`] and [`DocumentTrait::metadata_mut`].
+    #[must_use]
+    pub fn flatten_shared(&self, tolerance: f64) -> Arc<FlattenedDocument> {
+        if let Some(flattened) = self.flatten_cache.get(self.generation, tolerance) {
+            return flattened;
+        }
+
+        let flattened = Arc::new(self.flatten_uncached(tolerance));
+        self.flatten_cache
+            .set(self.generation, tolerance, flattened.clone());
+        flattened
+    }
+
+    fn flatten_uncached(&self, tolerance: f64) -> FlattenedDocument {
+        #[cfg(feature = "parallel")]
+        let layers = {
+            use rayon::prelude::*;
+
             self.layers
-                .iter()
+                .par_iter()
                 .map(|(id, layer)| (*id, layer.flatten(tolerance)))
-                .collect(),
-            self.metadata.with_source_suffix(" (flattened)"),
-        )
+                .collect()
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let layers = self
+            .layers
+            .iter()
+            .map(|(id, layer)| (*id, layer.flatten(tolerance)))
+            .collect();
+
+        FlattenedDocument::new(layers, self.metadata.with_source_suffix(" (flattened)"))
+    }
+
+    /// Build a new document with only the paths for which `pred` returns `true`.
+    ///
+    /// Paths keep their layer, and all layers are kept with their metadata, including those left
+    /// without any matching path. The document metadata (e.g. page size) is kept.
+    #[must_use]
+    pub fn filter(&self, pred: impl Fn(LayerID, &Path) -> bool) -> Document {
+        let mut doc = Document {
+            metadata: self.metadata.clone(),
+            ..Default::default()
+        };
+
+        for (&lid, layer) in &self.layers {
+            let new_layer = doc.get_mut(lid);
+            *new_layer.metadata_mut() = layer.metadata().clone();
+            new_layer.paths = layer
+                .paths
+                .iter()
+                .filter(|path| pred(lid, path))
+                .cloned()
+                .collect();
+        }
+
+        doc
+    }
+
+    /// Build a new document with only the paths whose attribute `key` is `value`.
+    ///
+    /// See [`crate::PathMetadata::attrs`] and [`Document::filter`].
+    #[must_use]
+    pub fn filter_by_attr(&self, key: &str, value: &str) -> Document {
+        self.filter(|_, path| path.metadata().attrs.get(key).is_some_and(|v| v == value))
+    }
+
+    /// Build a new document with only the visible layers, e.g. to export what a viewer displays.
+    ///
+    /// Layers missing from `visibility` are considered visible. Visible layers are kept entirely,
+    /// including when empty, and the document metadata is kept.
+    #[must_use]
+    pub fn visible_subset(&self, visibility: &HashMap<LayerID, bool>) -> Document {
+        let mut doc = Document {
+            metadata: self.metadata.clone(),
+            ..Default::default()
+        };
+
+        for (&lid, layer) in &self.layers {
+            if *visibility.get(&lid).unwrap_or(&true) {
+                doc.layers.insert(lid, layer.clone());
+            }
+        }
+
+        doc
+    }
+
+    /// Merge the layers of `other` into this document.
+    ///
+    /// With `remap_layers`, the layers of `other` whose ID is already in use are given new IDs,
+    /// above all the existing ones. Otherwise, their paths are appended to the existing layer with
+    /// the same ID, which keeps its metadata. The page size of `other` is only used if this
+    /// document has none.
+    pub fn append(&mut self, other: Document, remap_layers: bool) {
+        if self.metadata.page_size.is_none() {
+            self.metadata_mut().page_size = other.metadata.page_size;
+        }
+
+        for (lid, mut layer) in other.layers {
+            let layers = self.layers_mut();
+            match layers.get_mut(&lid) {
+                None => {
+                    layers.insert(lid, layer);
+                }
+                Some(existing) if !remap_layers => existing.paths.append(&mut layer.paths),
+                Some(_) => {
+                    let new_lid = self.next_layer_id();
+                    self.layers_mut().insert(new_lid, layer);
+                }
+            }
+        }
+    }
+
+    /// Drop the cached result of [`Document::flatten_shared`].
+    ///
+    /// This is only needed after mutating [`Document::layers`] directly, as the cache is
+    /// otherwise invalidated whenever the document is mutated.
+    pub fn clear_flatten_cache(&mut self) {
+        self.flatten_cache.clear();
+    }
+
+    /// Set the winding direction of all paths.
+    ///
+    /// See [`Path::set_winding`].
+    pub fn normalize_winding(&mut self, clockwise: bool) {
+        self.for_each(|layer| {
+            layer
+                .paths
+                .iter_mut()
+                .for_each(|path| path.set_winding(clockwise));
+        });
+    }
+
+    /// Convex hull of all the document's points, as a closed path.
+    ///
+    /// See [`Path::convex_hull`].
+    #[must_use]
+    pub fn convex_hull(&self) -> Path {
+        self.convex_hull_filtered(|_, _| true)
+    }
+
+    /// Convex hull of the points of the layers for which `filter` returns `true`.
+    ///
+    /// This is useful to exclude hidden layers. See [`Path::convex_hull`].
+    #[must_use]
+    pub fn convex_hull_filtered(&self, filter: impl Fn(LayerID, &Layer) -> bool) -> Path {
+        let mut compound = kurbo::BezPath::new();
+        for (_, layer) in self
+            .layers
+            .iter()
+            .filter(|(lid, layer)| filter(**lid, layer))
+        {
+            for path in &layer.paths {
+                compound.extend(path.data().iter());
+            }
+        }
+
+        Path::from(compound).convex_hull()
+    }
+
+    /// Append a polyline with the given color and stroke width to a layer, creating the layer if
+    /// needed.
+    ///
+    /// Returns the new path, so it can be further tweaked. See [`DocumentTrait::push_path`] to
+    /// push arbitrary paths.
+    pub fn push_points(
+        &mut self,
+        layer: LayerID,
+        points: impl IntoIterator<Item = impl Into<Point>>,
+        color: Color,
+        stroke_width: f64,
+    ) -> &mut Path {
+        let mut path = Path::from_points(points);
+        let metadata = path.metadata_mut();
+        metadata.color = color;
+        metadata.stroke_width = stroke_width;
+
+        let paths = &mut self.get_mut(layer).paths;
+        paths.push(path);
+        let idx = paths.len() - 1;
+        &mut paths[idx]
     }
 
     #[must_use]
@@ -71,12 +368,108 @@ impl Document {
     }
 
     /// Crops the contents to the bounds provided.
+    ///
+    /// See [`Layer::crop`].
     pub fn crop(&mut self, x_min: f64, y_min: f64, x_max: f64, y_max: f64) {
-        self.layers.iter_mut().for_each(|(_, layer)| {
+        self.layers_mut().iter_mut().for_each(|(_, layer)| {
             layer.crop(x_min, y_min, x_max, y_max);
         });
     }
 
+    /// Crops the contents to the page, if the document has a page size.
+    ///
+    /// See [`Layer::crop`].
+    pub fn crop_to_page(&mut self) {
+        if let Some((w, h)) = self.metadata().page_dimensions() {
+            self.crop(0., 0., w, h);
+        }
+    }
+
+    /// Remove coincident segments in all layers and return the number of removed segments.
+    ///
+    /// Segments of different layers are never considered duplicates. See [`Layer::dedup_lines`].
+    pub fn dedup_lines(&mut self, tolerance: f64) -> usize {
+        self.layers_mut()
+            .values_mut()
+            .map(|layer| layer.dedup_lines(tolerance))
+            .sum()
+    }
+
+    /// Scales and translates the content uniformly such that it fits within the page's margins,
+    /// centered.
+    ///
+    /// Empty documents are left untouched.
+    pub fn fit_to_page(&mut self, margins: Margins) -> Result<(), FitToPageError> {
+        let (w, h) = self
+            .metadata()
+            .page_dimensions()
+            .ok_or(FitToPageError::NoPageSize)?;
+        let target = margins
+            .inner_rect(PageSize::new(w, h))
+            .ok_or(FitToPageError::MarginsTooLarge)?;
+
+        let Some(bounds) = self.bounds() else {
+            return Ok(());
+        };
+
+        // degenerate dimensions (e.g. a single horizontal line) do not constrain the scale
+        let scale = [
+            (bounds.width(), target.width()),
+            (bounds.height(), target.height()),
+        ]
+        .into_iter()
+        .filter(|(size, _)| *size > 0.0)
+        .map(|(size, target_size)| target_size / size)
+        .reduce(f64::min)
+        .unwrap_or(1.0);
+
+        let center = bounds.center();
+        let target_center = target.center();
+        self.translate(-center.x, -center.y)
+            .scale(scale)
+            .translate(target_center.x, target_center.y);
+
+        Ok(())
+    }
+
+    /// Re-express all lengths, currently in `from` units, in `to` units.
+    ///
+    /// Coordinates and stroke widths are scaled by the ratio between the units, with the 96 pixels
+    /// per inch convention for [`Unit::Px`]. Documents are normally in pixels, including those
+    /// loaded from SVG regardless of [`DocumentMetadata::source_unit`].
+    ///
+    /// The new unit is recorded in [`DocumentMetadata::unit`], which page operations (e.g.
+    /// [`Document::crop_to_page`]) and exports account for. The page keeps its physical size, and
+    /// is stored as a [`PageSize::Custom`] in `to` units, such that its dimensions match the new
+    /// coordinates (e.g. 210 x 297 for A4 in millimeters).
+    pub fn convert_units(&mut self, from: Unit, to: Unit) {
+        self.metadata_mut().unit = to;
+        let factor = from.to_px() / to.to_px();
+        #[allow(clippy::float_cmp)]
+        if factor == 1.0 {
+            return;
+        }
+
+        self.scale(factor);
+        for layer in self.layers_mut().values_mut() {
+            for path in &mut layer.paths {
+                path.metadata_mut().stroke_width *= factor;
+            }
+        }
+        if let Some(page_size) = self.metadata().page_size {
+            self.metadata_mut().page_size = Some(PageSize::Custom(
+                page_size.w() / to.to_px(),
+                page_size.h() / to.to_px(),
+                to,
+            ));
+        }
+    }
+
+    /// Convert a document in pixels to millimeters, see [`Document::convert_units`].
+    pub fn to_mm(&mut self) {
+        self.convert_units(Unit::Px, Unit::Mm);
+    }
+
     /// Translates the content of the document so that it's centered on the page.
     ///
     /// If the document has no page size defined, the content is translated such that its bounds
@@ -86,8 +479,7 @@ impl Document {
             return;
         };
 
-        let (dx, dy) = if let Some(page_size) = self.metadata().page_size {
-            let (w, h) = page_size.to_pixels();
+        let (dx, dy) = if let Some((w, h)) = self.metadata().page_dimensions() {
             let content_width = bounds.width();
             let content_height = bounds.height();
 
@@ -101,12 +493,738 @@ impl Document {
 
         self.translate(dx, dy);
     }
+
+    /// Serialize the document to JSON.
+    ///
+    /// Unlike SVG, this representation is lossless: it preserves curves, layer IDs and all
+    /// metadata. The output is wrapped in a versioned envelope: `{"version": 1, "document": ...}`.
+    pub fn to_json(&self) -> Result<String, JsonError> {
+        Ok(serde_json::to_string(&JsonDocument {
+            version: JSON_FORMAT_VERSION,
+            document: self,
+        })?)
+    }
+
+    /// Deserialize a document from JSON produced by [`Document::to_json`].
+    ///
+    /// Missing fields take their default value, so files written by older versions keep loading.
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        let json_doc: JsonDocument<Document> = serde_json::from_str(json)?;
+        if json_doc.version > JSON_FORMAT_VERSION {
+            return Err(JsonError::UnsupportedVersion(json_doc.version));
+        }
+
+        Ok(json_doc.document)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{Layer, LayerTrait, Unit};
+    use crate::{Color, Layer, LayerTrait, PathTrait, Unit};
+    use approx::assert_abs_diff_eq;
+
+    fn dense_document() -> Document {
+        let mut doc = Document::default();
+        for i in 0_u16..500 {
+            let (x, y) = (f64::from(i % 25) * 10., f64::from(i / 25) * 10.);
+            doc.push_path(
+                usize::from(i % 7),
+                kurbo::Circle::new((x, y), 3.0 + f64::from(i % 5)),
+            );
+            doc.push_path(
+                usize::from(i % 3),
+                &[(x, y), (x + 5., y + 2.), (x, y + 7.)][..],
+            );
+        }
+        doc
+    }
+
+    #[test]
+    fn test_document_flatten_order() {
+        let doc = dense_document();
+        let flattened = doc.flatten(0.01);
+
+        assert!(flattened.layers.keys().eq(doc.layers.keys()));
+        for (lid, layer) in &doc.layers {
+            let serial: Vec<_> = layer.paths.iter().flat_map(|p| p.flatten(0.01)).collect();
+            assert_eq!(flattened.layers[lid].paths, serial);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_document_flatten_parallel_deterministic() {
+        let doc = dense_document();
+        let flatten_with_threads = |num_threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap()
+                .install(|| doc.flatten(0.01))
+        };
+
+        let serial = flatten_with_threads(1);
+        assert_eq!(flatten_with_threads(2), serial);
+        assert_eq!(flatten_with_threads(8), serial);
+    }
+
+    #[test]
+    fn test_document_flatten_cache() {
+        let mut doc = dense_document();
+        let flattened = doc.flatten_shared(0.1);
+        assert!(Arc::ptr_eq(&doc.flatten_shared(0.1), &flattened));
+        assert_eq!(doc.flatten(0.1), *flattened);
+
+        // poison the cache to observe hits
+        let poisoned = Arc::new(FlattenedDocument::default());
+        doc.flatten_cache.set(doc.generation, 0.1, poisoned.clone());
+        assert!(doc.flatten(0.1).layers.is_empty());
+        assert!(Arc::ptr_eq(&doc.flatten_shared(0.1), &poisoned));
+        assert_eq!(doc.flatten(0.2).layers.len(), flattened.layers.len());
+
+        // a plain flatten doesn't populate the cache
+        assert!(doc.flatten_cache.get(doc.generation, 0.2).is_none());
+
+        doc.flatten_cache.set(doc.generation, 0.1, poisoned);
+        doc.clear_flatten_cache();
+        assert_eq!(doc.flatten(0.1), *flattened);
+    }
+
+    #[test]
+    fn test_document_flatten_cache_invalidation() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (10., 0.)][..]);
+        let bounds = doc.flatten_shared(0.1).bounds().unwrap();
+
+        doc.translate(5., 5.);
+        assert_eq!(
+            doc.flatten_shared(0.1).bounds().unwrap(),
+            bounds + kurbo::Vec2::new(5., 5.)
+        );
+
+        doc.push_path(2, &[(0., 0.), (0., 100.)][..]);
+        assert_eq!(doc.flatten_shared(0.1).layers.len(), 2);
+
+        doc.metadata_mut().source = Some("test".to_owned());
+        assert_eq!(
+            doc.flatten_shared(0.1).metadata().source.as_deref(),
+            Some("test (flattened)")
+        );
+
+        doc.crop(0., 0., 10., 10.);
+        assert_eq!(doc.flatten_shared(0.1).bounds(), doc.bounds());
+
+        // direct mutation of the public layers bypasses the cache until it is cleared
+        doc.layers.get_mut(&1).unwrap().paths.clear();
+        assert!(!doc.flatten_shared(0.1).layers[&1].paths.is_empty());
+        doc.clear_flatten_cache();
+        assert!(doc.flatten_shared(0.1).layers[&1].paths.is_empty());
+
+        // clones carry the cache, but it doesn't leak back
+        let mut clone = doc.clone();
+        clone.scale(2.0);
+        assert_ne!(clone.flatten_shared(0.1), doc.flatten_shared(0.1));
+    }
+
+    #[test]
+    fn test_document_push_points() {
+        let mut doc = Document::default();
+        let path = doc.push_points(2, vec![[0., 0.], [10., 0.]], Color::RED, 2.0);
+        path.metadata_mut().color = Color::BLUE;
+        doc.push_points(2, (0..4).map(|i| (f64::from(i), 1.0)), Color::GREEN, 0.5);
+
+        let paths = &doc.try_get(2).unwrap().paths;
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].metadata().color, Color::BLUE);
+        assert_abs_diff_eq!(paths[0].metadata().stroke_width, 2.0);
+        assert_eq!(paths[1].metadata().color, Color::GREEN);
+        assert_eq!(paths[1].data().elements().len(), 4);
+    }
+
+    #[test]
+    fn test_document_remove_merge_layers() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (1., 1.)][..]);
+        doc.push_path(1, &[(0., 0.), (2., 2.)][..]);
+        doc.push_path(2, &[(0., 0.), (3., 3.)][..]);
+        doc.push_path(3, &[(0., 0.), (4., 4.)][..]);
+
+        doc.merge_layers(1, 2);
+        assert!(doc.layers().keys().eq([1, 3].iter()));
+        let paths = &doc.try_get(1).unwrap().paths;
+        assert_eq!(paths.len(), 3);
+        assert_abs_diff_eq!(paths[2].bounds().unwrap().x1, 3.);
+
+        // no-ops
+        doc.merge_layers(1, 2);
+        doc.merge_layers(4, 3);
+        doc.merge_layers(3, 3);
+        assert_eq!(doc.try_get(1).unwrap().paths.len(), 3);
+        assert_eq!(doc.try_get(3).unwrap().paths.len(), 1);
+
+        assert_eq!(doc.remove_layer(3).unwrap().paths.len(), 1);
+        assert!(doc.remove_layer(3).is_none());
+        assert!(doc.layers().keys().eq([1].iter()));
+    }
+
+    #[test]
+    fn test_document_reorder_layers() {
+        let mut doc = Document::default();
+        for lid in 1..=4 {
+            doc.push_path(lid, &[(0., 0.), (1., 1.)][..]);
+            doc.get_mut(lid).metadata_mut().name = format!("layer {lid}");
+        }
+        let names = |doc: &Document| -> Vec<(LayerID, String)> {
+            doc.layers()
+                .iter()
+                .map(|(lid, layer)| (*lid, layer.metadata().name.clone()))
+                .collect()
+        };
+
+        doc.reorder_layers(&[4, 1, 2, 3]);
+        assert_eq!(
+            names(&doc),
+            vec![
+                (1, "layer 4".to_owned()),
+                (2, "layer 1".to_owned()),
+                (3, "layer 2".to_owned()),
+                (4, "layer 3".to_owned()),
+            ]
+        );
+
+        // partial, with unknown and duplicate IDs
+        let mut doc2 = doc.clone();
+        doc2.reorder_layers(&[4, 7, 2, 4]);
+        assert_eq!(
+            names(&doc2),
+            vec![
+                (1, "layer 4".to_owned()),
+                (2, "layer 3".to_owned()),
+                (3, "layer 2".to_owned()),
+                (4, "layer 1".to_owned()),
+            ]
+        );
+
+        doc.reorder_layers(&[]);
+        doc.reorder_layers(&[1, 2, 3, 4]);
+        assert_eq!(names(&doc)[0], (1, "layer 4".to_owned()));
+    }
+
+    #[test]
+    fn test_document_estimate_plot_time() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (100., 0.)][..]);
+        doc.push_path(1, &[(100., 30.), (100., 70.)][..]);
+        doc.push_path(2, &[(100., 80.), (160., 80.)][..]);
+        doc.push_path(3, &[(0., 0.), (1000., 0.)][..]);
+        doc.get_mut(3).metadata_mut().hidden = true;
+
+        let params = crate::PlotParams {
+            draw_speed: 20.0,
+            travel_speed: 10.0,
+            pen_up_time: 0.5,
+            pen_down_time: 0.5,
+        };
+
+        // 200 / 20 + (30 + 10) / 10 + 3 * 1.0, the hidden layer is not counted
+        assert_eq!(
+            doc.estimate_plot_time(params),
+            std::time::Duration::from_secs(17)
+        );
+    }
+
+    #[test]
+    fn test_document_quantize() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0.49, 0.01), (0.51, -0.02), (1.02, 0.98)][..]);
+        doc.push_path(2, &[(9.76, 3.24), (10.1, 3.1)][..]);
+
+        doc.quantize(0.5);
+        doc.dedup_points(0.0);
+
+        assert_eq!(
+            doc.try_get(1).unwrap().paths[0].data,
+            kurbo::BezPath::from_svg("M 0.5,0 L 1,1").unwrap()
+        );
+
+        // collapsed to a single point
+        assert_eq!(
+            doc.try_get(2).unwrap().paths[0].data,
+            kurbo::BezPath::from_svg("M 10,3").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_document_jitter() {
+        let mut doc = Document::default();
+        for i in 0..3 {
+            doc.push_path(1, &[(0., f64::from(i)), (10., f64::from(i))][..]);
+            doc.push_path(2, &[(0., f64::from(i)), (10., f64::from(i))][..]);
+        }
+
+        let jitter = |seed| {
+            let mut doc = doc.clone();
+            doc.jitter(0.5, seed);
+            doc.to_json().unwrap()
+        };
+        let wobble = |seed| {
+            let mut doc = doc.clone();
+            doc.wobble(0.5, 5.0, seed);
+            doc.to_json().unwrap()
+        };
+
+        // same seed, byte-identical output
+        assert_eq!(jitter(1), jitter(1));
+        assert_ne!(jitter(1), jitter(2));
+        assert_eq!(wobble(1), wobble(1));
+        assert_ne!(wobble(1), wobble(2));
+
+        // identical paths in different layers or positions get different offsets
+        let mut jittered = doc.clone();
+        jittered.jitter(0.5, 1);
+        let paths = |id| &jittered.try_get(id).unwrap().paths;
+        assert_ne!(paths(1)[0].data, paths(2)[0].data);
+        let start_x = |path: &Path| path.start().unwrap().x();
+        assert!((start_x(&paths(1)[0]) - start_x(&paths(1)[1])).abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_document_paths() {
+        let mut doc = Document::default();
+        doc.push_path(3, Path::from_points([(0., 0.), (10., 0.)]));
+        doc.push_path(1, Path::from_points([(0., 0.), (20., 0.)]));
+        doc.push_path(1, Path::from_points([(0., 0.), (30., 0.)]));
+
+        let lengths: Vec<_> = doc
+            .paths()
+            .map(|(lid, path)| (lid, path.bounds().unwrap().width()))
+            .collect();
+        assert_eq!(lengths, vec![(1, 20.), (1, 30.), (3, 10.)]);
+
+        let mut index = 0;
+        doc.map_paths(|path| {
+            path.metadata_mut().stroke_width = f64::from(index);
+            index += 1;
+        });
+        let widths: Vec<_> = doc
+            .paths()
+            .map(|(_, path)| path.metadata().stroke_width)
+            .collect();
+        assert_eq!(widths, vec![0., 1., 2.]);
+    }
+
+    #[test]
+    fn test_document_filter() {
+        let mut doc = Document::new_with_page_size(PageSize::A4V);
+        doc.push_path(1, Path::from_points([(0., 0.), (10., 0.)]));
+        doc.push_path(1, Path::from_points([(0., 0.), (50., 0.)]));
+        doc.push_path(2, Path::from_points([(0., 0.), (5., 0.)]));
+        doc.get_mut(1).metadata_mut().name = "long".to_owned();
+
+        doc.get_mut(2).metadata_mut().hidden = true;
+
+        let long = doc.filter(|_, path| path.length() > 20.0);
+        assert_eq!(long.layers().len(), 2);
+        assert_eq!(long.try_get(1).unwrap().paths.len(), 1);
+        assert!(long.try_get(2).unwrap().paths.is_empty());
+        assert!(long.try_get(2).unwrap().metadata().hidden);
+        assert_eq!(long.try_get(1).unwrap().metadata().name, "long");
+        assert_eq!(long.metadata().page_size, Some(PageSize::A4V));
+
+        let second = doc.filter(|lid, _| lid == 2);
+        assert_eq!(second.paths().count(), 1);
+
+        // the original is untouched
+        assert_eq!(doc.paths().count(), 3);
+
+        let empty = doc.filter(|_, _| false);
+        assert_eq!(empty.paths().count(), 0);
+        assert_eq!(empty.layers().len(), 2);
+        assert_eq!(empty.metadata().page_size, Some(PageSize::A4V));
+    }
+
+    #[test]
+    fn test_document_convert_units() {
+        let mut doc = Document::new_with_page_size(PageSize::A4V);
+        let mut path = Path::from_points([(0., 0.), (96., 0.)]);
+        path.metadata_mut().stroke_width = 96.0 / 25.4;
+        doc.push_path(1, path);
+
+        doc.to_mm();
+        let path = &doc.try_get(1).unwrap().paths[0];
+        assert_abs_diff_eq!(path.length(), 25.4, epsilon = 1e-9);
+        assert_abs_diff_eq!(path.metadata().stroke_width, 1.0, epsilon = 1e-9);
+        let Some(PageSize::Custom(w, h, Unit::Mm)) = doc.metadata().page_size else {
+            panic!("page size should be in millimeters");
+        };
+        assert_abs_diff_eq!(w, 210.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(h, 297.0, epsilon = 1e-9);
+
+        doc.convert_units(Unit::Mm, Unit::Cm);
+        assert_abs_diff_eq!(doc.bounds().unwrap().width(), 2.54, epsilon = 1e-9);
+        doc.convert_units(Unit::Cm, Unit::In);
+        assert_abs_diff_eq!(doc.bounds().unwrap().width(), 1.0, epsilon = 1e-9);
+        let Some(PageSize::Custom(w, _, Unit::In)) = doc.metadata().page_size else {
+            panic!("page size should be in inches");
+        };
+        assert_abs_diff_eq!(w, 210.0 / 25.4, epsilon = 1e-9);
+
+        // back to pixels, the page is still A4
+        doc.convert_units(Unit::In, Unit::Px);
+        assert_abs_diff_eq!(doc.bounds().unwrap().width(), 96.0, epsilon = 1e-9);
+        let page_size = doc.metadata().page_size.unwrap();
+        assert_abs_diff_eq!(page_size.w(), PageSize::A4V.w(), epsilon = 1e-9);
+        assert_abs_diff_eq!(page_size.h(), PageSize::A4V.h(), epsilon = 1e-9);
+        assert_eq!(doc.metadata().unit, Unit::Px);
+    }
+
+    #[test]
+    fn test_document_convert_units_page_and_export() {
+        let mut px_doc = Document::new_with_page_size(PageSize::A4V);
+        px_doc.push_path(1, Path::from_points([(0., 0.), (96., 0.), (96., 96.)]));
+        let mut doc = px_doc.clone();
+        doc.to_mm();
+        assert_eq!(doc.metadata().unit, Unit::Mm);
+        let (w, h) = doc.metadata().page_dimensions().unwrap();
+        assert_abs_diff_eq!(w, 210.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(h, 297.0, epsilon = 1e-9);
+
+        // page operations work in millimeters
+        doc.crop_to_page();
+        assert_abs_diff_eq!(doc.length(), 50.8, epsilon = 1e-9);
+        doc.center_content();
+        let bounds = doc.bounds().unwrap();
+        assert_abs_diff_eq!(bounds.center().x, 105.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.center().y, 148.5, epsilon = 1e-9);
+        px_doc.center_content();
+
+        // exports are physically identical to those of the pixel document
+        let export = |doc: &Document| {
+            let mut gcode = Vec::new();
+            doc.to_gcode(&mut gcode, &crate::GcodeOptions::default())
+                .unwrap();
+            let mut dxf = Vec::new();
+            doc.to_dxf(&mut dxf, &crate::DxfOptions::default()).unwrap();
+            (
+                String::from_utf8(gcode).unwrap(),
+                String::from_utf8(dxf).unwrap(),
+            )
+        };
+        assert_eq!(export(&doc), export(&px_doc));
+
+        let reloaded = Document::from_string(&doc.to_svg_string().unwrap(), false).unwrap();
+        let page_size = reloaded.metadata().page_size.unwrap();
+        assert_abs_diff_eq!(page_size.w(), PageSize::A4V.w(), epsilon = 1e-3);
+        assert_abs_diff_eq!(reloaded.length(), 192.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_document_filter_by_attr() {
+        let mut doc = Document::default();
+        let mut border = Path::from_points([(0., 0.), (10., 0.)]);
+        border
+            .metadata_mut()
+            .attrs
+            .insert("role".to_owned(), "border".to_owned());
+        doc.push_path(1, border.clone());
+        doc.push_path(1, Path::from_points([(0., 0.), (5., 0.)]));
+        doc.push_path(2, border);
+
+        let borders = doc.filter_by_attr("role", "border");
+        assert_eq!(borders.paths().count(), 2);
+        assert_eq!(borders.layers().len(), 2);
+
+        assert_eq!(doc.filter_by_attr("role", "fill").paths().count(), 0);
+        assert_eq!(doc.filter_by_attr("missing", "border").paths().count(), 0);
+    }
+
+    #[test]
+    fn test_document_visible_subset() {
+        let mut doc = Document::new_with_page_size(PageSize::A4V);
+        doc.push_path(1, Path::from_points([(0., 0.), (10., 0.)]));
+        doc.push_path(2, Path::from_points([(0., 0.), (20., 0.)]));
+        doc.push_path(3, Path::from_points([(0., 0.), (30., 0.)]));
+        doc.get_mut(1).metadata_mut().name = "first".to_owned();
+
+        let visibility = HashMap::from([(2, false), (3, true), (4, false)]);
+        let visible = doc.visible_subset(&visibility);
+        assert_eq!(
+            visible.layers().keys().copied().collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(visible.try_get(1).unwrap().metadata().name, "first");
+        assert_eq!(visible.paths().count(), 2);
+        assert_eq!(visible.metadata().page_size, Some(PageSize::A4V));
+
+        assert_eq!(doc.visible_subset(&HashMap::new()), doc);
+    }
+
+    #[test]
+    fn test_document_append() {
+        let mut doc = Document::default();
+        doc.push_path(1, Path::from_points([(0., 0.), (10., 0.)]));
+        doc.push_path(2, Path::from_points([(0., 0.), (20., 0.)]));
+
+        let mut other = Document::new_with_page_size(PageSize::A4V);
+        other.push_path(1, Path::from_points([(0., 0.), (30., 0.)]));
+        other.push_path(1, Path::from_points([(0., 0.), (40., 0.)]));
+        other.push_path(5, Path::from_points([(0., 0.), (50., 0.)]));
+        other.get_mut(1).metadata_mut().name = "other".to_owned();
+
+        // same-ID layers combine
+        let mut merged = doc.clone();
+        merged.append(other.clone(), false);
+        assert_eq!(merged.paths().count(), 5);
+        assert_eq!(
+            merged.layers().keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 5]
+        );
+        assert_eq!(merged.try_get(1).unwrap().paths.len(), 3);
+        assert!(merged.try_get(1).unwrap().metadata().name.is_empty());
+        assert_eq!(merged.metadata().page_size, Some(PageSize::A4V));
+
+        // colliding layers get new IDs
+        let mut remapped = doc.clone();
+        remapped.append(other, true);
+        assert_eq!(remapped.paths().count(), 5);
+        assert_eq!(
+            remapped.layers().keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5]
+        );
+        assert_eq!(remapped.try_get(1).unwrap().paths.len(), 1);
+        assert_eq!(remapped.try_get(3).unwrap().paths.len(), 2);
+        assert_eq!(remapped.try_get(3).unwrap().metadata().name, "other");
+
+        // the page size of self wins
+        let mut letter = Document::new_with_page_size(PageSize::LetterV);
+        letter.append(Document::new_with_page_size(PageSize::A4V), true);
+        assert_eq!(letter.metadata().page_size, Some(PageSize::LetterV));
+    }
+
+    #[test]
+    fn test_document_add_layer() {
+        let mut doc = Document::default();
+        assert_eq!(doc.next_layer_id(), 1);
+
+        let (a, b) = (doc.add_layer(), doc.add_layer());
+        assert_ne!(a, b);
+        assert!(doc.try_get(a).unwrap().paths.is_empty());
+        assert!(doc.try_get(b).is_some());
+
+        doc.push_path(10, Path::from_points([(0., 0.), (10., 0.)]));
+        assert_eq!(doc.next_layer_id(), 11);
+
+        doc.ensure_exists(LayerID::MAX);
+        let id = doc.add_layer();
+        assert!(id != LayerID::MAX && ![a, b, 10].contains(&id));
+    }
+
+    #[test]
+    fn test_document_split_by_color() {
+        let mut doc = Document::default();
+        for (lid, color) in [
+            (1, Color::RED),
+            (1, Color::BLUE),
+            (1, Color::RED),
+            (4, Color::BLUE),
+        ] {
+            let mut path = Path::from_points([(0., 0.), (1., 1.)]);
+            path.metadata_mut().color = color;
+            doc.push_path(lid, path);
+        }
+        doc.get_mut(1).metadata_mut().name = "pens".to_owned();
+        doc.get_mut(4).metadata_mut().hidden = true;
+        doc.ensure_exists(7);
+
+        let mapping = doc.split_by_color();
+        assert_eq!(
+            mapping,
+            vec![(Color::RED, 1), (Color::BLUE, 8), (Color::BLUE, 4)]
+        );
+        assert!(doc.layers().keys().eq([1, 4, 7, 8].iter()));
+        assert_eq!(doc.try_get(1).unwrap().paths.len(), 2);
+        assert_eq!(doc.try_get(8).unwrap().paths.len(), 1);
+        assert_eq!(doc.try_get(8).unwrap().metadata().name, "pens");
+        assert!(doc.try_get(4).unwrap().metadata().hidden);
+        assert!(doc.try_get(7).unwrap().paths.is_empty());
+        for (color, lid) in mapping {
+            assert!(doc
+                .try_get(lid)
+                .unwrap()
+                .paths
+                .iter()
+                .all(|p| p.metadata().color == color));
+        }
+    }
+
+    #[test]
+    fn test_document_merge_by_color() {
+        let mut doc = Document::default();
+        for (from, to, color, stroke_width) in [
+            ((0., 0.), (10., 0.), Color::RED, 1.0),
+            ((10., 0.), (10., 10.), Color::BLUE, 1.0),
+            ((10., 10.), (0., 10.), Color::RED, 2.0),
+            ((10., 10.), (10., 0.), Color::RED, 1.0),
+            ((0., 10.), (0., 20.), Color::RED, 2.0),
+        ] {
+            let mut path = Path::from_points([from, to]);
+            path.metadata_mut().color = color;
+            path.metadata_mut().stroke_width = stroke_width;
+            doc.push_path(1, path);
+        }
+
+        // attributes other than color and stroke width are ignored
+        doc.get_mut(1).paths[3]
+            .metadata_mut()
+            .attrs
+            .insert("class".to_owned(), "outline".to_owned());
+        let mut merged = doc.clone();
+        merged.merge_paths(0.1);
+        assert_eq!(merged.try_get(1).unwrap().paths.len(), 4);
+
+        doc.merge_by_color(0.1);
+        let paths = &doc.try_get(1).unwrap().paths;
+        assert_eq!(paths.len(), 3);
+
+        // the touching red paths are merged, the touching blue one stays separate
+        assert_eq!(
+            paths[0].data,
+            kurbo::BezPath::from_svg("M 0,0 L 10,0 L 10,10").unwrap()
+        );
+        assert_eq!(paths[1].metadata().color, Color::BLUE);
+
+        // paths of different stroke width are not merged with the others
+        assert_abs_diff_eq!(paths[2].metadata().stroke_width, 2.0);
+        assert_eq!(paths[2].data().elements().len(), 3);
+    }
+
+    #[test]
+    fn test_document_dedup_lines() {
+        // a grid drawn twice, the second time backward and slightly offset
+        let mut doc = Document::default();
+        for pass in 0..2 {
+            for i in 0..20_u8 {
+                let (a, b) = (f64::from(i) * 5., 100.0);
+                let offset = f64::from(pass) * 0.01;
+                let mut points = [(a + offset, 0.), (a + offset, b), (a + 2.5, b)];
+                if pass == 1 {
+                    points.reverse();
+                }
+                doc.push_path(1, &points[..]);
+            }
+        }
+        doc.push_path(2, &[(0., 0.), (0., 100.)][..]);
+
+        // the overlapping line of layer 2 is kept
+        assert_abs_diff_eq!(doc.length(), 2. * 20. * 102.5 + 100., epsilon = 1.0);
+        assert_eq!(doc.dedup_lines(0.05), 40);
+        assert_abs_diff_eq!(doc.length(), 20. * 102.5 + 100., epsilon = 1e-9);
+        assert_eq!(doc.try_get(1).unwrap().paths.len(), 20);
+        assert_eq!(doc.try_get(2).unwrap().paths.len(), 1);
+
+        assert_eq!(doc.dedup_lines(0.05), 0);
+    }
+
+    #[test]
+    fn test_document_filter_paths() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (0.2, 0.)][..]);
+        doc.push_path(1, &[(0., 0.), (2., 0.)][..]);
+        doc.push_path(2, &[(0., 0.), (0.1, 0.1)][..]);
+        doc.push_path(2, Path::default());
+
+        assert_eq!(doc.remove_empty_paths(), 1);
+        assert_eq!(doc.filter_paths(0.5), 2);
+        assert_eq!(doc.try_get(1).unwrap().paths.len(), 1);
+        assert!(doc.try_get(2).unwrap().paths.is_empty());
+    }
+
+    #[test]
+    fn test_document_resample() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (10., 0.)][..]);
+        doc.push_path(2, kurbo::Circle::new((0., 0.), 10.));
+        doc.resample(2.0);
+
+        assert_eq!(doc.try_get(1).unwrap().paths[0].data().elements().len(), 6);
+        // 2π·10 / 2 ≈ 31.4, so 32 segments
+        assert_eq!(doc.try_get(2).unwrap().paths[0].data().elements().len(), 33);
+    }
+
+    #[test]
+    fn test_document_convex_hull() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (10., 0.), (5., 5.)][..]);
+        doc.push_path(2, &[(0., 10.), (5., 20.)][..]);
+
+        assert_eq!(
+            doc.convex_hull().data().clone(),
+            kurbo::BezPath::from_svg("M 0,0 L 10,0 L 5,20 L 0,10 Z").unwrap()
+        );
+        assert_eq!(
+            doc.convex_hull_filtered(|lid, _| lid == 2).data().clone(),
+            kurbo::BezPath::from_svg("M 0,10 L 5,20 Z").unwrap()
+        );
+        assert!(Document::default()
+            .convex_hull()
+            .data()
+            .elements()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_document_normalize_winding() {
+        let cw = Path::from(kurbo::Rect::new(0., 0., 1., 1.));
+        assert!(cw.signed_area() > 0.0);
+
+        let mut doc = Document::default();
+        doc.push_path(1, cw.clone());
+        doc.push_path(2, cw.reversed());
+
+        doc.normalize_winding(true);
+        assert_eq!(doc.try_get(1).unwrap().paths[0], cw);
+        assert_eq!(doc.try_get(2).unwrap().paths[0], cw.reversed().reversed());
+
+        doc.normalize_winding(false);
+        assert!(doc
+            .layers()
+            .values()
+            .all(|layer| layer.paths[0].signed_area() < 0.0));
+    }
+
+    #[test]
+    fn test_document_json_round_trip() {
+        let mut doc = Document::new_with_page_size(PageSize::A4V);
+        doc.metadata_mut().source = Some("test".to_owned());
+        doc.push_path(1, kurbo::Circle::new((10., 20.), 5.3));
+        doc.push_path(3, &[(0.1, 0.2), (1. / 3., 2. / 3.)][..]);
+        doc.get_mut(3).metadata_mut().name = "layer three".to_owned();
+        let path = &mut doc.get_mut(3).paths[0];
+        path.metadata_mut().color = Color::new(1, 2, 3, 4);
+        path.metadata_mut().stroke_width = 0.123;
+
+        let json = doc.to_json().unwrap();
+        assert!(json.starts_with(r#"{"version":1,"#));
+        assert_eq!(Document::from_json(&json).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_document_from_json_defaults() {
+        let doc = Document::from_json(r#"{"document": {"layers": {"2": {}}}}"#).unwrap();
+        assert_eq!(doc.layers.len(), 1);
+        assert!(doc.try_get(2).unwrap().paths.is_empty());
+        assert_eq!(doc.metadata().page_size, None);
+
+        assert!(matches!(
+            Document::from_json(r#"{"version": 99, "document": {}}"#),
+            Err(JsonError::UnsupportedVersion(99))
+        ));
+        assert!(matches!(
+            Document::from_json("not json"),
+            Err(JsonError::Json(_))
+        ));
+    }
 
     #[test]
     fn test_document_bounds() {
@@ -133,6 +1251,159 @@ mod test {
         assert_eq!(doc.bounds(), Some(kurbo::Rect::new(10., -100., 250., 54.)));
     }
 
+    #[test]
+    fn test_document_bounds_filtered() {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Line::new((10., 10.), (25., 53.)));
+        doc.push_path(2, kurbo::Line::new((25., -100.), (250., 54.)));
+        doc.ensure_exists(3);
+
+        assert_eq!(
+            doc.bounds_filtered(|lid, _| lid != 2),
+            Some(kurbo::Rect::new(10., 10., 25., 53.))
+        );
+        assert_eq!(doc.bounds_filtered(|lid, _| lid == 3), None);
+        assert_eq!(doc.bounds_filtered(|_, _| false), None);
+        assert_eq!(
+            doc.bounds_filtered(|_, layer| !layer.paths.is_empty()),
+            doc.bounds()
+        );
+    }
+
+    #[test]
+    fn test_document_length() {
+        let mut doc = Document::default();
+        assert_abs_diff_eq!(doc.length(), 0.0);
+
+        doc.push_path(1, kurbo::Line::new((0., 0.), (3., 4.)));
+        doc.push_path(1, kurbo::Line::new((0., 0.), (0., 10.)));
+        doc.push_path(2, kurbo::Rect::new(0., 0., 1., 1.));
+        assert_abs_diff_eq!(doc.layers[&1].length(), 15.);
+        assert_abs_diff_eq!(doc.length(), 19., epsilon = 1e-9);
+        assert_abs_diff_eq!(doc.flatten(0.1).length(), 19., epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_document_simplify() {
+        let mut doc = Document::default();
+        doc.push_path(
+            1,
+            (0..=100)
+                .map(|i| (f64::from(i), 0.001 * f64::from(i % 2)))
+                .collect::<Vec<_>>(),
+        );
+        doc.push_path(2, kurbo::Circle::new((50., 50.), 10.));
+
+        let bounds = doc.bounds();
+        doc.simplify(0.01);
+        assert_eq!(
+            doc.layers[&1].paths[0],
+            Path::from(kurbo::Line::new((0., 0.), (100., 0.)))
+        );
+        assert_eq!(doc.bounds(), bounds);
+
+        let mut flattened = doc.flatten(0.001);
+        let num_points = flattened.layers[&2].paths[0].data.points().len();
+        flattened.simplify(0.1);
+        assert!(flattened.layers[&2].paths[0].data.points().len() < num_points / 4);
+    }
+
+    #[test]
+    fn test_document_pen_up_distance() {
+        let mut doc = Document::default();
+        assert_abs_diff_eq!(doc.pen_up_distance(), 0.0);
+
+        doc.push_path(1, kurbo::Line::new((0., 0.), (10., 0.)));
+        doc.push_path(1, kurbo::Line::new((13., 4.), (20., 0.)));
+        doc.push_path(3, kurbo::Line::new((20., 10.), (30., 10.)));
+        doc.push_path(2, kurbo::Line::new((100., 100.), (0., 0.)));
+
+        // layer 2 comes before layer 3, regardless of insertion order
+        let expected = 5.0 + 80f64.hypot(100.) + 20f64.hypot(10.);
+        assert_abs_diff_eq!(doc.pen_up_distance(), expected);
+        assert_abs_diff_eq!(doc.flatten(0.1).pen_up_distance(), expected);
+
+        doc.get_mut(2).metadata_mut().hidden = true;
+        assert_abs_diff_eq!(doc.pen_up_distance(), 5.0 + 10.0);
+        assert_abs_diff_eq!(doc.pen_up_distance_filtered(|lid, _| lid == 1), 5.0);
+        assert_abs_diff_eq!(doc.layers[&1].pen_up_length(), 5.0);
+    }
+
+    #[test]
+    fn test_document_sort() {
+        let mut doc = Document::default();
+        for lid in [1, 2] {
+            doc.push_path(lid, kurbo::Line::new((20., 0.), (30., 0.)));
+            doc.push_path(lid, kurbo::Line::new((0., 0.), (10., 0.)));
+            doc.push_path(lid, kurbo::Line::new((20., 10.), (10., 10.)));
+        }
+
+        let before = doc.pen_up_distance();
+        doc.sort(true);
+        assert!(doc.pen_up_distance() < before);
+        assert_eq!(doc.layers[&1].paths, doc.layers[&2].paths);
+
+        // from (10, 0), both remaining paths are 10 units away: the tie is broken by order
+        assert_eq!(
+            doc.layers[&1].paths,
+            vec![
+                Path::from(kurbo::Line::new((0., 0.), (10., 0.))),
+                Path::from(kurbo::Line::new((20., 0.), (30., 0.))),
+                Path::from(kurbo::Line::new((20., 10.), (10., 10.))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_sort_for_plotting() {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Line::new((0., 0.), (10., 0.)));
+        doc.push_path(1, kurbo::Line::new((30., 10.), (20., 10.)));
+        doc.push_path(1, kurbo::Line::new((10., 5.), (20., 5.)));
+        doc.push_path(2, kurbo::Line::new((0., 0.), (10., 0.)));
+
+        // includes the move from the end of layer 1 to the start of layer 2
+        let (before, after) = doc.sort_for_plotting();
+        assert_abs_diff_eq!(before, 20f64.hypot(10.) + 10f64.hypot(5.) + 20f64.hypot(5.));
+        assert_abs_diff_eq!(after, 10.0 + 30f64.hypot(10.));
+        assert_abs_diff_eq!(doc.pen_up_distance(), after);
+        assert_eq!(
+            doc.layers[&1].paths[2],
+            Path::from(kurbo::Line::new((20., 10.), (30., 10.)))
+        );
+
+        // already optimal: nothing to gain, nothing changed
+        let sorted = doc.clone();
+        assert_eq!(doc.sort_for_plotting(), (after, after));
+        assert_eq!(doc, sorted);
+    }
+
+    #[test]
+    fn test_document_fit_to_page() {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Line::new((10., 10.), (30., 70.)));
+        assert_eq!(
+            doc.fit_to_page(Margins::default()),
+            Err(FitToPageError::NoPageSize)
+        );
+
+        doc.metadata_mut().page_size = Some(PageSize::Custom(300., 200., Unit::Px));
+        assert_eq!(
+            doc.fit_to_page(Margins::uniform(100.)),
+            Err(FitToPageError::MarginsTooLarge)
+        );
+
+        doc.fit_to_page(Margins::new(10., 20., 30., 40.)).unwrap();
+        let bounds = doc.bounds().unwrap();
+        assert_abs_diff_eq!(bounds.y0, 10., epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.y1, 170., epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.width(), 160. / 3., epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.center().x, 160., epsilon = 1e-9);
+
+        let mut empty = Document::new_with_page_size(PageSize::A4V);
+        assert_eq!(empty.fit_to_page(Margins::uniform(10.)), Ok(()));
+    }
+
     #[test]
     fn test_document_push_shape() {
         let mut doc = Document::default();
@@ -162,4 +1433,30 @@ mod test {
         doc.center_content();
         assert_eq!(doc.bounds(), Some(kurbo::Rect::new(140., 70.0, 160., 130.)));
     }
+
+    #[test]
+    fn test_document_flip() {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Line::new((10., 10.), (30., 70.)));
+        doc.push_path(1, kurbo::Line::new((20., 20.), (20., 30.)));
+        let bounds = doc.bounds();
+
+        doc.flip_horizontal();
+        assert_eq!(doc.bounds(), bounds);
+        assert_eq!(
+            doc.layers[&1].paths[0],
+            Path::from(kurbo::Line::new((30., 10.), (10., 70.)))
+        );
+
+        doc.flip_vertical();
+        assert_eq!(doc.bounds(), bounds);
+        assert_eq!(
+            doc.layers[&1].paths[1],
+            Path::from(kurbo::Line::new((20., 60.), (20., 50.)))
+        );
+
+        let mut empty = Document::default();
+        empty.flip_horizontal();
+        assert_eq!(empty.bounds(), None);
+    }
 }
@@ -2,7 +2,7 @@ use super::{DocumentMetadata, DocumentTrait, LayerID};
 use crate::{FlattenedLayer, FlattenedPath, Polyline, Transforms};
 use std::collections::BTreeMap;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct FlattenedDocument {
     pub layers: BTreeMap<LayerID, FlattenedLayer>,
     metadata: DocumentMetadata,
@@ -1,12 +1,69 @@
-use crate::PageSize;
+use crate::{PageSize, Unit};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct DocumentMetadata {
     pub page_size: Option<PageSize>,
     pub source: Option<String>,
+
+    /// Unit of the SVG file's `width` and `height` attributes, if loaded from SVG.
+    ///
+    /// This is informational: the SVG loader always converts coordinates to pixels.
+    pub source_unit: Option<Unit>,
+
+    /// Unit of the coordinates and stroke widths, pixels unless set by
+    /// [`crate::Document::convert_units`].
+    ///
+    /// Page operations and exports account for it, see [`DocumentMetadata::page_dimensions`].
+    pub unit: Unit,
+
+    /// Free-form metadata such as title, author or notes.
+    ///
+    /// Entries are saved to and loaded from the SVG `<metadata>` element, as Dublin Core elements
+    /// named after their key (except `author`, stored as `dc:creator`). Keys must be valid XML
+    /// names, and `format`, `date` and `source` are reserved.
+    pub properties: BTreeMap<String, String>,
 }
 
 impl DocumentMetadata {
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.properties.get("title").map(String::as_str)
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.properties.insert("title".to_owned(), title.into());
+    }
+
+    #[must_use]
+    pub fn author(&self) -> Option<&str> {
+        self.properties.get("author").map(String::as_str)
+    }
+
+    pub fn set_author(&mut self, author: impl Into<String>) {
+        self.properties.insert("author".to_owned(), author.into());
+    }
+
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.properties.get("description").map(String::as_str)
+    }
+
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.properties
+            .insert("description".to_owned(), description.into());
+    }
+
+    /// Page size in [`DocumentMetadata::unit`], if the document has a page size.
+    #[must_use]
+    pub fn page_dimensions(&self) -> Option<(f64, f64)> {
+        self.page_size.map(|page_size| {
+            let (w, h) = page_size.to_pixels();
+            (w / self.unit.to_px(), h / self.unit.to_px())
+        })
+    }
+
     pub(super) fn with_source_suffix(&self, suffix: &str) -> Self {
         Self {
             source: Some(format!(
@@ -1,14 +1,16 @@
-use crate::{LayerTrait, PathDataTrait, PathTrait, Transforms};
+use crate::{Color, LayerTrait, PathDataTrait, PathTrait, Point, Transforms};
 use std::collections::BTreeMap;
 
+mod builder;
 #[allow(clippy::module_inception)]
 mod document;
 mod flattened_document;
 mod metadata;
 
-use crate::document_to_svg_doc;
-use crate::stats::LayerStats;
-pub use document::Document;
+use crate::stats::{LayerStats, PlotParams};
+use crate::{document_to_svg_doc, SvgOptions};
+pub use builder::{BuilderError, DocumentBuilder, LayerBuilder};
+pub use document::{Document, FitToPageError, JsonError};
 pub use flattened_document::FlattenedDocument;
 pub use metadata::DocumentMetadata;
 
@@ -42,6 +44,142 @@ pub trait DocumentTrait<L: LayerTrait<P, D>, P: PathTrait<D>, D: PathDataTrait>:
         let _ = self.get_mut(id);
     }
 
+    /// Return a layer ID which is not in use, above all the existing ones (starting at 1).
+    #[must_use]
+    fn next_layer_id(&self) -> LayerID {
+        match self.layers().keys().next_back() {
+            None => 1,
+            Some(&max) => max.checked_add(1).unwrap_or_else(|| {
+                // the last ID is taken, look for a gap instead
+                (1..LayerID::MAX)
+                    .find(|id| !self.layers().contains_key(id))
+                    .expect("there are fewer layers than IDs")
+            }),
+        }
+    }
+
+    /// Create an empty layer with a new ID and return the ID.
+    ///
+    /// See [`DocumentTrait::next_layer_id`].
+    fn add_layer(&mut self) -> LayerID {
+        let id = self.next_layer_id();
+        self.ensure_exists(id);
+        id
+    }
+
+    /// Find the ID of the first layer with the given name.
+    fn layer_by_name(&self, name: &str) -> Option<LayerID> {
+        self.layers()
+            .iter()
+            .find_map(|(lid, layer)| (layer.metadata().name == name).then_some(*lid))
+    }
+
+    /// Remove a layer from the document and return it, if it exists.
+    fn remove_layer(&mut self, id: LayerID) -> Option<L> {
+        self.layers_mut().remove(&id)
+    }
+
+    /// Reorder layers such that they are drawn in the order given by `order`.
+    ///
+    /// Layers are stored and drawn in ID order, so this reassigns IDs: the layers listed in
+    /// `order` are redistributed among their own (sorted) IDs. For example, with layers 1, 2 and
+    /// 3, `reorder_layers(&[3, 1])` moves the content of layer 3 to ID 1 and the content of
+    /// layer 1 to ID 3. Unknown or duplicate IDs in `order` are ignored, and unlisted layers are
+    /// left untouched.
+    fn reorder_layers(&mut self, order: &[LayerID]) {
+        let mut ids: Vec<LayerID> = Vec::with_capacity(order.len());
+        for id in order {
+            if self.layers().contains_key(id) && !ids.contains(id) {
+                ids.push(*id);
+            }
+        }
+
+        let layers: Vec<L> = ids
+            .iter()
+            .filter_map(|id| self.layers_mut().remove(id))
+            .collect();
+
+        ids.sort_unstable();
+        self.layers_mut().extend(ids.into_iter().zip(layers));
+    }
+
+    /// Move all paths of layer `from` at the end of layer `into`, and remove layer `from`.
+    ///
+    /// This is a no-op if either layer doesn't exist, or if both IDs are the same.
+    fn merge_layers(&mut self, into: LayerID, from: LayerID) {
+        if into == from || !self.layers().contains_key(&into) {
+            return;
+        }
+
+        if let Some(mut from_layer) = self.remove_layer(from) {
+            self.get_mut(into)
+                .paths_mut()
+                .append(from_layer.paths_mut());
+        }
+    }
+
+    /// Split each layer into one layer per path color.
+    ///
+    /// Each layer keeps its ID and metadata along with the paths of the first color appearing in
+    /// it. The paths of every other color are moved to a new layer, with an ID allocated by
+    /// [`DocumentTrait::next_layer_id`] and a copy of the original layer's metadata. Empty
+    /// layers are left untouched. Returns the color of each resulting layer, in the order of the
+    /// original layers and of the first appearance of each color within them.
+    fn split_by_color(&mut self) -> Vec<(Color, LayerID)> {
+        let ids: Vec<LayerID> = self.layers().keys().copied().collect();
+        let mut mapping = vec![];
+
+        for lid in ids {
+            let layer = self.get_mut(lid);
+            let mut groups: Vec<(Color, Vec<P>)> = vec![];
+            for path in layer.paths_mut().drain(..) {
+                let color = path.metadata().color;
+                match groups.iter_mut().find(|(c, _)| *c == color) {
+                    Some((_, paths)) => paths.push(path),
+                    None => groups.push((color, vec![path])),
+                }
+            }
+
+            let mut groups = groups.into_iter();
+            let Some((color, paths)) = groups.next() else {
+                continue;
+            };
+            *layer.paths_mut() = paths;
+            let metadata = layer.metadata().clone();
+            mapping.push((color, lid));
+
+            for (color, paths) in groups {
+                let id = self.next_layer_id();
+                let new_layer = self.get_mut(id);
+                *new_layer.metadata_mut() = metadata.clone();
+                *new_layer.paths_mut() = paths;
+                mapping.push((color, id));
+            }
+        }
+
+        mapping
+    }
+
+    /// Iterate over all paths along with their layer ID, in layer ID order.
+    fn paths<'a>(&'a self) -> impl Iterator<Item = (LayerID, &'a P)>
+    where
+        L: 'a,
+        P: 'a,
+    {
+        self.layers()
+            .iter()
+            .flat_map(|(&lid, layer)| layer.paths().iter().map(move |path| (lid, path)))
+    }
+
+    /// Apply `f` to all paths, in the same order as [`DocumentTrait::paths`].
+    fn map_paths(&mut self, mut f: impl FnMut(&mut P)) {
+        for layer in self.layers_mut().values_mut() {
+            for path in layer.paths_mut() {
+                f(path);
+            }
+        }
+    }
+
     fn for_each<F>(&mut self, f: F)
     where
         F: Fn(&mut L),
@@ -51,18 +189,219 @@ pub trait DocumentTrait<L: LayerTrait<P, D>, P: PathTrait<D>, D: PathDataTrait>:
 
     #[must_use]
     fn bounds(&self) -> Option<kurbo::Rect> {
-        if self.layers().is_empty() {
-            return None;
+        self.bounds_filtered(|_, _| true)
+    }
+
+    /// Compute the bounds of the layers for which `filter` returns `true`.
+    ///
+    /// This is useful to exclude hidden layers. Returns `None` if no selected layer has content.
+    #[must_use]
+    fn bounds_filtered<F>(&self, filter: F) -> Option<kurbo::Rect>
+    where
+        F: Fn(LayerID, &L) -> bool,
+    {
+        self.layers()
+            .iter()
+            .filter(|(lid, layer)| filter(**lid, layer))
+            .filter_map(|(_, layer)| layer.bounds())
+            .reduce(|acc, bounds| acc.union(bounds))
+    }
+
+    /// Total geometric length of all paths in the document.
+    #[must_use]
+    fn length(&self) -> f64 {
+        self.layers().values().map(LayerTrait::length).sum()
+    }
+
+    /// Simplify all paths using the Ramer–Douglas–Peucker algorithm.
+    ///
+    /// See [`PathDataTrait::simplify`].
+    fn simplify(&mut self, tolerance: f64) {
+        self.for_each(|layer| layer.simplify(tolerance));
+    }
+
+    /// Simplify all paths using the Visvalingam–Whyatt algorithm.
+    ///
+    /// See [`PathDataTrait::simplify_vw`].
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        self.for_each(|layer| layer.simplify_vw(area_threshold));
+    }
+
+    /// Resample all paths with points every `spacing` units along their length.
+    ///
+    /// See [`PathDataTrait::resample`].
+    fn resample(&mut self, spacing: f64) {
+        self.for_each(|layer| layer.resample(spacing));
+    }
+
+    /// Smooth all paths with Chaikin's corner-cutting algorithm.
+    ///
+    /// See [`PathDataTrait::smooth_chaikin`].
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        self.for_each(|layer| layer.smooth_chaikin(iterations));
+    }
+
+    /// Remove consecutive duplicate points from all paths.
+    ///
+    /// See [`PathDataTrait::dedup_points`].
+    fn dedup_points(&mut self, epsilon: f64) {
+        self.for_each(|layer| layer.dedup_points(epsilon));
+    }
+
+    /// Round all coordinates to the nearest multiple of `step`.
+    ///
+    /// Call [`DocumentTrait::dedup_points`] afterwards to remove the resulting duplicate points.
+    /// See [`PathDataTrait::quantize`].
+    fn quantize(&mut self, step: f64) {
+        self.for_each(|layer| layer.quantize(step));
+    }
+
+    /// Displace each point by a seeded random offset of up to `amplitude` along each axis.
+    ///
+    /// The same seed and document always give the same result. Each layer is given its own
+    /// seed, derived from `seed` and its ID. See [`PathDataTrait::jitter`].
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        for (id, layer) in self.layers_mut() {
+            layer.jitter(amplitude, crate::noise::hash(seed, *id as u64));
+        }
+    }
+
+    /// Displace each point by a smooth, seeded random offset of up to `amplitude` along each
+    /// axis.
+    ///
+    /// Like [`DocumentTrait::jitter`], but the offset varies continuously along each path. See
+    /// [`PathDataTrait::wobble`].
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        for (id, layer) in self.layers_mut() {
+            layer.wobble(amplitude, wavelength, crate::noise::hash(seed, *id as u64));
+        }
+    }
+
+    /// Remove paths shorter than `min_length` from all layers and return how many were removed.
+    ///
+    /// See [`LayerTrait::filter_paths`].
+    fn filter_paths(&mut self, min_length: f64) -> usize {
+        self.layers_mut()
+            .values_mut()
+            .map(|layer| layer.filter_paths(min_length))
+            .sum()
+    }
+
+    /// Remove paths with fewer than two points from all layers and return how many were removed.
+    ///
+    /// See [`LayerTrait::remove_empty_paths`].
+    fn remove_empty_paths(&mut self) -> usize {
+        self.layers_mut()
+            .values_mut()
+            .map(LayerTrait::remove_empty_paths)
+            .sum()
+    }
+
+    /// Sort the paths of all layers such as to minimize the pen-up distance.
+    ///
+    /// See [`LayerTrait::sort`].
+    fn sort(&mut self, flip: bool) {
+        self.for_each(|layer| layer.sort(flip));
+    }
+
+    /// Sort the paths of all layers for plotting, and return the document's pen-up distance
+    /// before and after sorting.
+    ///
+    /// See [`LayerTrait::sort_for_plotting`] and [`DocumentTrait::pen_up_distance`].
+    fn sort_for_plotting(&mut self) -> (f64, f64) {
+        let before = self.pen_up_distance();
+        for layer in self.layers_mut().values_mut() {
+            layer.sort_for_plotting();
+        }
+        (before, self.pen_up_distance())
+    }
+
+    /// Merge paths whose end points are within `tolerance` of each other, for all layers.
+    ///
+    /// Paths are only merged with paths of the same layer and metadata. See
+    /// [`LayerTrait::merge_paths`], and [`DocumentTrait::merge_by_color`] to ignore attributes
+    /// other than color and stroke width.
+    fn merge_paths(&mut self, tolerance: f64) {
+        self.for_each(|layer| layer.merge_paths(tolerance));
+    }
+
+    /// Merge paths whose end points are within `tolerance` of each other, if they share the same
+    /// layer, color and stroke width, for all layers.
+    ///
+    /// See [`LayerTrait::merge_by_color`].
+    fn merge_by_color(&mut self, tolerance: f64) {
+        self.for_each(|layer| layer.merge_by_color(tolerance));
+    }
+
+    /// Mirror the content horizontally around the center of its bounds.
+    ///
+    /// Use [`Transforms::flip_horizontal_around`] to mirror around another axis, e.g. the page
+    /// center.
+    fn flip_horizontal(&mut self) {
+        if let Some(bounds) = self.bounds() {
+            self.flip_horizontal_around(bounds.center().x);
+        }
+    }
+
+    /// Mirror the content vertically around the center of its bounds.
+    ///
+    /// Use [`Transforms::flip_vertical_around`] to mirror around another axis, e.g. the page
+    /// center.
+    fn flip_vertical(&mut self) {
+        if let Some(bounds) = self.bounds() {
+            self.flip_vertical_around(bounds.center().y);
+        }
+    }
+
+    /// Total pen-up distance to plot the visible layers, in document order.
+    ///
+    /// This is the sum of the distances from each path's end to the next path's start, for the
+    /// paths of all layers not marked as [`crate::LayerMetadata::hidden`], in layer ID order.
+    /// This includes the moves from each layer's last path to the next layer's first path.
+    #[must_use]
+    fn pen_up_distance(&self) -> f64 {
+        self.pen_up_distance_filtered(|_, layer| !layer.metadata().hidden)
+    }
+
+    /// Total pen-up distance to plot the layers for which `filter` returns `true`, in document
+    /// order.
+    ///
+    /// See [`DocumentTrait::pen_up_distance`].
+    #[must_use]
+    fn pen_up_distance_filtered<F>(&self, filter: F) -> f64
+    where
+        F: Fn(LayerID, &L) -> bool,
+    {
+        let mut distance = 0.0;
+        let mut pos: Option<Point> = None;
+        for path in self
+            .layers()
+            .iter()
+            .filter(|(lid, layer)| filter(**lid, layer))
+            .flat_map(|(_, layer)| layer.paths())
+        {
+            if let (Some(end), Some(start)) = (pos, path.start()) {
+                distance += end.distance(&start);
+            }
+            pos = path.end().or(pos);
         }
+        distance
+    }
 
-        let mut values = self.layers().values();
-        let first = values.next().expect("not empty").bounds();
-        values.fold(first, |acc, layer| match (acc, layer.bounds()) {
-            (Some(acc), Some(layer)) => Some(acc.union(layer)),
-            (Some(acc), None) => Some(acc),
-            (None, Some(path)) => Some(path),
-            (None, None) => None,
-        })
+    /// Estimate the time needed to plot the visible layers with a plotter described by `params`.
+    ///
+    /// The pen-up travel is measured with [`DocumentTrait::pen_up_distance`]. See
+    /// [`PlotParams::estimate`].
+    #[must_use]
+    fn estimate_plot_time(&self, params: PlotParams) -> std::time::Duration {
+        let visible = || {
+            self.layers()
+                .values()
+                .filter(|layer| !layer.metadata().hidden)
+        };
+        let length = visible().map(LayerTrait::length).sum();
+        let path_count = visible().map(|layer| layer.paths().len()).sum();
+        params.estimate(length, self.pen_up_distance(), path_count)
     }
 
     #[must_use]
@@ -74,19 +413,40 @@ pub trait DocumentTrait<L: LayerTrait<P, D>, P: PathTrait<D>, D: PathDataTrait>:
     }
 
     fn to_svg_string(&self) -> Result<String, std::fmt::Error> {
+        self.to_svg_string_with_options(&SvgOptions::default())
+    }
+
+    fn to_svg_string_with_options(&self, options: &SvgOptions) -> Result<String, std::fmt::Error> {
         use std::fmt::Write;
 
-        let doc = document_to_svg_doc(self);
+        let doc = document_to_svg_doc(self, options);
         let mut svg = String::new();
-        write!(svg, "{doc}").map(|_| svg)
+        write!(svg, "{doc}").map(|()| svg)
     }
+
     fn to_svg(&self, writer: impl std::io::Write) -> std::io::Result<()> {
-        let doc = document_to_svg_doc(self);
+        self.to_svg_with_options(writer, &SvgOptions::default())
+    }
+
+    fn to_svg_with_options(
+        &self,
+        writer: impl std::io::Write,
+        options: &SvgOptions,
+    ) -> std::io::Result<()> {
+        let doc = document_to_svg_doc(self, options);
         svg::write(writer, &doc)
     }
 
     fn to_svg_file(&self, file_path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        self.to_svg_file_with_options(file_path, &SvgOptions::default())
+    }
+
+    fn to_svg_file_with_options(
+        &self,
+        file_path: impl AsRef<std::path::Path>,
+        options: &SvgOptions,
+    ) -> std::io::Result<()> {
         let file = std::io::BufWriter::new(std::fs::File::create(file_path)?);
-        self.to_svg(file)
+        self.to_svg_with_options(file, options)
     }
 }
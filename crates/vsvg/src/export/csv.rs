@@ -0,0 +1,55 @@
+use crate::{Document, DocumentTrait, PathTrait, DEFAULT_TOLERANCE};
+use std::io::Write;
+
+impl Document {
+    /// Write the coordinates of all points as CSV.
+    ///
+    /// The output has a `layer_id,path_index,point_index,x,y` header, followed by one row per
+    /// point, in layer ID, path and point order. Curves are flattened with [`DEFAULT_TOLERANCE`]
+    /// and each sub-path counts as a path. Coordinates are in document units (see
+    /// [`crate::DocumentMetadata::unit`]).
+    pub fn to_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, "layer_id,path_index,point_index,x,y")?;
+        for (lid, layer) in self.flatten(DEFAULT_TOLERANCE).layers() {
+            for (path_index, path) in layer.paths.iter().enumerate() {
+                for (point_index, pt) in path.data().points().iter().enumerate() {
+                    writeln!(
+                        writer,
+                        "{lid},{path_index},{point_index},{},{}",
+                        pt.x(),
+                        pt.y()
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv() {
+        let mut doc = Document::default();
+        doc.push_path(2, &[(0., 0.), (10., 0.), (10., 5.5)][..]);
+        doc.push_path(1, &[(1., 2.), (3., 4.), (-5., 6.25)][..]);
+
+        let mut out = Vec::new();
+        doc.to_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "layer_id,path_index,point_index,x,y");
+        assert_eq!(lines[1], "1,0,0,1,2");
+        assert_eq!(lines[3], "1,0,2,-5,6.25");
+        assert_eq!(lines[6], "2,0,2,10,5.5");
+
+        let mut empty = Vec::new();
+        Document::default().to_csv(&mut empty).unwrap();
+        assert_eq!(empty, b"layer_id,path_index,point_index,x,y\n");
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{Color, Document, DocumentTrait, PathTrait, Point, Unit};
+use std::io::Write;
+
+/// Standard ACI (color index) colors, used for best-effort color mapping.
+const ACI_COLORS: [(u8, Color); 10] = [
+    (1, Color::RED),
+    (2, Color::YELLOW),
+    (3, Color::GREEN),
+    (4, Color::rgb(0, 255, 255)),
+    (5, Color::BLUE),
+    (6, Color::rgb(255, 0, 255)),
+    // ACI 7 is black or white depending on the background
+    (7, Color::BLACK),
+    (7, Color::WHITE),
+    (8, Color::rgb(128, 128, 128)),
+    (9, Color::rgb(192, 192, 192)),
+];
+
+/// Options for [`Document::to_dxf`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxfOptions {
+    /// Tolerance used to flatten curves, in document units.
+    pub tolerance: f64,
+}
+
+impl Default for DxfOptions {
+    fn default() -> Self {
+        Self { tolerance: 0.1 }
+    }
+}
+
+/// Find the ACI color closest to `color`.
+fn aci_color(color: Color) -> u8 {
+    let dist = |other: &Color| {
+        [(color.r, other.r), (color.g, other.g), (color.b, other.b)]
+            .iter()
+            .map(|(a, b)| (i32::from(*a) - i32::from(*b)).pow(2))
+            .sum::<i32>()
+    };
+
+    ACI_COLORS
+        .iter()
+        .min_by_key(|(_, aci_color)| dist(aci_color))
+        .map_or(7, |(aci, _)| *aci)
+}
+
+impl Document {
+    /// Write the document as DXF.
+    ///
+    /// Each path is written as an `LWPOLYLINE` entity on a DXF layer named after its layer ID.
+    /// Closed paths have the polyline's closed flag set, and path colors are mapped to the
+    /// closest ACI color. Coordinates are in millimeters, with the Y axis flipped such that the
+    /// page appears upright in CAD software.
+    pub fn to_dxf(&self, mut writer: impl Write, options: &DxfOptions) -> std::io::Result<()> {
+        let height = self.metadata().page_dimensions().map_or(0.0, |(_, h)| h);
+        let scale = self.metadata().unit.to_px() / Unit::Mm.to_px();
+        let mm = |pt: &Point| (pt.x() * scale, (height - pt.y()) * scale);
+
+        let mut group = |code: u16, value: &dyn std::fmt::Display| -> std::io::Result<()> {
+            writeln!(writer, "{code}\n{value}")
+        };
+
+        group(0, &"SECTION")?;
+        group(2, &"HEADER")?;
+        group(9, &"$ACADVER")?;
+        group(1, &"AC1015")?;
+        group(9, &"$INSUNITS")?;
+        group(70, &4)?; // millimeters
+        group(0, &"ENDSEC")?;
+
+        group(0, &"SECTION")?;
+        group(2, &"ENTITIES")?;
+        for (lid, layer) in self.flatten(options.tolerance).layers() {
+            for path in &layer.paths {
+                let mut points = path.data().points();
+                if points.len() < 2 {
+                    continue;
+                }
+
+                let closed = points.len() > 2 && points.first() == points.last();
+                if closed {
+                    points = &points[..points.len() - 1];
+                }
+
+                group(0, &"LWPOLYLINE")?;
+                group(100, &"AcDbEntity")?;
+                group(8, lid)?;
+                group(62, &aci_color(path.metadata().color))?;
+                group(100, &"AcDbPolyline")?;
+                group(90, &points.len())?;
+                group(70, &u8::from(closed))?;
+                for pt in points {
+                    let (x, y) = mm(pt);
+                    group(10, &x)?;
+                    group(20, &y)?;
+                }
+            }
+        }
+        group(0, &"ENDSEC")?;
+        group(0, &"EOF")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PageSize;
+    use approx::assert_abs_diff_eq;
+
+    /// Parse the DXF group code/value pairs.
+    fn parse_dxf(dxf: &str) -> Vec<(u16, &str)> {
+        let lines: Vec<_> = dxf.lines().collect();
+        lines
+            .chunks(2)
+            .map(|c| (c[0].parse().unwrap(), c[1]))
+            .collect()
+    }
+
+    /// Extract the value following each occurrence of `code`.
+    fn values<'a>(groups: &[(u16, &'a str)], code: u16) -> Vec<&'a str> {
+        groups
+            .iter()
+            .filter_map(|(c, v)| (*c == code).then_some(*v))
+            .collect()
+    }
+
+    #[test]
+    fn test_to_dxf() {
+        let mut doc = Document::new_with_page_size(PageSize::new(96., 96.));
+        doc.push_path(2, &[(0., 0.), (96., 0.), (96., 48.)][..]);
+        doc.push_path(5, kurbo::Rect::new(0., 0., 48., 48.));
+        doc.get_mut(5).paths[0].metadata_mut().color = Color::rgb(20, 30, 240);
+
+        let mut out = Vec::new();
+        doc.to_dxf(&mut out, &DxfOptions::default()).unwrap();
+        let dxf = String::from_utf8(out).unwrap();
+        let groups = parse_dxf(&dxf);
+
+        assert_eq!(groups.last(), Some(&(0, "EOF")));
+        assert_eq!(
+            values(&groups, 0)
+                .iter()
+                .filter(|v| **v == "LWPOLYLINE")
+                .count(),
+            2
+        );
+        assert_eq!(values(&groups, 8), vec!["2", "5"]);
+        assert_eq!(values(&groups, 62), vec!["7", "5"]);
+        assert_eq!(values(&groups, 90), vec!["3", "4"]);
+        assert_eq!(values(&groups, 70), vec!["4", "0", "1"]);
+
+        // 96px is 25.4mm, and the Y axis is flipped
+        let xs: Vec<f64> = values(&groups, 10)
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let ys: Vec<f64> = values(&groups, 20)
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        assert_abs_diff_eq!(xs[1], 25.4, epsilon = 1e-9);
+        assert_abs_diff_eq!(ys[0], 25.4, epsilon = 1e-9);
+        assert_abs_diff_eq!(ys[2], 12.7, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_aci_color() {
+        assert_eq!(aci_color(Color::BLACK), 7);
+        assert_eq!(aci_color(Color::WHITE), 7);
+        assert_eq!(aci_color(Color::rgb(250, 10, 10)), 1);
+        assert_eq!(aci_color(Color::rgb(0, 200, 0)), 3);
+        assert_eq!(aci_color(Color::GOLD), 2);
+        assert_eq!(aci_color(Color::rgb(140, 130, 120)), 8);
+    }
+}
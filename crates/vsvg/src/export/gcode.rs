@@ -0,0 +1,153 @@
+use crate::{Document, DocumentTrait, PathTrait, Point, Unit};
+use std::io::Write;
+
+/// Options for [`Document::to_gcode`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcodeOptions {
+    /// Command to raise the pen.
+    pub pen_up: String,
+
+    /// Command to lower the pen.
+    pub pen_down: String,
+
+    /// Feed rate while drawing, in mm/min.
+    pub feed_rate: f64,
+
+    /// Feed rate for pen-up travel, in mm/min.
+    pub travel_rate: f64,
+
+    /// Number of decimals for coordinates.
+    pub precision: usize,
+
+    /// Insert a pause (`M0`) between layers, e.g. for pen changes.
+    pub pause_between_layers: bool,
+
+    /// Tolerance used to flatten curves, in document units.
+    pub tolerance: f64,
+}
+
+impl Default for GcodeOptions {
+    fn default() -> Self {
+        Self {
+            pen_up: String::from("M5"),
+            pen_down: String::from("M3 S90"),
+            feed_rate: 1000.0,
+            travel_rate: 3000.0,
+            precision: 3,
+            pause_between_layers: false,
+            tolerance: 0.1,
+        }
+    }
+}
+
+impl Document {
+    /// Write the document as G-code.
+    ///
+    /// Coordinates are emitted in millimeters and absolute mode (`G21`, `G90`). The pen is
+    /// assumed to be up at the start. For each path, the pen travels to the first point with `G0`,
+    /// is lowered, draws the remaining points with `G1`, and is raised again.
+    pub fn to_gcode(&self, mut writer: impl Write, options: &GcodeOptions) -> std::io::Result<()> {
+        let prec = options.precision;
+        let scale = self.metadata().unit.to_px() / Unit::Mm.to_px();
+        let mm = |pt: &Point| (pt.x() * scale, pt.y() * scale);
+
+        writeln!(writer, "G21")?;
+        writeln!(writer, "G90")?;
+
+        let flattened = self.flatten(options.tolerance);
+        for (i, layer) in flattened.layers().values().enumerate() {
+            if i > 0 && options.pause_between_layers {
+                writeln!(writer, "M0")?;
+            }
+
+            for path in &layer.paths {
+                let Some((first, rest)) = path.data().points().split_first() else {
+                    continue;
+                };
+
+                let (x, y) = mm(first);
+                writeln!(
+                    writer,
+                    "G0 X{x:.prec$} Y{y:.prec$} F{}",
+                    options.travel_rate
+                )?;
+                writeln!(writer, "{}", options.pen_down)?;
+                for (j, pt) in rest.iter().enumerate() {
+                    let (x, y) = mm(pt);
+                    if j == 0 {
+                        writeln!(writer, "G1 X{x:.prec$} Y{y:.prec$} F{}", options.feed_rate)?;
+                    } else {
+                        writeln!(writer, "G1 X{x:.prec$} Y{y:.prec$}")?;
+                    }
+                }
+                writeln!(writer, "{}", options.pen_up)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gcode(doc: &Document, options: &GcodeOptions) -> String {
+        let mut out = Vec::new();
+        doc.to_gcode(&mut out, options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_to_gcode_square() {
+        let mut doc = Document::default();
+        doc.push_path(
+            0,
+            &[(0., 0.), (96., 0.), (96., 96.), (0., 96.), (0., 0.)][..],
+        );
+
+        let options = GcodeOptions::default();
+        let gcode = gcode(&doc, &options);
+        let lines: Vec<_> = gcode.lines().collect();
+
+        assert_eq!(lines.iter().filter(|l| **l == "M3 S90").count(), 1);
+        assert_eq!(lines.iter().filter(|l| l.starts_with("G1 ")).count(), 4);
+        assert_eq!(lines.iter().filter(|l| **l == "M5").count(), 1);
+        assert_eq!(
+            lines,
+            [
+                "G21",
+                "G90",
+                "G0 X0.000 Y0.000 F3000",
+                "M3 S90",
+                "G1 X25.400 Y0.000 F1000",
+                "G1 X25.400 Y25.400",
+                "G1 X0.000 Y25.400",
+                "G1 X0.000 Y0.000",
+                "M5",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_gcode_options() {
+        let mut doc = Document::default();
+        doc.push_path(0, &[(0., 0.), (10., 0.)][..]);
+        doc.push_path(1, &[(0., 10.), (10., 10.)][..]);
+
+        let options = GcodeOptions {
+            pen_up: String::from("G0 Z5"),
+            pen_down: String::from("G0 Z0"),
+            precision: 1,
+            pause_between_layers: true,
+            ..Default::default()
+        };
+        let gcode = gcode(&doc, &options);
+
+        assert_eq!(gcode.matches("G0 Z0\n").count(), 2);
+        assert_eq!(gcode.matches("G0 Z5\n").count(), 2);
+        assert_eq!(gcode.matches("M0\n").count(), 1);
+        assert!(gcode.contains("G1 X2.6 Y0.0 F1000\n"));
+        assert!(gcode.find("M0").unwrap() > gcode.find("X2.6 Y0.0").unwrap());
+    }
+}
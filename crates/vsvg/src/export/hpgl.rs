@@ -0,0 +1,204 @@
+use crate::{Document, DocumentTrait, PathTrait, Point, Unit};
+use std::io::Write;
+
+/// Options for [`Document::to_hpgl`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HpglOptions {
+    /// Plotter units per millimeter (40 for most HP plotters).
+    pub units_per_mm: f64,
+
+    /// Document point mapped to the plotter's origin. Defaults to the bottom-left corner of the
+    /// page if `flip_y` is set, and to the document's origin otherwise.
+    pub origin: Option<Point>,
+
+    /// Flip the Y axis, such that the output is upright on plotters with the origin at the
+    /// bottom-left and the Y axis pointing up (e.g. the HP 7475A).
+    pub flip_y: bool,
+
+    /// Tolerance used to flatten curves, in document units.
+    pub tolerance: f64,
+}
+
+impl Default for HpglOptions {
+    fn default() -> Self {
+        Self {
+            units_per_mm: 40.0,
+            origin: None,
+            flip_y: true,
+            tolerance: 0.1,
+        }
+    }
+}
+
+impl Document {
+    /// Write the document as HPGL.
+    ///
+    /// Each layer is drawn with its own pen, numbered from 1 in layer order (`SP1;`, `SP2;`,
+    /// etc.). Paths are emitted as a `PU` move to their first point followed by a `PD` sequence,
+    /// with coordinates rounded to plotter units. With [`HpglOptions::flip_y`], the Y axis is
+    /// flipped around the page height (or the bottom of the content if the document has no page
+    /// size), like for [`Document::to_dxf`].
+    pub fn to_hpgl(&self, mut writer: impl Write, options: &HpglOptions) -> std::io::Result<()> {
+        let scale = options.units_per_mm * self.metadata().unit.to_px() / Unit::Mm.to_px();
+        let origin = options.origin.unwrap_or_else(|| {
+            if options.flip_y {
+                let height = self
+                    .metadata()
+                    .page_dimensions()
+                    .map_or_else(|| self.bounds().map_or(0.0, |bounds| bounds.y1), |(_, h)| h);
+                Point::new(0.0, height)
+            } else {
+                Point::ZERO
+            }
+        });
+        let y_sign = if options.flip_y { -1.0 } else { 1.0 };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let to_units = |pt: &Point| {
+            (
+                ((pt.x() - origin.x()) * scale).round() as i64,
+                ((pt.y() - origin.y()) * y_sign * scale).round() as i64,
+            )
+        };
+
+        writeln!(writer, "IN;")?;
+        for (pen, layer) in self
+            .flatten(options.tolerance)
+            .layers()
+            .values()
+            .enumerate()
+        {
+            writeln!(writer, "SP{};", pen + 1)?;
+
+            for path in &layer.paths {
+                let Some((first, rest)) = path.data().points().split_first() else {
+                    continue;
+                };
+
+                let (x, y) = to_units(first);
+                write!(writer, "PU{x},{y};")?;
+                if !rest.is_empty() {
+                    let coords: Vec<_> = rest
+                        .iter()
+                        .map(|pt| {
+                            let (x, y) = to_units(pt);
+                            format!("{x},{y}")
+                        })
+                        .collect();
+                    write!(writer, "PD{};", coords.join(","))?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        writeln!(writer, "PU;SP0;")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlattenedPath, LayerTrait, PageSize};
+
+    /// Parse the pen-down coordinates back from HPGL, in plotter units.
+    fn parse_hpgl(hpgl: &str) -> Vec<(usize, Vec<(i64, i64)>)> {
+        let mut pen = 0;
+        let mut paths = vec![];
+        for cmd in hpgl.split(';').map(str::trim) {
+            let numbers = |s: &str| -> Vec<i64> {
+                s.split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(|n| n.parse().unwrap())
+                    .collect()
+            };
+
+            if let Some(arg) = cmd.strip_prefix("SP") {
+                pen = arg.parse().unwrap();
+            } else if let Some(arg) = cmd.strip_prefix("PU") {
+                let n = numbers(arg);
+                if !n.is_empty() {
+                    paths.push((pen, vec![(n[0], n[1])]));
+                }
+            } else if let Some(arg) = cmd.strip_prefix("PD") {
+                let n = numbers(arg);
+                let path = &mut paths.last_mut().unwrap().1;
+                path.extend(n.chunks(2).map(|c| (c[0], c[1])));
+            }
+        }
+        paths
+    }
+
+    #[test]
+    fn test_to_hpgl() {
+        let mut doc = Document::new_with_page_size(PageSize::new(192., 96.));
+        doc.push_path(3, &[(0., 0.), (96., 0.), (96., 48.)][..]);
+        doc.push_path(7, &[(10., 10.), (20., 20.)][..]);
+
+        let mut out = Vec::new();
+        doc.to_hpgl(&mut out, &HpglOptions::default()).unwrap();
+        let hpgl = String::from_utf8(out).unwrap();
+
+        assert!(hpgl.starts_with("IN;\nSP1;\n"));
+        assert!(hpgl.ends_with("PU;SP0;\n"));
+        assert!(hpgl.contains("SP2;\n"));
+
+        // 1 inch is 25.4 mm, or 1016 plotter units, and the Y axis is flipped around the page
+        assert_eq!(
+            parse_hpgl(&hpgl)[0],
+            (1, vec![(0, 1016), (1016, 1016), (1016, 508)])
+        );
+
+        let options = HpglOptions {
+            flip_y: false,
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        doc.to_hpgl(&mut out, &options).unwrap();
+        assert_eq!(
+            parse_hpgl(&String::from_utf8(out).unwrap())[0],
+            (1, vec![(0, 0), (1016, 0), (1016, 508)])
+        );
+
+        // without page size, the content's bottom is at Y = 0
+        doc.metadata_mut().page_size = None;
+        let mut out = Vec::new();
+        doc.to_hpgl(&mut out, &HpglOptions::default()).unwrap();
+        let parsed = parse_hpgl(&String::from_utf8(out).unwrap());
+        assert_eq!(parsed[0], (1, vec![(0, 508), (1016, 508), (1016, 0)]));
+        assert_eq!(parsed[1], (2, vec![(106, 402), (212, 296)]));
+    }
+
+    #[test]
+    fn test_to_hpgl_round_trip() {
+        let mut doc = Document::default();
+        doc.push_path(0, kurbo::Circle::new((50., 30.), 20.));
+        doc.push_path(1, &[(3.3, 4.4), (55.5, 66.6), (77.7, 1.1)][..]);
+
+        let options = HpglOptions {
+            origin: Some(Point::new(10., 5.)),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        doc.to_hpgl(&mut out, &options).unwrap();
+        let parsed = parse_hpgl(&String::from_utf8(out).unwrap());
+
+        let flattened = doc.flatten(options.tolerance);
+        let source: Vec<&FlattenedPath> = flattened
+            .layers()
+            .values()
+            .flat_map(|layer| layer.paths().iter())
+            .collect();
+        assert_eq!(parsed.len(), source.len());
+
+        let scale = options.units_per_mm / Unit::Mm.to_px();
+        #[allow(clippy::cast_precision_loss)]
+        for ((_, coords), path) in parsed.iter().zip(source) {
+            assert_eq!(coords.len(), path.data().points().len());
+            for ((x, y), pt) in coords.iter().zip(path.data().points()) {
+                assert!((*x as f64 / scale + 10. - pt.x()).abs() <= 0.5 / scale);
+                assert!((5. - *y as f64 / scale - pt.y()).abs() <= 0.5 / scale);
+            }
+        }
+    }
+}
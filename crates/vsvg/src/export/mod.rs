@@ -0,0 +1,10 @@
+//! Exporters to plotter-specific and other non-SVG formats.
+
+mod csv;
+mod dxf;
+mod gcode;
+mod hpgl;
+
+pub use dxf::*;
+pub use gcode::*;
+pub use hpgl::*;
@@ -0,0 +1,228 @@
+//! Basic geometric primitives, ready to be pushed into a layer.
+//!
+//! ```
+//! use vsvg::{geom, Document, DocumentTrait};
+//!
+//! let mut doc = Document::default();
+//! doc.push_path(1, geom::circle(50., 50., 20., 0.1));
+//! doc.push_path(1, geom::spiral(50., 50., 5., 15., 3., 0.1));
+//! ```
+
+mod hershey;
+mod text;
+
+use crate::{Path, Point};
+use std::f64::consts::TAU;
+
+pub use hershey::{hershey_text, HersheyFont};
+pub use text::{text, Font, FontError};
+
+/// Smallest tolerance used for the primitives which are approximated.
+const MIN_TOLERANCE: f64 = 1e-6;
+
+/// A closed circle centered on (`cx`, `cy`), approximated by cubic Béziers within `tolerance`.
+#[must_use]
+pub fn circle(cx: f64, cy: f64, r: f64, tolerance: f64) -> Path {
+    Path::from_tolerance(
+        kurbo::Circle::new((cx, cy), r),
+        tolerance.max(MIN_TOLERANCE),
+    )
+}
+
+/// A closed axis-aligned rectangle with its top-left corner at (`x`, `y`).
+#[must_use]
+pub fn rectangle(x: f64, y: f64, w: f64, h: f64) -> Path {
+    Path::from(kurbo::Rect::new(x, y, x + w, y + h))
+}
+
+/// A closed axis-aligned rectangle with corners rounded with `radius`.
+///
+/// The radius is clamped to half the rectangle's smallest side.
+#[must_use]
+pub fn rounded_rectangle(x: f64, y: f64, w: f64, h: f64, radius: f64) -> Path {
+    Path::from(kurbo::RoundedRect::new(x, y, x + w, y + h, radius))
+}
+
+/// An Archimedean spiral centered on (`cx`, `cy`), with its radius going from `r0` to `r1` over
+/// `turns` clockwise turns.
+///
+/// The spiral starts on the right of its center and is made of line segments, with points close
+/// enough that the spiral deviates by less than `tolerance` from the exact curve.
+#[must_use]
+pub fn spiral(cx: f64, cy: f64, r0: f64, r1: f64, turns: f64, tolerance: f64) -> Path {
+    let tolerance = tolerance.max(MIN_TOLERANCE);
+    let r_max = r0.abs().max(r1.abs());
+    let total_angle = turns * TAU;
+
+    // maximum angle between points for a chord sag within tolerance
+    let step = if tolerance < r_max {
+        2.0 * (1.0 - tolerance / r_max).acos()
+    } else {
+        TAU / 4.0
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = ((total_angle.abs() / step).ceil() as usize).max(1);
+
+    #[allow(clippy::cast_precision_loss)]
+    Path::from_points((0..=count).map(|i| {
+        let t = i as f64 / count as f64;
+        let angle = t * total_angle;
+        let r = r0 + (r1 - r0) * t;
+        Point::new(cx + r * angle.cos(), cy + r * angle.sin())
+    }))
+}
+
+/// A regular lattice of `cols` x `rows` points, spaced by `dx` and `dy`, with its first point at
+/// `origin`.
+///
+/// Points are ordered row by row. See [`staggered_grid`] for triangular and hexagonal lattices.
+#[must_use]
+pub fn grid(origin: [f64; 2], cols: usize, rows: usize, dx: f64, dy: f64) -> Vec<[f64; 2]> {
+    staggered_grid(origin, cols, rows, dx, dy, 0.0)
+}
+
+/// Like [`grid`], but with every other row shifted horizontally by `stagger` times `dx`.
+///
+/// A stagger of 0.5 with `dy = dx * 3f64.sqrt() / 2.` yields a triangular lattice, whose points
+/// are the centers of a hexagonal tiling.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn staggered_grid(
+    origin: [f64; 2],
+    cols: usize,
+    rows: usize,
+    dx: f64,
+    dy: f64,
+    stagger: f64,
+) -> Vec<[f64; 2]> {
+    (0..rows)
+        .flat_map(|row| {
+            let shift = if row % 2 == 1 { stagger * dx } else { 0.0 };
+            (0..cols).map(move |col| {
+                [
+                    origin[0] + col as f64 * dx + shift,
+                    origin[1] + row as f64 * dy,
+                ]
+            })
+        })
+        .collect()
+}
+
+/// Convert points into small circles of `radius`, such that a plotter draws them as dots.
+///
+/// See [`circle`] for the meaning of `tolerance`.
+#[must_use]
+pub fn points_to_dots(
+    points: impl IntoIterator<Item = impl Into<Point>>,
+    radius: f64,
+    tolerance: f64,
+) -> Vec<Path> {
+    points
+        .into_iter()
+        .map(|pt| {
+            let pt = pt.into();
+            circle(pt.x(), pt.y(), radius, tolerance)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathTrait;
+    use approx::assert_abs_diff_eq;
+    use kurbo::PathEl;
+
+    fn is_closed(path: &Path) -> bool {
+        matches!(path.data().elements().last(), Some(PathEl::ClosePath))
+    }
+
+    #[test]
+    fn test_circle() {
+        let path = circle(10., 20., 5., 0.01);
+        assert!(is_closed(&path));
+        let bounds = path.bounds().unwrap();
+        assert_abs_diff_eq!(bounds.width(), 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.height(), 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.center().x, 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.center().y, 20.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let path = rectangle(1., 2., 10., 5.);
+        assert!(is_closed(&path));
+        assert_eq!(path.bounds(), Some(kurbo::Rect::new(1., 2., 11., 7.)));
+        assert_abs_diff_eq!(path.length(), 30.0);
+
+        let path = rounded_rectangle(0., 0., 10., 10., 2.);
+        assert!(is_closed(&path));
+        let bounds = path.bounds().unwrap();
+        assert_abs_diff_eq!(bounds.width(), 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.height(), 10.0, epsilon = 1e-9);
+        assert!(path.length() < 40.0);
+    }
+
+    #[test]
+    fn test_spiral() {
+        let path = spiral(0., 0., 1., 10., 3., 0.01);
+        assert!(!is_closed(&path));
+
+        let lines = path.flatten(0.01);
+        let points = lines[0].data().points();
+        let first = points.first().unwrap();
+        let last = points.last().unwrap();
+        assert_abs_diff_eq!(first.x(), 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(first.y(), 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(last.x(), 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(last.y(), 0.0, epsilon = 1e-9);
+
+        // the radius increases steadily
+        let radii: Vec<_> = points.iter().map(|pt| pt.distance(&Point::ZERO)).collect();
+        assert!(radii.windows(2).all(|w| w[0] < w[1]));
+
+        // a coarser tolerance means fewer points
+        let coarse = spiral(0., 0., 1., 10., 3., 0.5);
+        assert!(coarse.data().elements().len() < points.len());
+    }
+
+    #[test]
+    fn test_grid() {
+        let points: Vec<Point> = grid([1., 2.], 3, 3, 10., 5.)
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[0], Point::new(1., 2.));
+        assert_eq!(points[1], Point::new(11., 2.));
+        assert_eq!(points[3], Point::new(1., 7.));
+        assert_eq!(points[8], Point::new(21., 12.));
+
+        assert!(grid([0., 0.], 0, 3, 1., 1.).is_empty());
+    }
+
+    #[test]
+    fn test_staggered_grid() {
+        let points = staggered_grid([0., 0.], 2, 3, 2., 1., 0.5);
+        assert!(points.into_iter().map(Point::from).eq([
+            (0., 0.),
+            (2., 0.),
+            (1., 1.),
+            (3., 1.),
+            (0., 2.),
+            (2., 2.)
+        ]
+        .map(Point::from)));
+    }
+
+    #[test]
+    fn test_points_to_dots() {
+        let dots = points_to_dots(grid([0., 0.], 2, 2, 10., 10.), 0.5, 0.01);
+        assert_eq!(dots.len(), 4);
+        assert!(dots.iter().all(is_closed));
+        let bounds = dots[3].bounds().unwrap();
+        assert_abs_diff_eq!(bounds.center().x, 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.width(), 1.0, epsilon = 1e-9);
+    }
+}
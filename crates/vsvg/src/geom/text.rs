@@ -0,0 +1,211 @@
+//! Rendering of text to outline paths, based on [`ttf_parser`].
+
+use crate::Path;
+use kurbo::BezPath;
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+/// Error returned when loading a [`Font`].
+#[derive(thiserror::Error, Debug)]
+pub enum FontError {
+    /// The font file could not be read.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// The data is not a valid TrueType or OpenType font.
+    #[error("invalid font: {0}")]
+    Parse(#[from] ttf_parser::FaceParsingError),
+}
+
+/// A TrueType or OpenType font, used with [`text`].
+#[derive(Clone)]
+pub struct Font {
+    data: Vec<u8>,
+}
+
+impl std::fmt::Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font")
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+impl Font {
+    /// Load a font from the content of a font file.
+    ///
+    /// For font collections, the first font is used.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, FontError> {
+        Face::parse(&data, 0)?;
+        Ok(Self { data })
+    }
+
+    /// Load a font from a font file.
+    ///
+    /// See [`Font::from_bytes`].
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, FontError> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, 0).expect("font data is validated on creation")
+    }
+}
+
+/// Collect glyph outlines as one path per contour, scaled and flipped to the document's
+/// y-down coordinates.
+struct ContourBuilder {
+    scale: f64,
+    origin: kurbo::Point,
+    current: BezPath,
+    contours: Vec<BezPath>,
+}
+
+impl ContourBuilder {
+    fn point(&self, x: f32, y: f32) -> kurbo::Point {
+        kurbo::Point::new(
+            self.origin.x + f64::from(x) * self.scale,
+            self.origin.y - f64::from(y) * self.scale,
+        )
+    }
+}
+
+impl OutlineBuilder for ContourBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        if !self.current.elements().is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+        self.current.move_to(self.point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current.line_to(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.current.quad_to(self.point(x1, y1), self.point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.current
+            .curve_to(self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+    }
+
+    fn close(&mut self) {
+        self.current.close_path();
+        self.contours.push(std::mem::take(&mut self.current));
+    }
+}
+
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> f64 {
+    face.tables()
+        .kern
+        .and_then(|kern| {
+            kern.subtables
+                .into_iter()
+                .filter(|subtable| subtable.horizontal && !subtable.variable)
+                .find_map(|subtable| subtable.glyphs_kerning(left, right))
+        })
+        .map_or(0.0, f64::from)
+}
+
+/// Render `s` to outline paths with `font`, one path per glyph contour.
+///
+/// `size` is the font size (i.e. the height of the em square) and `pos` is the start of the
+/// baseline. Glyphs are laid out from left to right on a single line, with the font's kerning.
+/// Characters missing from the font are rendered with its fallback glyph.
+#[must_use]
+pub fn text(s: &str, font: &Font, size: f64, pos: [f64; 2]) -> Vec<Path> {
+    let face = font.face();
+    let mut builder = ContourBuilder {
+        scale: size / f64::from(face.units_per_em()),
+        origin: kurbo::Point::new(pos[0], pos[1]),
+        current: BezPath::new(),
+        contours: vec![],
+    };
+
+    let mut previous = None;
+    for c in s.chars() {
+        let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+        if let Some(previous) = previous {
+            builder.origin.x += kerning(&face, previous, glyph) * builder.scale;
+        }
+
+        face.outline_glyph(glyph, &mut builder);
+        if !builder.current.elements().is_empty() {
+            builder.contours.push(std::mem::take(&mut builder.current));
+        }
+
+        builder.origin.x +=
+            f64::from(face.glyph_hor_advance(glyph).unwrap_or_default()) * builder.scale;
+        previous = Some(glyph);
+    }
+
+    builder.contours.into_iter().map(Path::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_file, PathTrait};
+    use approx::assert_abs_diff_eq;
+    use kurbo::PathEl;
+
+    fn font() -> Font {
+        Font::from_file(test_file!("Tuffy.ttf")).unwrap()
+    }
+
+    fn union_bounds(paths: &[Path]) -> kurbo::Rect {
+        paths
+            .iter()
+            .filter_map(PathTrait::bounds)
+            .reduce(|a, b| a.union(b))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_text_glyph() {
+        let font = font();
+        let paths = text("A", &font, 10.0, [100., 50.]);
+
+        // outer contour and counter
+        assert_eq!(paths.len(), 2);
+        assert!(paths
+            .iter()
+            .all(|path| matches!(path.data().elements().last(), Some(PathEl::ClosePath))));
+
+        // sits on the baseline, starting around the origin
+        let bounds = union_bounds(&paths);
+        assert_abs_diff_eq!(bounds.y1, 50.0, epsilon = 0.1);
+        assert!(bounds.x0 >= 99.0 && bounds.x0 < 102.0);
+        assert!(bounds.height() > 5.0 && bounds.height() < 10.0);
+
+        // bounds scale with size
+        let large = union_bounds(&text("A", &font, 20.0, [100., 50.]));
+        assert_abs_diff_eq!(large.width(), 2.0 * bounds.width(), epsilon = 1e-9);
+        assert_abs_diff_eq!(large.height(), 2.0 * bounds.height(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_text_layout() {
+        let font = font();
+        assert!(text("", &font, 10.0, [0., 0.]).is_empty());
+        assert!(text(" ", &font, 10.0, [0., 0.]).is_empty());
+
+        let single = text("l", &font, 10.0, [0., 0.]);
+        let double = text("ll", &font, 10.0, [0., 0.]);
+        assert_eq!(double.len(), 2 * single.len());
+        assert!(union_bounds(&double[single.len()..]).x0 > union_bounds(&single).x1);
+    }
+
+    #[test]
+    fn test_font_error() {
+        assert!(matches!(
+            Font::from_bytes(vec![0; 16]),
+            Err(FontError::Parse(_))
+        ));
+        assert!(matches!(
+            Font::from_file(test_file!("missing.ttf")),
+            Err(FontError::Io(_))
+        ));
+    }
+}
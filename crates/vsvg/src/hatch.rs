@@ -0,0 +1,141 @@
+//! Hatch filling of polygons.
+
+use crate::Point;
+use kurbo::Affine;
+
+/// Compute hatch lines filling the interior of `rings`, according to the even-odd rule.
+///
+/// Lines are at `angle` radians from the X axis and `spacing` apart, the first one being half a
+/// spacing away from the extremity of the rings. Rings are implicitly closed. Lines are returned
+/// in order, alternating direction such as to minimize pen-up travel. Returns an empty vector if
+/// `spacing` is not strictly positive.
+pub(crate) fn hatch_lines(rings: &[&[Point]], angle: f64, spacing: f64) -> Vec<(Point, Point)> {
+    if spacing <= 0.0 || !spacing.is_finite() {
+        return vec![];
+    }
+
+    // rotate the rings such that the hatch lines are horizontal
+    let to_hatch = Affine::rotate(-angle);
+    let edges: Vec<(kurbo::Point, kurbo::Point)> = rings
+        .iter()
+        .filter(|ring| ring.len() > 1)
+        .flat_map(|ring| {
+            ring.iter().zip(ring.iter().cycle().skip(1)).map(|(a, b)| {
+                (
+                    to_hatch * kurbo::Point::from(*a),
+                    to_hatch * kurbo::Point::from(*b),
+                )
+            })
+        })
+        .collect();
+    let Some((y_min, y_max)) =
+        edges
+            .iter()
+            .map(|(a, _)| a.y)
+            .fold(None, |acc: Option<(f64, f64)>, y| {
+                Some(acc.map_or((y, y), |(min, max)| (min.min(y), max.max(y))))
+            })
+    else {
+        return vec![];
+    };
+
+    let from_hatch = Affine::rotate(angle);
+    let mut lines = vec![];
+    let mut reverse = false;
+    for y in (0..)
+        .map(|i| y_min + (f64::from(i) + 0.5) * spacing)
+        .take_while(|y| *y < y_max)
+    {
+        let mut xs: Vec<f64> = edges
+            .iter()
+            .filter(|(a, b)| (a.y <= y) != (b.y <= y))
+            .map(|(a, b)| a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x))
+            .collect();
+        xs.sort_by(f64::total_cmp);
+
+        let mut row: Vec<_> = xs
+            .chunks_exact(2)
+            .map(|pair| {
+                (
+                    Point::from(from_hatch * kurbo::Point::new(pair[0], y)),
+                    Point::from(from_hatch * kurbo::Point::new(pair[1], y)),
+                )
+            })
+            .collect();
+        if reverse {
+            row.reverse();
+            for (a, b) in &mut row {
+                std::mem::swap(a, b);
+            }
+        }
+        lines.extend(row);
+
+        reverse = !reverse;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn square(x0: f64, y0: f64, size: f64) -> Vec<Point> {
+        [
+            (x0, y0),
+            (x0 + size, y0),
+            (x0 + size, y0 + size),
+            (x0, y0 + size),
+        ]
+        .map(Point::from)
+        .to_vec()
+    }
+
+    #[test]
+    fn test_hatch_square() {
+        let square = square(0., 0., 10.);
+        let lines = hatch_lines(&[&square], 0.0, 1.0);
+        assert_eq!(lines.len(), 10);
+        for (i, (a, b)) in lines.iter().enumerate() {
+            let y = f64::from(u8::try_from(i).unwrap()) + 0.5;
+            assert_abs_diff_eq!(a.y(), y, epsilon = 1e-9);
+            assert_abs_diff_eq!(b.y(), y, epsilon = 1e-9);
+            assert_abs_diff_eq!((a.x() - b.x()).abs(), 10.0, epsilon = 1e-9);
+        }
+
+        // alternating direction
+        assert!(lines[0].0.x() < lines[0].1.x());
+        assert!(lines[1].0.x() > lines[1].1.x());
+
+        assert!(hatch_lines(&[&square], 0.0, 0.0).is_empty());
+        assert!(hatch_lines(&[], 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_hatch_angle() {
+        let square = square(0., 0., 10.);
+        let lines = hatch_lines(&[&square], std::f64::consts::FRAC_PI_2, 2.0);
+        assert_eq!(lines.len(), 5);
+        for (a, b) in &lines {
+            assert_abs_diff_eq!(a.x(), b.x(), epsilon = 1e-9);
+            assert_abs_diff_eq!((a.y() - b.y()).abs(), 10.0, epsilon = 1e-9);
+        }
+
+        // diagonal lines are shorter near the corners
+        let lines = hatch_lines(&[&square], std::f64::consts::FRAC_PI_4, 1.0);
+        let length = |(a, b): &(Point, Point)| a.distance(b);
+        assert!(length(&lines[0]) < length(&lines[lines.len() / 2]));
+    }
+
+    #[test]
+    fn test_hatch_even_odd() {
+        // a square with a hole
+        let outer = square(0., 0., 10.);
+        let inner = square(4., 4., 2.);
+        let lines = hatch_lines(&[&outer, &inner], 0.0, 1.0);
+        assert_eq!(lines.len(), 12);
+        let total: f64 = lines.iter().map(|(a, b)| a.distance(b)).sum();
+        assert_abs_diff_eq!(total, 100.0 - 2.0 * 2.0, epsilon = 1e-9);
+    }
+}
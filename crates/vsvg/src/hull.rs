@@ -0,0 +1,110 @@
+//! Convex hull computation.
+
+use crate::Point;
+use kurbo::BezPath;
+
+/// Cross product of `(a - o)` and `(b - o)`.
+fn cross(o: &Point, a: &Point, b: &Point) -> f64 {
+    (a.x() - o.x()) * (b.y() - o.y()) - (a.y() - o.y()) * (b.x() - o.x())
+}
+
+/// Compute the convex hull of a set of points with Andrew's monotone chain algorithm.
+///
+/// The hull vertices are returned in order, without repeating the first one, and collinear
+/// points are excluded. Collinear inputs yield their two extreme points, and a single
+/// (possibly repeated) point yields that point alone.
+pub(crate) fn convex_hull(mut points: Vec<Point>) -> Vec<Point> {
+    points.sort_by(|a, b| a.x().total_cmp(&b.x()).then(a.y().total_cmp(&b.y())));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+    let push = |hull: &mut Vec<Point>, pt: &Point, min_len: usize| {
+        while hull.len() >= min_len
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], pt) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*pt);
+    };
+
+    // lower hull, then upper hull
+    for pt in &points {
+        push(&mut hull, pt, 2);
+    }
+    let lower_len = hull.len() + 1;
+    for pt in points.iter().rev().skip(1) {
+        push(&mut hull, pt, lower_len);
+    }
+
+    hull.pop(); // the first point is repeated at the end
+    hull
+}
+
+/// Build a closed path from hull vertices, as returned by [`convex_hull`].
+pub(crate) fn hull_to_bezpath(hull: &[Point]) -> BezPath {
+    let mut path = BezPath::new();
+    if let Some((first, rest)) = hull.split_first() {
+        path.move_to(*first);
+        if rest.is_empty() {
+            path.line_to(*first);
+        }
+        for pt in rest {
+            path.line_to(*pt);
+        }
+        path.close_path();
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pts(coords: &[(f64, f64)]) -> Vec<Point> {
+        coords.iter().copied().map(Point::from).collect()
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let points = pts(&[
+            (0., 0.),
+            (2., 1.),
+            (4., 0.),
+            (3., 2.),
+            (4., 4.),
+            (2., 4.),
+            (0., 4.),
+            (1., 1.),
+        ]);
+        assert_eq!(
+            convex_hull(points),
+            pts(&[(0., 0.), (4., 0.), (4., 4.), (0., 4.)])
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_degenerate() {
+        assert!(convex_hull(vec![]).is_empty());
+        assert_eq!(convex_hull(pts(&[(1., 1.), (1., 1.)])), pts(&[(1., 1.)]));
+        assert_eq!(
+            convex_hull(pts(&[(2., 2.), (0., 0.), (1., 1.), (3., 3.)])),
+            pts(&[(0., 0.), (3., 3.)])
+        );
+    }
+
+    #[test]
+    fn test_hull_to_bezpath() {
+        assert_eq!(
+            hull_to_bezpath(&pts(&[(0., 0.), (1., 0.), (0., 1.)])),
+            BezPath::from_svg("M 0,0 L 1,0 L 0,1 Z").unwrap()
+        );
+        assert_eq!(
+            hull_to_bezpath(&pts(&[(1., 2.)])),
+            BezPath::from_svg("M 1,2 L 1,2 Z").unwrap()
+        );
+        assert!(hull_to_bezpath(&[]).elements().is_empty());
+    }
+}
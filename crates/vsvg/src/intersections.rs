@@ -0,0 +1,294 @@
+//! Splitting of paths at their self-intersections.
+
+use crate::{Path, PathTrait, DEFAULT_TOLERANCE};
+use kurbo::Point;
+
+/// Relative tolerance on segment parameters, below which an intersection is considered to be at
+/// a vertex.
+const VERTEX_EPSILON: f64 = 1e-9;
+
+/// Parameters `(t, u)` and point of the intersection of segments `p0p1` and `q0q1`, if any,
+/// excluding collinear overlaps.
+///
+/// Both parameters are in `0.0..=1.0`, including contacts at the segments' end points. Values
+/// within [`VERTEX_EPSILON`] of an end point are snapped to it, in which case the intersection
+/// point is exactly that end point.
+fn intersect(p0: Point, p1: Point, q0: Point, q1: Point) -> Option<(f64, f64, Point)> {
+    let (r, s) = (p1 - p0, q1 - q0);
+    let denom = r.cross(s);
+    if denom.abs() < f64::EPSILON * r.hypot() * s.hypot() {
+        return None;
+    }
+
+    let snap = |v: f64| {
+        if v.abs() < VERTEX_EPSILON {
+            Some(0.0)
+        } else if (v - 1.0).abs() < VERTEX_EPSILON {
+            Some(1.0)
+        } else {
+            (0.0..=1.0).contains(&v).then_some(v)
+        }
+    };
+
+    let t = snap((q0 - p0).cross(s) / denom)?;
+    let u = snap((q0 - p0).cross(r) / denom)?;
+
+    #[allow(clippy::float_cmp)]
+    let pt = match (t, u) {
+        (0.0, _) => p0,
+        (1.0, _) => p1,
+        (_, 0.0) => q0,
+        (_, 1.0) => q1,
+        _ => p0 + r * t,
+    };
+    Some((t, u, pt))
+}
+
+/// A cut position along the polylines: polyline index, segment index, segment parameter and
+/// point.
+///
+/// The point is shared by both cuts of an intersection, such that the resulting pieces meet
+/// exactly.
+pub(crate) type Cut = (usize, usize, f64, Point);
+
+/// Whether a polyline is closed, i.e. its first point is repeated at the end.
+fn is_closed(points: &[Point]) -> bool {
+    points.len() > 3 && points.first() == points.last()
+}
+
+/// Cut positions of all intersections between the segments of `lines`, sorted.
+///
+/// Adjacent segments of a polyline (including the closing pair of closed polylines) only share
+/// their common vertex and are not tested. Intersections at a vertex are reported once, at the
+/// start of the following segment, and those at the ends of open polylines are ignored since
+/// there is nothing to cut. This is a brute force O(n²) search, with a bounding box check.
+pub(crate) fn find_cuts(lines: &[Vec<Point>]) -> Vec<Cut> {
+    let segments: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line_idx, points)| {
+            let closed = is_closed(points);
+            let count = points.len() - 1;
+            points.windows(2).enumerate().map(move |(seg_idx, w)| {
+                let bounds = kurbo::Rect::from_points(w[0], w[1]);
+                (line_idx, seg_idx, w[0], w[1], bounds, closed, count)
+            })
+        })
+        .collect();
+
+    // move cuts at the end of a segment to the start of the next one
+    let normalize = |(line, seg, t, pt): Cut, closed: bool, count: usize| -> Option<Cut> {
+        #[allow(clippy::float_cmp)]
+        let cut = if t == 1.0 {
+            if seg + 1 < count {
+                (line, seg + 1, 0.0, pt)
+            } else if closed {
+                (line, 0, 0.0, pt)
+            } else {
+                return None;
+            }
+        } else {
+            (line, seg, t, pt)
+        };
+        (closed || cut.1 != 0 || cut.2 != 0.0).then_some(cut)
+    };
+
+    let mut cuts = vec![];
+    for (i, &(line_a, seg_a, p0, p1, bounds_a, closed_a, count_a)) in segments.iter().enumerate() {
+        for &(line_b, seg_b, q0, q1, bounds_b, closed_b, count_b) in &segments[i + 1..] {
+            if line_a == line_b
+                && (seg_b == seg_a + 1 || (closed_a && seg_a == 0 && seg_b == count_a - 1))
+            {
+                continue;
+            }
+            if bounds_a.x1 < bounds_b.x0
+                || bounds_b.x1 < bounds_a.x0
+                || bounds_a.y1 < bounds_b.y0
+                || bounds_b.y1 < bounds_a.y0
+            {
+                continue;
+            }
+
+            if let Some((t, u, pt)) = intersect(p0, p1, q0, q1) {
+                cuts.extend(normalize((line_a, seg_a, t, pt), closed_a, count_a));
+                cuts.extend(normalize((line_b, seg_b, u, pt), closed_b, count_b));
+            }
+        }
+    }
+
+    cuts.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)).then(a.2.total_cmp(&b.2)));
+    cuts.dedup_by(|a, b| (a.0, a.1) == (b.0, b.1) && (a.2 - b.2).abs() < VERTEX_EPSILON);
+    cuts
+}
+
+/// Split `points` at `cuts`, which must be sorted and refer to this polyline.
+///
+/// Pieces meeting at a cut share the exact same end point. Closed polylines are rotated such
+/// that their pieces start and end on a cut.
+pub(crate) fn split_polyline(points: &[Point], cuts: &[Cut]) -> Vec<Vec<Point>> {
+    let mut pieces = vec![];
+    let mut current = vec![points[0]];
+    for (seg_idx, w) in points.windows(2).enumerate() {
+        for &(_, _, _, pt) in cuts.iter().filter(|cut| cut.1 == seg_idx) {
+            if current.last() != Some(&pt) {
+                current.push(pt);
+            }
+            pieces.push(std::mem::replace(&mut current, vec![pt]));
+        }
+        current.push(w[1]);
+    }
+    pieces.push(current);
+
+    if is_closed(points) && pieces.len() > 1 {
+        let first = pieces.remove(0);
+        if let Some(last) = pieces.last_mut() {
+            last.extend(first.into_iter().skip(1));
+        }
+    }
+
+    pieces.retain(|piece| piece.len() > 1);
+    pieces
+}
+
+impl Path {
+    /// Split the path where it crosses itself.
+    ///
+    /// All segment pairs are tested for intersections, including across sub-paths, and the path
+    /// is cut at each crossing, with the intersection point ending one piece and starting the
+    /// next. Closed sub-paths are rotated such that their first and last pieces are merged. For
+    /// example, a figure-eight is split into its two loops. Segments touching at a point are cut
+    /// there too, while collinear overlaps are not considered crossings.
+    ///
+    /// Curves are flattened with [`DEFAULT_TOLERANCE`], unless the path has no crossings, in
+    /// which case it is returned unchanged. The metadata is kept.
+    #[must_use]
+    pub fn split_at_self_intersections(&self) -> Vec<Path> {
+        let lines: Vec<Vec<Point>> = self
+            .flatten(DEFAULT_TOLERANCE)
+            .iter()
+            .map(|line| {
+                let mut points: Vec<Point> = line.data().points().iter().map(Into::into).collect();
+                points.dedup();
+                points
+            })
+            .filter(|points| points.len() > 1)
+            .collect();
+
+        let cuts = find_cuts(&lines);
+        if cuts.is_empty() {
+            return vec![self.clone()];
+        }
+
+        lines
+            .iter()
+            .enumerate()
+            .flat_map(|(line_idx, points)| {
+                let line_cuts: Vec<_> = cuts
+                    .iter()
+                    .filter(|cut| cut.0 == line_idx)
+                    .copied()
+                    .collect();
+                split_polyline(points, &line_cuts)
+            })
+            .map(|piece| {
+                let mut path = Path::from_points(piece);
+                *path.metadata_mut() = self.metadata().clone();
+                path
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn points(path: &Path) -> Vec<(f64, f64)> {
+        path.flatten(DEFAULT_TOLERANCE)[0]
+            .data()
+            .points()
+            .iter()
+            .map(|pt| (pt.x(), pt.y()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_figure_eight() {
+        let mut path = Path::from_points([(0., 0.), (2., 2.), (2., 0.), (0., 2.), (0., 0.)]);
+        path.metadata_mut().color = Color::RED;
+
+        let pieces = path.split_at_self_intersections();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(
+            points(&pieces[0]),
+            vec![(1., 1.), (2., 2.), (2., 0.), (1., 1.)]
+        );
+        assert_eq!(
+            points(&pieces[1]),
+            vec![(1., 1.), (0., 2.), (0., 0.), (1., 1.)]
+        );
+        assert!(pieces
+            .iter()
+            .all(|piece| piece.metadata().color == Color::RED));
+    }
+
+    #[test]
+    fn test_split_open() {
+        // a loop in the middle of an open path
+        let path = Path::from_points([(0., 0.), (4., 0.), (3., -1.), (2., 1.)]);
+        let pieces = path.split_at_self_intersections();
+        assert_eq!(pieces.len(), 3);
+        let (x, y) = points(&pieces[0])[1];
+        assert_eq!(points(&pieces[0]), vec![(0., 0.), (x, y)]);
+        assert!((x - 2.5).abs() < 1e-9 && y.abs() < 1e-9);
+        assert_eq!(points(&pieces[1]).len(), 4);
+        assert_eq!(points(&pieces[2]), vec![(x, y), (2., 1.)]);
+    }
+
+    #[test]
+    fn test_split_at_vertex() {
+        // the crossing is at a vertex of both loops
+        let path = Path::from_points([
+            (0., 0.),
+            (1., 1.),
+            (2., 2.),
+            (2., 0.),
+            (1., 1.),
+            (0., 2.),
+            (0., 0.),
+        ]);
+        let pieces = path.split_at_self_intersections();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(
+            points(&pieces[0]),
+            vec![(1., 1.), (2., 2.), (2., 0.), (1., 1.)]
+        );
+
+        // a sub-path through a vertex of the other is cut once on each
+        let mut cross = Path::from_points([(0., 1.), (1., 1.), (2., 1.)]);
+        cross
+            .data_mut()
+            .extend(Path::from_points([(1., 0.), (1., 2.)]).data().iter());
+        let pieces = cross.split_at_self_intersections();
+        assert_eq!(pieces.len(), 4);
+        assert!(pieces.iter().all(|piece| points(piece).len() == 2));
+
+        // an end point touching another segment only cuts that segment
+        let path = Path::from_points([(0., 0.), (2., 0.), (2., 2.), (1., 0.)]);
+        assert_eq!(path.split_at_self_intersections().len(), 2);
+    }
+
+    #[test]
+    fn test_split_none() {
+        let square = Path::from(kurbo::Rect::new(0., 0., 10., 10.));
+        assert_eq!(square.split_at_self_intersections(), vec![square.clone()]);
+
+        // two crossing sub-paths
+        let mut cross = Path::from_points([(0., 5.), (10., 5.)]);
+        cross
+            .data_mut()
+            .extend(Path::from_points([(5., 0.), (5., 10.)]).data().iter());
+        assert_eq!(cross.split_at_self_intersections().len(), 4);
+    }
+}
@@ -1,7 +1,7 @@
 use super::{LayerMetadata, LayerTrait};
 use crate::{FlattenedPath, Polyline, Transforms};
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct FlattenedLayer {
     pub paths: Vec<FlattenedPath>,
     metadata: LayerMetadata,
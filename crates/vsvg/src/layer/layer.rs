@@ -1,7 +1,10 @@
 use super::{FlattenedLayer, LayerMetadata, LayerTrait, Transforms};
-use crate::{FlattenedPath, Path, Point};
+use crate::dedup::SegmentIndex;
+use crate::{Path, PathDataTrait, PathTrait, Point};
+use kurbo::{ParamCurve, PathEl, PathSeg};
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Layer {
     pub paths: Vec<Path>,
     metadata: LayerMetadata,
@@ -44,15 +47,28 @@ impl LayerTrait<Path, kurbo::BezPath> for Layer {
 }
 
 impl Layer {
+    /// Flatten the layer's paths into polylines.
+    ///
+    /// With the `parallel` feature, paths are flattened in parallel. The output order is the
+    /// same either way.
     #[must_use]
     pub fn flatten(&self, tolerance: f64) -> FlattenedLayer {
-        let flattened_paths =
+        #[cfg(feature = "parallel")]
+        let flattened_paths = {
+            use rayon::prelude::*;
+
             self.paths
-                .iter()
-                .fold(Vec::<FlattenedPath>::new(), |mut polylines, path| {
-                    polylines.append(&mut path.flatten(tolerance));
-                    polylines
-                });
+                .par_iter()
+                .flat_map_iter(|path| path.flatten(tolerance))
+                .collect()
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let flattened_paths = self
+            .paths
+            .iter()
+            .flat_map(|path| path.flatten(tolerance))
+            .collect();
 
         FlattenedLayer::new(flattened_paths, self.metadata.clone())
     }
@@ -81,13 +97,109 @@ impl Layer {
             .collect()
     }
 
+    /// Crop all paths to the given rectangle.
+    ///
+    /// Paths entirely outside the rectangle are removed, and paths crossing its boundary are
+    /// split into one path per inside portion. Paths entirely inside are left untouched.
     pub fn crop(&mut self, x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> &Self {
-        self.paths.iter_mut().for_each(|path| {
-            path.crop(x_min, y_min, x_max, y_max);
-        });
+        self.paths = std::mem::take(&mut self.paths)
+            .into_iter()
+            .flat_map(|mut path| {
+                let Some(bounds) = path.bounds() else {
+                    return vec![path];
+                };
+
+                if bounds.x1 < x_min || bounds.x0 > x_max || bounds.y1 < y_min || bounds.y0 > y_max
+                {
+                    vec![]
+                } else if bounds.x0 >= x_min
+                    && bounds.x1 <= x_max
+                    && bounds.y0 >= y_min
+                    && bounds.y1 <= y_max
+                {
+                    vec![path]
+                } else {
+                    path.crop(x_min, y_min, x_max, y_max);
+                    let mut subpaths: Vec<_> = path
+                        .into_subpaths()
+                        .into_iter()
+                        .filter(|path| !path.data().is_degenerate())
+                        .collect();
+
+                    // the portions of a closed path may connect across its start point
+                    if subpaths.len() > 1
+                        && subpaths[subpaths.len() - 1].end() == subpaths[0].start()
+                    {
+                        let first = subpaths.remove(0);
+                        let last = subpaths.len() - 1;
+                        subpaths[last].data_mut().join(first.data());
+                    }
+
+                    subpaths
+                }
+            })
+            .collect();
 
         self
     }
+
+    /// Remove segments which are coincident with another segment of the layer, within
+    /// `tolerance` and regardless of direction.
+    ///
+    /// The first occurrence of each segment is kept. Paths which lose some of their segments are
+    /// split into one path per remaining portion. Returns the number of removed segments.
+    pub fn dedup_lines(&mut self, tolerance: f64) -> usize {
+        let mut index = SegmentIndex::new(tolerance);
+        let mut removed = 0;
+
+        self.paths = std::mem::take(&mut self.paths)
+            .into_iter()
+            .flat_map(|path| {
+                let mut portions: Vec<kurbo::BezPath> = vec![];
+                let mut current = kurbo::BezPath::new();
+                let mut current_end = None;
+                let mut path_removed = 0;
+                for seg in path.data.segments() {
+                    if !index.insert(seg) {
+                        path_removed += 1;
+                        continue;
+                    }
+
+                    if current_end != Some(seg.start()) {
+                        if !current.elements().is_empty() {
+                            portions.push(std::mem::take(&mut current));
+                        }
+                        current.move_to(seg.start());
+                    }
+                    current.push(match seg {
+                        PathSeg::Line(line) => PathEl::LineTo(line.p1),
+                        PathSeg::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+                        PathSeg::Cubic(cubic) => PathEl::CurveTo(cubic.p1, cubic.p2, cubic.p3),
+                    });
+                    current_end = Some(seg.end());
+                }
+                if !current.elements().is_empty() {
+                    portions.push(current);
+                }
+
+                removed += path_removed;
+                if path_removed == 0 {
+                    // keep the original data, including close commands
+                    return vec![path];
+                }
+
+                portions
+                    .into_iter()
+                    .map(|data| Path {
+                        data,
+                        metadata: path.metadata().clone(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        removed
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +211,9 @@ mod test {
         let mut layer = Layer::new();
         assert_eq!(layer.bounds(), None);
 
+        layer.push_path(Path::default());
+        assert_eq!(layer.bounds(), None);
+
         layer.push_path(Path::from(kurbo::Line::new((0.0, 0.0), (10., 15.))));
         assert_eq!(layer.bounds(), Some(kurbo::Rect::new(0.0, 0.0, 10.0, 15.0)));
 
@@ -109,6 +224,89 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_layer_filter_paths() {
+        let mut layer = Layer::new();
+        layer.push_path(Path::default());
+        layer.push_path(Path::from_points([(1., 1.)]));
+        layer.push_path(Path::from(kurbo::Line::new((0., 0.), (0.5, 0.))));
+        layer.push_path(Path::from(kurbo::Line::new((0., 0.), (5., 0.))));
+        layer.push_path(kurbo::Circle::new((0., 0.), 0.1));
+
+        let mut layer2 = layer.clone();
+        assert_eq!(layer2.remove_empty_paths(), 2);
+        assert_eq!(layer2.paths.len(), 3);
+        assert_eq!(layer2.remove_empty_paths(), 0);
+
+        assert_eq!(layer.filter_paths(1.0), 4);
+        assert_eq!(
+            layer.paths,
+            vec![Path::from(kurbo::Line::new((0., 0.), (5., 0.)))]
+        );
+    }
+
+    #[test]
+    fn test_layer_dedup_lines() {
+        let mut layer = Layer::new();
+        layer.push_path(kurbo::Rect::new(0., 0., 10., 10.));
+        // shares the right side of the square, backward
+        layer.push_path(&[(20., 0.), (10., 0.), (10., 10.), (20., 10.)][..]);
+        // exact duplicate of the square
+        layer.push_path(kurbo::Rect::new(0., 0., 10., 10.));
+
+        assert_eq!(layer.dedup_lines(0.01), 5);
+        assert_eq!(layer.paths.len(), 3);
+        assert_eq!(
+            layer.paths[0],
+            Path::from(kurbo::Rect::new(0., 0., 10., 10.))
+        );
+        assert_eq!(layer.paths[1], Path::from_svg("M 20,0 L 10,0").unwrap());
+        assert_eq!(layer.paths[2], Path::from_svg("M 10,10 L 20,10").unwrap());
+    }
+
+    #[test]
+    fn test_layer_crop() {
+        let mut layer = Layer::new();
+        // straddles the right edge
+        layer.push_path(kurbo::Line::new((5., 5.), (15., 5.)));
+        // fully outside
+        layer.push_path(kurbo::Line::new((20., 20.), (30., 30.)));
+        // fully inside, closed
+        layer.push_path(kurbo::Rect::new(1., 1., 2., 2.));
+        // goes out and back in
+        layer.push_path(&[(5., 8.), (15., 8.), (15., 9.), (5., 9.)][..]);
+
+        layer.crop(0., 0., 10., 10.);
+        assert_eq!(layer.paths.len(), 4);
+        assert_eq!(
+            layer.paths[0],
+            Path::from(kurbo::Line::new((5., 5.), (10., 5.)))
+        );
+        assert_eq!(layer.paths[1], Path::from(kurbo::Rect::new(1., 1., 2., 2.)));
+        assert_eq!(
+            layer.paths[2],
+            Path::from(kurbo::Line::new((5., 8.), (10., 8.)))
+        );
+        assert_eq!(
+            layer.paths[3],
+            Path::from(kurbo::Line::new((10., 9.), (5., 9.)))
+        );
+
+        // the inside portions of a closed path are joined across their start point
+        let mut layer = Layer::new();
+        layer.push_path(kurbo::Rect::new(5., 5., 15., 8.));
+        layer.crop(0., 0., 10., 10.);
+        assert_eq!(
+            layer.paths,
+            vec![Path::from_points([
+                (10., 8.),
+                (5., 8.),
+                (5., 5.),
+                (10., 5.)
+            ])]
+        );
+    }
+
     #[test]
     fn test_layer_push_shape() {
         let mut layer = Layer::new();
@@ -119,4 +317,56 @@ mod test {
             Path::from(kurbo::Rect::new(0.0, 0.0, 10.0, 10.0))
         );
     }
+
+    #[test]
+    fn test_layer_merge_paths() {
+        let mut layer = Layer::new();
+        layer.push_path(kurbo::Line::new((0.0, 0.0), (10.0, 0.0)));
+        layer.push_path(kurbo::Line::new((10.0, 0.05), (10.0, 10.0)));
+        layer.merge_paths(0.1);
+
+        assert_eq!(layer.paths.len(), 1);
+        assert_eq!(
+            layer.paths[0],
+            Path::from_svg("M 0,0 L 10,0 L 10,0.05 L 10,10").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_layer_merge_paths_reverse() {
+        let mut layer = Layer::new();
+        layer.push_path(kurbo::Line::new((10.0, 0.0), (10.0, 10.0)));
+        layer.push_path(kurbo::Line::new((20.0, 20.0), (30.0, 30.0)));
+        layer.push_path(kurbo::Line::new((0.0, 0.0), (10.0, 10.0)));
+        layer.push_path(kurbo::Line::new((10.0, 0.0), (20.0, 0.0)));
+        layer.merge_paths(0.1);
+
+        assert_eq!(layer.paths.len(), 2);
+        assert_eq!(
+            layer.paths[0],
+            Path::from_svg("M 20,0 L 10,0 L 10,10 L 0,0").unwrap()
+        );
+        assert_eq!(
+            layer.paths[1],
+            Path::from(kurbo::Line::new((20.0, 20.0), (30.0, 30.0)))
+        );
+    }
+
+    #[test]
+    fn test_layer_merge_paths_metadata() {
+        let mut layer = Layer::new();
+        layer.push_path(kurbo::Line::new((0.0, 0.0), (10.0, 0.0)));
+        let mut path = Path::from(kurbo::Line::new((10.0, 0.0), (10.0, 10.0)));
+        path.metadata_mut().color = crate::Color::RED;
+        layer.push_path(path.clone());
+        layer.push_path(kurbo::Line::new((0.0, 10.0), (0.0, 0.0)));
+        layer.merge_paths(0.1);
+
+        assert_eq!(layer.paths.len(), 2);
+        assert_eq!(
+            layer.paths[0],
+            Path::from_svg("M 0,10 L 0,0 L 10,0").unwrap()
+        );
+        assert_eq!(layer.paths[1], path);
+    }
 }
@@ -1,4 +1,8 @@
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct LayerMetadata {
     pub name: String,
+
+    /// Hidden layers are not displayed by default, e.g. when imported from a hidden SVG group.
+    pub hidden: bool,
 }
@@ -3,7 +3,7 @@ mod flattened_layer;
 mod layer;
 mod metadata;
 
-use crate::{IndexBuilder, PathDataTrait, PathTrait, Point, Transforms};
+use crate::{IndexBuilder, PathDataTrait, PathMetadata, PathTrait, Point, Transforms};
 
 use crate::stats::LayerStats;
 pub use flattened_layer::FlattenedLayer;
@@ -19,28 +19,144 @@ pub trait LayerTrait<P: PathTrait<D>, D: PathDataTrait>: Default + Transforms {
 
     fn metadata_mut(&mut self) -> &mut LayerMetadata;
 
+    /// Bounding box of the layer, or `None` if it has no non-empty path.
     fn bounds(&self) -> Option<kurbo::Rect> {
-        if self.paths().is_empty() {
-            return None;
+        self.paths()
+            .iter()
+            .filter_map(PathTrait::bounds)
+            .reduce(|acc, bounds| acc.union(bounds))
+    }
+
+    /// Total geometric length of the layer's paths.
+    fn length(&self) -> f64 {
+        self.paths().iter().map(PathTrait::length).sum()
+    }
+
+    /// Simplify all paths using the Ramer–Douglas–Peucker algorithm.
+    ///
+    /// See [`PathDataTrait::simplify`].
+    fn simplify(&mut self, tolerance: f64) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.simplify(tolerance));
+    }
+
+    /// Simplify all paths using the Visvalingam–Whyatt algorithm.
+    ///
+    /// See [`PathDataTrait::simplify_vw`].
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.simplify_vw(area_threshold));
+    }
+
+    /// Resample all paths with points every `spacing` units along their length.
+    ///
+    /// See [`PathDataTrait::resample`].
+    fn resample(&mut self, spacing: f64) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.resample(spacing));
+    }
+
+    /// Smooth all paths with Chaikin's corner-cutting algorithm.
+    ///
+    /// See [`PathDataTrait::smooth_chaikin`].
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.smooth_chaikin(iterations));
+    }
+
+    /// Remove consecutive duplicate points from all paths.
+    ///
+    /// See [`PathDataTrait::dedup_points`].
+    fn dedup_points(&mut self, epsilon: f64) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.dedup_points(epsilon));
+    }
+
+    /// Round all coordinates of all paths to the nearest multiple of `step`.
+    ///
+    /// See [`PathDataTrait::quantize`].
+    fn quantize(&mut self, step: f64) {
+        self.paths_mut()
+            .iter_mut()
+            .for_each(|path| path.quantize(step));
+    }
+
+    /// Displace each point of all paths by a random offset.
+    ///
+    /// Each path is given its own seed, derived from `seed` and its index. See
+    /// [`PathDataTrait::jitter`].
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        for (i, path) in (0..).zip(self.paths_mut()) {
+            path.jitter(amplitude, crate::noise::hash(seed, i));
         }
+    }
 
-        let first = self.paths().first().expect("checked").bounds();
-        Some(
-            self.paths()
-                .iter()
-                .skip(1)
-                .fold(first, |acc, path| acc.union(path.bounds())),
-        )
+    /// Displace each point of all paths by a smooth random offset.
+    ///
+    /// Each path is given its own seed, derived from `seed` and its index. See
+    /// [`PathDataTrait::wobble`].
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        for (i, path) in (0..).zip(self.paths_mut()) {
+            path.wobble(amplitude, wavelength, crate::noise::hash(seed, i));
+        }
+    }
+
+    /// Remove paths shorter than `min_length` and return how many were removed.
+    ///
+    /// Degenerate paths (see [`PathDataTrait::is_degenerate`]) are removed regardless of
+    /// `min_length`.
+    fn filter_paths(&mut self, min_length: f64) -> usize {
+        let count = self.paths().len();
+        self.paths_mut()
+            .retain(|path| !path.data().is_degenerate() && path.length() >= min_length);
+        count - self.paths().len()
+    }
+
+    /// Remove paths with fewer than two points and return how many were removed.
+    fn remove_empty_paths(&mut self) -> usize {
+        let count = self.paths().len();
+        self.paths_mut().retain(|path| !path.data().is_degenerate());
+        count - self.paths().len()
     }
 
     fn push_path(&mut self, path: impl Into<P>) {
         self.paths_mut().push(path.into());
     }
 
+    /// Sort the paths such as to minimize the pen-up distance.
+    ///
+    /// This is done using a greedy, nearest-neighbour algorithm starting from the origin. If
+    /// `flip` is `true`, paths may be reversed. Ties are broken by original order, so the result
+    /// is deterministic. Any path that cannot be spatially indexed (e.g. empty) is moved at the
+    /// end.
     fn sort(&mut self, flip: bool) {
         self.sort_with_builder(IndexBuilder::default().flip(flip));
     }
 
+    /// Sort the paths for plotting, and return the pen-up length before and after sorting.
+    ///
+    /// Paths are sorted with [`LayerTrait::sort`] and reversed where it shortens the travel. The
+    /// original order is kept if sorting doesn't reduce the pen-up length, so the second value is
+    /// never larger than the first. See [`LayerTrait::pen_up_length`].
+    fn sort_for_plotting(&mut self) -> (f64, f64) {
+        let before = self.pen_up_length();
+        let original = self.paths().to_vec();
+        self.sort(true);
+
+        let after = self.pen_up_length();
+        if after > before {
+            *self.paths_mut() = original;
+            (before, before)
+        } else {
+            (before, after)
+        }
+    }
+
     fn sort_with_builder(&mut self, builder: IndexBuilder) {
         if self.paths().len() <= 1 {
             return;
@@ -72,6 +188,93 @@ pub trait LayerTrait<P: PathTrait<D>, D: PathDataTrait>: Default + Transforms {
         *self.paths_mut() = new_paths;
     }
 
+    /// Merge paths whose end points are within `tolerance` of each other.
+    ///
+    /// Paths are reversed as needed to be chained. Only paths with identical metadata (color,
+    /// stroke width and attributes) are merged together.
+    fn merge_paths(&mut self, tolerance: f64) {
+        self.merge_paths_by(tolerance, |a, b| a == b);
+    }
+
+    /// Merge paths whose end points are within `tolerance` of each other, if they share the same
+    /// color and stroke width.
+    ///
+    /// Unlike [`LayerTrait::merge_paths`], other attributes are ignored, and the merged path
+    /// keeps the metadata of its first path.
+    fn merge_by_color(&mut self, tolerance: f64) {
+        #[allow(clippy::float_cmp)]
+        self.merge_paths_by(tolerance, |a, b| {
+            a.color == b.color && a.stroke_width == b.stroke_width
+        });
+    }
+
+    /// Merge paths whose end points are within `tolerance` of each other, if `same` returns
+    /// `true` for their metadata.
+    ///
+    /// Paths are grouped with the first path for which `same` holds, in order of appearance,
+    /// and each group is merged independently.
+    fn merge_paths_by<F>(&mut self, tolerance: f64, same: F)
+    where
+        F: Fn(&PathMetadata, &PathMetadata) -> bool,
+    {
+        if self.paths().len() <= 1 {
+            return;
+        }
+
+        // group paths by metadata, in order of first appearance
+        let mut groups: Vec<Vec<P>> = vec![];
+        for path in self.paths_mut().drain(..) {
+            match groups
+                .iter_mut()
+                .find(|group| same(group[0].metadata(), path.metadata()))
+            {
+                Some(group) => group.push(path),
+                None => groups.push(vec![path]),
+            }
+        }
+
+        let mut new_paths = Vec::new();
+        for group in &groups {
+            let mut index = IndexBuilder::default()
+                .flip(true)
+                .strict_order(true)
+                .build(group);
+
+            while let Some(path_item) = index.pop_first() {
+                let mut path = path_item.path.clone();
+
+                // extend forward from the end, then backward from the start
+                for _ in 0..2 {
+                    while let Some(end) = path.end() {
+                        let Some((path_item, reverse)) = index.pop_nearest_within(&end, tolerance)
+                        else {
+                            break;
+                        };
+
+                        if reverse {
+                            path.data_mut().join(&path_item.path.reversed().into_data());
+                        } else {
+                            path.data_mut().join(path_item.path.data());
+                        }
+                    }
+                    path.reverse();
+                }
+
+                new_paths.push(path);
+            }
+        }
+
+        *self.paths_mut() = new_paths;
+    }
+
+    /// Total length of the pen-up trajectories between consecutive paths.
+    fn pen_up_length(&self) -> f64 {
+        self.pen_up_trajectories()
+            .iter()
+            .map(|(start, end)| start.distance(end))
+            .sum()
+    }
+
     fn pen_up_trajectories(&self) -> Vec<(Point, Point)> {
         self.paths()
             .windows(2)
@@ -6,34 +6,62 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::missing_errors_doc)]
 
+#[cfg(feature = "geo")]
+mod boolean;
 mod color;
 mod crop;
+mod dedup;
 mod document;
+mod export;
+pub mod geom;
+mod hatch;
+mod hull;
+mod intersections;
 mod layer;
+mod noise;
+mod offset;
 mod optimization;
 mod page_size;
 mod path;
 mod path_index;
+#[cfg(feature = "tiny-skia")]
+mod raster;
+mod resample;
+mod simplify;
+mod smooth;
 mod stats;
+#[cfg(feature = "geo")]
+mod stroke;
 mod svg;
 mod test_utils;
 mod traits;
 mod unit;
 
 pub use crate::svg::*;
+#[cfg(feature = "geo")]
+pub use boolean::BoolOp;
 pub use color::*;
 
 pub use crop::*;
 pub use document::*;
+pub use export::*;
 pub use layer::*;
+pub use offset::OffsetJoin;
+#[allow(unused_imports)] // the module currently only extends existing types
 pub use optimization::*;
 pub use page_size::*;
 pub use path::*;
 pub use path_index::*;
+#[cfg(feature = "tiny-skia")]
+pub use raster::*;
 pub use stats::*;
+#[cfg(feature = "geo")]
+pub use stroke::{CapStyle, JoinStyle};
 pub use traits::*;
 pub use unit::*;
 
 // re-export
 #[cfg(feature = "geo")]
 pub use ::geo;
+#[cfg(feature = "tiny-skia")]
+pub use ::tiny_skia;
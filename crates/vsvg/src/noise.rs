@@ -0,0 +1,82 @@
+//! Seeded pseudo-random displacements, used by the jitter and wobble operations.
+//!
+//! Everything here is a pure function of its inputs, so that results are reproducible across
+//! runs and platforms.
+
+/// Mix `value` into `seed` with the `SplitMix64` finalizer.
+pub(crate) fn hash(seed: u64, value: u64) -> u64 {
+    let mut z = seed ^ value.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Pseudo-random value in `[-1, 1)` for `index`.
+#[allow(clippy::cast_precision_loss)]
+fn random(seed: u64, index: u64) -> f64 {
+    // keep the 53 most significant bits, which an f64 represents exactly
+    (hash(seed, index) >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// One-dimensional value noise in `[-1, 1]`, with random values at integer coordinates and
+/// smooth interpolation in between.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn value_noise(seed: u64, x: f64) -> f64 {
+    let cell = x.floor();
+    let t = x - cell;
+    let t = t * t * (3.0 - 2.0 * t);
+
+    // wrapping conversion, such that negative cells get their own values
+    let index = cell as i64 as u64;
+    let (a, b) = (random(seed, index), random(seed, index.wrapping_add(1)));
+    a + (b - a) * t
+}
+
+/// Random offset of up to `amplitude` along each axis, for the point with `index`.
+pub(crate) fn jitter_offset(seed: u64, index: u64, amplitude: f64) -> (f64, f64) {
+    (
+        amplitude * random(hash(seed, 0), index),
+        amplitude * random(hash(seed, 1), index),
+    )
+}
+
+/// Smooth offset of up to `amplitude` along each axis, for the point at distance `s` along the
+/// path.
+///
+/// The offset varies continuously with `s`, with features about `wavelength` apart.
+pub(crate) fn wobble_offset(seed: u64, s: f64, amplitude: f64, wavelength: f64) -> (f64, f64) {
+    let x = s / wavelength;
+    (
+        amplitude * value_noise(hash(seed, 0), x),
+        amplitude * value_noise(hash(seed, 1), x),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_range() {
+        let values: Vec<_> = (0..1000).map(|i| random(42, i)).collect();
+        assert!(values.iter().all(|v| (-1.0..1.0).contains(v)));
+        assert!(values.iter().any(|v| *v < -0.9));
+        assert!(values.iter().any(|v| *v > 0.9));
+
+        // different seeds give different sequences
+        assert_ne!(random(1, 0).to_bits(), random(2, 0).to_bits());
+    }
+
+    #[test]
+    fn test_value_noise_continuous() {
+        for i in -100..100 {
+            let x = f64::from(i) * 0.137;
+            let (a, b) = (value_noise(7, x), value_noise(7, x + 1e-4));
+            assert!((a - b).abs() < 1e-3);
+            assert!((-1.0..=1.0).contains(&a));
+        }
+
+        // interpolates the lattice random values
+        assert_eq!(value_noise(7, 3.0).to_bits(), random(7, 3).to_bits());
+    }
+}
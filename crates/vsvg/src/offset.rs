@@ -0,0 +1,349 @@
+//! Offsetting of polylines.
+
+use crate::intersections::{find_cuts, split_polyline};
+use kurbo::{Point, Vec2};
+use std::f64::consts::{FRAC_PI_4, PI};
+
+/// Join style for the outer corners of an offset path.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OffsetJoin {
+    /// Corners are rounded with an arc centered on the original vertex.
+    #[default]
+    Round,
+
+    /// Corners are extended to a sharp point, unless the miter length exceeds `limit` times the
+    /// offset distance, in which case the corner is beveled.
+    Miter { limit: f64 },
+}
+
+/// Left normal of a direction, as seen on screen with the Y axis pointing down.
+fn normal(dir: Vec2) -> Vec2 {
+    Vec2::new(dir.y, -dir.x)
+}
+
+/// Distance from `pt` to the segment `ab`.
+fn segment_distance(pt: Point, a: Point, b: Point) -> f64 {
+    let ab = b - a;
+    let len2 = ab.hypot2();
+    let t = if len2 > 0.0 {
+        ((pt - a).dot(ab) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    pt.distance(a + ab * t)
+}
+
+/// Segments of a polyline, including the closing one if `closed`.
+fn segments(points: &[Point], closed: bool) -> impl Iterator<Item = (Point, Point)> + '_ {
+    let closing = closed.then(|| (points[points.len() - 1], points[0]));
+    points.windows(2).map(|w| (w[0], w[1])).chain(closing)
+}
+
+/// Offset a polyline by `distance`, positive to the left of the direction of travel as seen on
+/// screen (Y axis pointing down).
+///
+/// Closed polylines (first point repeated at the end) are offset all around. Open polylines are
+/// offset without end caps. The raw offset curve is split at its self-intersections and the
+/// portions closer than `distance` to the original polyline are discarded, so the result may
+/// contain several polylines, each with a flag indicating whether it is closed. Round joins are
+/// approximated within `tolerance`.
+pub(crate) fn offset_polyline(
+    points: &[crate::Point],
+    distance: f64,
+    join: OffsetJoin,
+    tolerance: f64,
+) -> Vec<(Vec<crate::Point>, bool)> {
+    let mut points: Vec<Point> = points.iter().map(Into::into).collect();
+    points.dedup();
+    let closed = points.len() > 3 && points.first() == points.last();
+    if closed {
+        points.pop();
+    }
+    if points.len() < 2 || distance == 0.0 || !distance.is_finite() {
+        return vec![];
+    }
+
+    // angular step of round joins, and the corresponding maximum deviation from the arc
+    let abs_distance = distance.abs();
+    let step = if tolerance > 0.0 && tolerance < abs_distance {
+        (2.0 * (1.0 - tolerance / abs_distance).acos()).min(FRAC_PI_4)
+    } else {
+        FRAC_PI_4
+    };
+    let sag = abs_distance * (1.0 - (step / 2.0).cos());
+
+    let raw = raw_offset(&points, closed, distance, join, step);
+    let pieces = split_at_intersections(&raw, closed);
+
+    // discard the portions which come too close to the original polyline
+    let threshold = abs_distance - sag - abs_distance * 1e-6;
+    let valid: Vec<Vec<Point>> = pieces
+        .into_iter()
+        .filter(|piece| {
+            let Some((a, b)) = piece
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+                .max_by(|(a, b), (c, d)| a.distance(*b).total_cmp(&c.distance(*d)))
+            else {
+                return false;
+            };
+            let mid = a.midpoint(b);
+            segments(&points, closed).all(|(p, q)| segment_distance(mid, p, q) >= threshold)
+        })
+        .collect();
+
+    chain(valid)
+        .into_iter()
+        .map(|(pts, closed)| (pts.into_iter().map(Into::into).collect(), closed))
+        .collect()
+}
+
+/// Offset each segment and connect them with joins, without removing self-intersections.
+///
+/// For closed polylines, the first point is not repeated.
+fn raw_offset(
+    points: &[Point],
+    closed: bool,
+    distance: f64,
+    join: OffsetJoin,
+    step: f64,
+) -> Vec<Point> {
+    let dirs: Vec<Vec2> = segments(points, closed)
+        .map(|(a, b)| (b - a).normalize())
+        .collect();
+    let n = dirs.len();
+    let mut raw = vec![];
+
+    let add_join = |raw: &mut Vec<Point>, vertex: Point, prev: Vec2, next: Vec2| {
+        let (n_prev, n_next) = (normal(prev), normal(next));
+        let (cross, dot) = (prev.cross(next), prev.dot(next));
+        let (o_prev, o_next) = (n_prev * distance, n_next * distance);
+        raw.push(vertex + o_prev);
+
+        if cross.abs() < 1e-9 && dot > 0.0 {
+            // collinear, nothing to join
+        } else if cross * distance > 0.0 || cross.abs() < 1e-9 {
+            // outer corner (or U-turn)
+            match join {
+                OffsetJoin::Round => {
+                    let mut sweep = o_prev.cross(o_next).atan2(o_prev.dot(o_next));
+                    if cross.abs() < 1e-9 {
+                        // U-turn: go around the front of the vertex
+                        sweep = if Vec2::new(-o_prev.y, o_prev.x).dot(prev) > 0.0 {
+                            PI
+                        } else {
+                            -PI
+                        };
+                    }
+                    let start = o_prev.atan2();
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let count = (sweep.abs() / step).ceil() as usize;
+                    #[allow(clippy::cast_precision_loss)]
+                    for i in 1..count {
+                        let angle = start + sweep * i as f64 / count as f64;
+                        raw.push(vertex + Vec2::from_angle(angle) * distance.abs());
+                    }
+                }
+                OffsetJoin::Miter { limit } => {
+                    let ratio = (2.0 / (1.0 + n_prev.dot(n_next))).sqrt();
+                    if ratio.is_finite() && ratio <= limit {
+                        let bisector = (n_prev + n_next) / (1.0 + n_prev.dot(n_next));
+                        raw.push(vertex + bisector * distance);
+                    }
+                }
+            }
+        } else {
+            // inner corner: going through the vertex makes the resulting loop easy to discard
+            raw.push(vertex);
+        }
+
+        raw.push(vertex + o_next);
+    };
+
+    if closed {
+        for i in 0..n {
+            add_join(&mut raw, points[i], dirs[(i + n - 1) % n], dirs[i]);
+        }
+    } else {
+        raw.push(points[0] + normal(dirs[0]) * distance);
+        for i in 1..n {
+            add_join(&mut raw, points[i], dirs[i - 1], dirs[i]);
+        }
+        raw.push(points[n] + normal(dirs[n - 1]) * distance);
+    }
+
+    raw.dedup();
+    raw
+}
+
+/// Split a polyline into pieces at its self-intersections, see [`find_cuts`].
+///
+/// Pieces meeting at an intersection share the exact same end point.
+fn split_at_intersections(raw: &[Point], closed: bool) -> Vec<Vec<Point>> {
+    let mut line = raw.to_vec();
+    if closed {
+        line.push(raw[0]);
+    }
+
+    let cuts = find_cuts(std::slice::from_ref(&line));
+    split_polyline(&line, &cuts)
+}
+
+/// Join pieces which end exactly where another starts, and flag the closed ones.
+fn chain(mut pieces: Vec<Vec<Point>>) -> Vec<(Vec<Point>, bool)> {
+    let mut result = vec![];
+    while !pieces.is_empty() {
+        let mut current = pieces.swap_remove(0);
+        while current.first() != current.last() {
+            let end = current[current.len() - 1];
+            let Some(next) = pieces.iter().position(|piece| piece[0] == end) else {
+                break;
+            };
+            current.extend_from_slice(&pieces.swap_remove(next)[1..]);
+        }
+
+        let closed = current.len() > 2 && current.first() == current.last();
+        result.push((current, closed));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn square() -> Vec<crate::Point> {
+        // clockwise on screen
+        [(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]
+            .map(crate::Point::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn test_offset_open_line() {
+        let line = [(0., 0.), (10., 0.)].map(crate::Point::from);
+        let result = offset_polyline(&line, 2.0, OffsetJoin::Round, 0.1);
+        assert_eq!(
+            result,
+            vec![(vec![(0., -2.).into(), (10., -2.).into()], false)]
+        );
+
+        let result = offset_polyline(&line, -2.0, OffsetJoin::Round, 0.1);
+        assert_eq!(
+            result,
+            vec![(vec![(0., 2.).into(), (10., 2.).into()], false)]
+        );
+    }
+
+    #[test]
+    fn test_offset_square_miter() {
+        let miter = OffsetJoin::Miter { limit: 2.0 };
+
+        // outward with sharp corners
+        let result = offset_polyline(&square(), 2.0, miter, 0.1);
+        assert_eq!(result.len(), 1);
+        let (points, closed) = &result[0];
+        assert!(closed);
+        for corner in [(-2., -2.), (12., -2.), (12., 12.), (-2., 12.)] {
+            assert!(points.iter().any(|pt| pt.distance(&corner.into()) < 1e-9));
+        }
+        for pt in points {
+            assert_abs_diff_eq!(
+                (pt.x() - 5.).abs().max((pt.y() - 5.).abs()),
+                7.0,
+                epsilon = 1e-9
+            );
+        }
+
+        // inward, the inner corner loops are removed
+        let result = offset_polyline(&square(), -2.0, miter, 0.1);
+        assert_eq!(result.len(), 1);
+        let (points, closed) = &result[0];
+        assert!(closed);
+        for pt in points {
+            assert_abs_diff_eq!((pt.x() - 5.).abs(), 3.0, epsilon = 1e-9);
+            assert_abs_diff_eq!((pt.y() - 5.).abs(), 3.0, epsilon = 1e-9);
+        }
+
+        // the shape collapses when inset too much
+        assert!(offset_polyline(&square(), -6.0, miter, 0.1).is_empty());
+
+        // beyond the miter limit, corners are beveled
+        let result = offset_polyline(&square(), 2.0, OffsetJoin::Miter { limit: 1.2 }, 0.1);
+        assert_eq!(result[0].0.len(), 9);
+    }
+
+    #[test]
+    fn test_offset_split() {
+        // two squares connected by a narrow bridge, which vanishes when inset
+        let dumbbell = [
+            (0., 0.),
+            (10., 0.),
+            (10., 4.),
+            (20., 4.),
+            (20., 0.),
+            (30., 0.),
+            (30., 10.),
+            (20., 10.),
+            (20., 6.),
+            (10., 6.),
+            (10., 10.),
+            (0., 10.),
+            (0., 0.),
+        ]
+        .map(crate::Point::from);
+
+        let result = offset_polyline(&dumbbell, -2.0, OffsetJoin::Round, 0.1);
+        assert_eq!(result.len(), 2);
+        for (points, closed) in &result {
+            assert!(closed);
+            let width = points.iter().map(crate::Point::x).fold(f64::MIN, f64::max)
+                - points.iter().map(crate::Point::x).fold(f64::MAX, f64::min);
+            // the round joins around the bridge's corners meet half-way
+            assert_abs_diff_eq!(width, 8.0 - 3.0_f64.sqrt(), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_offset_square_round() {
+        let result = offset_polyline(&square(), 2.0, OffsetJoin::Round, 0.01);
+        assert_eq!(result.len(), 1);
+        let (points, closed) = &result[0];
+        assert!(closed);
+        assert!(points.len() > 20);
+
+        // every point is at the offset distance from the square
+        for pt in points {
+            let dx = (pt.x() - 5.).abs() - 5.;
+            let dy = (pt.y() - 5.).abs() - 5.;
+            let dist = Vec2::new(dx.max(0.), dy.max(0.)).hypot();
+            assert_abs_diff_eq!(dist, 2.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_offset_u_turn() {
+        // going back and forth gives a round cap at the far end, on both sides
+        let back_and_forth = [(0., 0.), (10., 0.), (0., 0.)].map(crate::Point::from);
+        for distance in [2.0, -2.0] {
+            let result = offset_polyline(&back_and_forth, distance, OffsetJoin::Round, 0.1);
+            assert_eq!(result.len(), 1);
+            let (points, closed) = &result[0];
+            assert!(!closed);
+            assert_eq!(points[0], (0., -distance).into());
+            assert_eq!(points[points.len() - 1], (0., distance).into());
+            let max_x = points.iter().map(crate::Point::x).fold(0., f64::max);
+            assert_abs_diff_eq!(max_x, 12.0, epsilon = 0.1);
+        }
+
+        // a narrow hairpin collapses on the inside
+        let hairpin = [(0., 0.), (10., 0.), (10., 1.), (0., 1.)].map(crate::Point::from);
+        let result = offset_polyline(&hairpin, 2.0, OffsetJoin::Round, 0.1);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0[0], (0., -2.).into());
+        assert_eq!(result[0].0[result[0].0.len() - 1], (0., 3.).into());
+        assert!(offset_polyline(&hairpin, -2.0, OffsetJoin::Round, 0.1).is_empty());
+    }
+}
@@ -155,6 +155,34 @@ impl PageSize {
         }
     }
 
+    /// Returns `true` if the page is wider than it is tall.
+    #[must_use]
+    pub fn is_landscape(&self) -> bool {
+        self.w() > self.h()
+    }
+
+    /// Return the landscape version of this page size, i.e. with its larger dimension along the
+    /// X axis.
+    #[must_use]
+    pub fn landscape(self) -> Self {
+        if self.w() < self.h() {
+            self.flip()
+        } else {
+            self
+        }
+    }
+
+    /// Return the portrait version of this page size, i.e. with its larger dimension along the
+    /// Y axis.
+    #[must_use]
+    pub fn portrait(self) -> Self {
+        if self.is_landscape() {
+            self.flip()
+        } else {
+            self
+        }
+    }
+
     #[must_use]
     pub fn to_pixels(&self) -> (f64, f64) {
         match self {
@@ -198,6 +226,10 @@ impl PageSize {
         self.to_pixels().1
     }
 
+    /// Parse a page size from its name (e.g. `"A4"`, `"letter (h)"`) or from a `WxH[unit]`
+    /// specification (e.g. `"11x8.5in"`, `"300x200mm"`).
+    ///
+    /// If the unit is omitted, pixels are assumed.
     #[must_use]
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
@@ -223,10 +255,30 @@ impl PageSize {
             "legal (h)" => Some(Self::LegalH),
             "executive (h)" => Some(Self::ExecutiveH),
             "tabloid (h)" => Some(Self::TabloidH),
-            _ => None, //TODO: implement WWxHHunit
+            other => Self::parse_custom(other),
         }
     }
 
+    fn parse_custom(s: &str) -> Option<Self> {
+        let (w, rest) = s.split_once('x')?;
+        let unit_idx = rest
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (h, unit) = rest.split_at(unit_idx);
+
+        let unit = if unit.is_empty() {
+            Unit::Px
+        } else {
+            Unit::from(unit.trim())?
+        };
+
+        Some(Self::Custom(
+            w.trim().parse().ok()?,
+            h.trim().parse().ok()?,
+            unit,
+        ))
+    }
+
     #[must_use]
     pub fn to_format(&self) -> Option<&'static str> {
         match self {
@@ -257,6 +309,43 @@ impl PageSize {
     }
 }
 
+/// Margins around the drawable area of a page, in pixels.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Margins {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl Margins {
+    #[must_use]
+    pub const fn new(top: f64, right: f64, bottom: f64, left: f64) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create margins with the same value on all sides.
+    #[must_use]
+    pub const fn uniform(margin: f64) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+
+    /// Compute the rectangle remaining inside the margins for a given page size.
+    ///
+    /// Returns `None` if the margins leave no room on the page.
+    #[must_use]
+    pub fn inner_rect(&self, page_size: PageSize) -> Option<kurbo::Rect> {
+        let (w, h) = page_size.to_pixels();
+        let rect = kurbo::Rect::new(self.left, self.top, w - self.right, h - self.bottom);
+        (rect.x0 < rect.x1 && rect.y0 < rect.y1).then_some(rect)
+    }
+}
+
 impl From<PageSize> for (f64, f64) {
     fn from(page_size: PageSize) -> Self {
         page_size.to_pixels()
@@ -299,6 +388,7 @@ impl fmt::Display for PageSize {
 mod test {
 
     use super::*;
+    use approx::assert_abs_diff_eq;
 
     #[test]
     fn test_page_size_display() {
@@ -324,8 +414,79 @@ mod test {
         assert_eq!(PageSize::parse("Executive"), Some(PageSize::ExecutiveV));
         assert_eq!(PageSize::parse("Tabloid"), Some(PageSize::TabloidV));
 
-        //TODO: this should work
-        assert_eq!(PageSize::parse("100x200px"), None);
+        assert_eq!(
+            PageSize::parse("100x200px"),
+            Some(PageSize::Custom(100.0, 200.0, Unit::Px))
+        );
+        assert_eq!(
+            PageSize::parse("11x8.5in"),
+            Some(PageSize::Custom(11.0, 8.5, Unit::In))
+        );
+        assert_eq!(
+            PageSize::parse("300 x 200 mm"),
+            Some(PageSize::Custom(300.0, 200.0, Unit::Mm))
+        );
+        assert_eq!(
+            PageSize::parse("640x480"),
+            Some(PageSize::Custom(640.0, 480.0, Unit::Px))
+        );
+        assert_eq!(PageSize::parse("100x200parsec"), None);
+        assert_eq!(PageSize::parse("100xpx"), None);
+        assert_eq!(PageSize::parse("A7"), None);
+    }
+
+    #[test]
+    fn test_page_size_orientation() {
+        assert!(!PageSize::A4V.is_landscape());
+        assert!(PageSize::A4H.is_landscape());
+        assert_eq!(PageSize::A4V.landscape(), PageSize::A4H);
+        assert_eq!(PageSize::A4H.landscape(), PageSize::A4H);
+        assert_eq!(PageSize::A4H.portrait(), PageSize::A4V);
+        assert_eq!(PageSize::A4V.portrait(), PageSize::A4V);
+
+        let custom = PageSize::custom(10.0, 20.0, Unit::Cm);
+        assert!(!custom.is_landscape());
+        assert_eq!(custom.landscape(), PageSize::custom(20.0, 10.0, Unit::Cm));
+        assert_eq!(custom.portrait(), custom);
+
+        let square = PageSize::custom(10.0, 10.0, Unit::Cm);
+        assert_eq!(square.landscape(), square);
+        assert_eq!(square.portrait(), square);
+
+        for page_size in PAGE_SIZES {
+            assert!(page_size.landscape().is_landscape());
+            assert!(!page_size.portrait().is_landscape());
+        }
+    }
+
+    #[test]
+    fn test_margins_inner_rect() {
+        let page_size = PageSize::custom(100.0, 50.0, Unit::Px);
+        assert_eq!(
+            Margins::new(1.0, 2.0, 3.0, 4.0).inner_rect(page_size),
+            Some(kurbo::Rect::new(4.0, 1.0, 98.0, 47.0))
+        );
+        assert_eq!(
+            Margins::default().inner_rect(page_size),
+            Some(kurbo::Rect::new(0.0, 0.0, 100.0, 50.0))
+        );
+        assert_eq!(Margins::uniform(25.0).inner_rect(page_size), None);
+    }
+
+    #[test]
+    fn test_page_size_dimensions() {
+        let (w, h) = PageSize::A4V.to_pixels();
+        assert_abs_diff_eq!(w / Unit::Mm, 210.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(h / Unit::Mm, 297.0, epsilon = 1e-9);
+
+        // A3's long side is twice A4's short side
+        assert_abs_diff_eq!(PageSize::A3V.h(), 2.0 * PageSize::A4V.w(), epsilon = 1e-9);
+        assert_abs_diff_eq!(PageSize::A3V.w(), PageSize::A4V.h(), epsilon = 1e-9);
+
+        for page_size in PAGE_SIZES {
+            assert_abs_diff_eq!(page_size.w(), page_size.flip().h());
+            assert_eq!(PageSize::parse(&page_size.to_string()), Some(page_size));
+        }
     }
 
     #[test]
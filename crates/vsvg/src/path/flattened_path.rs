@@ -1,4 +1,8 @@
-use super::{PathDataTrait, PathMetadata, Point};
+use super::{quantize, PathDataTrait, PathMetadata, Point};
+use crate::noise::{jitter_offset, wobble_offset};
+use crate::resample::resample_segments;
+use crate::simplify::{douglas_peucker, visvalingam_whyatt};
+use crate::smooth::chaikin;
 use crate::{PathTrait, Transforms};
 use kurbo::Affine;
 
@@ -62,17 +66,14 @@ impl Transforms for Polyline {
 }
 
 impl PathDataTrait for Polyline {
-    fn bounds(&self) -> kurbo::Rect {
-        assert!(
-            !self.0.is_empty(),
-            "Cannot compute bounds of empty polyline"
-        );
-
-        let rect = kurbo::Rect::from_center_size(self.points()[0], (0.0, 0.0));
-        self.points()
-            .iter()
-            .skip(1)
-            .fold(rect, |acc, point| acc.union_pt(point.into()))
+    fn bounds(&self) -> Option<kurbo::Rect> {
+        let (first, others) = self.points().split_first()?;
+        let rect = kurbo::Rect::from_center_size(*first, (0.0, 0.0));
+        Some(
+            others
+                .iter()
+                .fold(rect, |acc, point| acc.union_pt(point.into())),
+        )
     }
 
     fn start(&self) -> Option<Point> {
@@ -87,9 +88,105 @@ impl PathDataTrait for Polyline {
         self.0.len() == 1
     }
 
+    fn is_degenerate(&self) -> bool {
+        self.0.len() < 2
+    }
+
     fn flip(&mut self) {
         self.0.reverse();
     }
+
+    fn length(&self) -> f64 {
+        self.0.windows(2).map(|w| w[0].distance(&w[1])).sum()
+    }
+
+    fn resample(&mut self, spacing: f64) {
+        if spacing > 0.0 {
+            let segments: Vec<_> = self
+                .0
+                .windows(2)
+                .map(|w| kurbo::PathSeg::Line(kurbo::Line::new(w[0], w[1])))
+                .collect();
+            if !segments.is_empty() {
+                self.0 = resample_segments(&segments, spacing);
+            }
+        }
+    }
+
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        let closed = self.0.len() > 3 && self.0.first() == self.0.last();
+        if closed {
+            self.0.pop();
+            self.0 = chaikin(&self.0, iterations, true);
+            self.0.push(self.0[0]);
+        } else {
+            self.0 = chaikin(&self.0, iterations, false);
+        }
+    }
+
+    fn dedup_points(&mut self, epsilon: f64) {
+        self.0.dedup_by(|pt, prev| pt.distance(prev) <= epsilon);
+    }
+
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        let closed = self.0.len() > 1 && self.0.first() == self.0.last();
+        for (i, pt) in (0..).zip(&mut self.0) {
+            let (dx, dy) = jitter_offset(seed, i, amplitude);
+            *pt = Point::new(pt.x() + dx, pt.y() + dy);
+        }
+
+        // keep closed polylines closed
+        if closed {
+            let last = self.0.len() - 1;
+            self.0[last] = self.0[0];
+        }
+    }
+
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        if wavelength <= 0.0 {
+            return;
+        }
+
+        let closed = self.0.len() > 1 && self.0.first() == self.0.last();
+        let mut s = 0.0;
+        let mut prev = self.0.first().copied();
+        for pt in &mut self.0 {
+            if let Some(prev) = prev {
+                s += prev.distance(pt);
+            }
+            prev = Some(*pt);
+
+            let (dx, dy) = wobble_offset(seed, s, amplitude, wavelength);
+            *pt = Point::new(pt.x() + dx, pt.y() + dy);
+        }
+
+        // keep closed polylines closed
+        if closed {
+            let last = self.0.len() - 1;
+            self.0[last] = self.0[0];
+        }
+    }
+
+    fn quantize(&mut self, step: f64) {
+        if step > 0.0 {
+            for pt in &mut self.0 {
+                *pt = Point::new(quantize(pt.x(), step), quantize(pt.y(), step));
+            }
+        }
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        self.0 = douglas_peucker(&self.0, tolerance);
+    }
+
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        self.0 = visvalingam_whyatt(&self.0, area_threshold);
+    }
+
+    fn join(&mut self, other: &Self) {
+        let skip = usize::from(self.0.last().is_some() && self.0.last() == other.0.first());
+        self.0.extend_from_slice(&other.0[skip..]);
+    }
 }
 
 // ======================================================================================
@@ -119,10 +216,6 @@ impl PathTrait<Polyline> for FlattenedPath {
         self.data
     }
 
-    fn bounds(&self) -> kurbo::Rect {
-        self.data.bounds()
-    }
-
     fn metadata(&self) -> &PathMetadata {
         &self.metadata
     }
@@ -150,6 +243,103 @@ impl From<Vec<Point>> for FlattenedPath {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_polyline_smooth_chaikin() {
+        let mut square: Polyline = [(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]
+            .into_iter()
+            .collect();
+        square.smooth_chaikin(1);
+        assert_eq!(square.points().len(), 9);
+        assert_eq!(square.points().first(), square.points().last());
+        assert_eq!(square.points()[0], Point::new(1., 0.));
+
+        let mut open: Polyline = [(0., 0.), (4., 0.), (4., 4.)].into_iter().collect();
+        open.smooth_chaikin(2);
+        assert_eq!(open.points().len(), 6);
+        assert_eq!(open.start(), Some(Point::new(0., 0.)));
+        assert_eq!(open.end(), Some(Point::new(4., 4.)));
+    }
+
+    #[test]
+    fn test_polyline_dedup_points() {
+        let mut polyline: Polyline = [(0., 0.), (0., 0.), (0., 0.), (1., 0.), (1., 0.01), (2., 0.)]
+            .into_iter()
+            .collect();
+        polyline.dedup_points(0.1);
+        assert_eq!(
+            polyline.points(),
+            &[(0., 0.).into(), (1., 0.).into(), (2., 0.).into()]
+        );
+
+        let mut polyline: Polyline = [(3., 3.), (3., 3.), (3., 3.)].into_iter().collect();
+        polyline.dedup_points(0.0);
+        assert_eq!(polyline.points(), &[(3., 3.).into()]);
+    }
+
+    #[test]
+    fn test_polyline_quantize() {
+        let mut polyline: Polyline = [(0.26, 0.74), (0.49, 1.01), (-0.3, 10.2)]
+            .into_iter()
+            .collect();
+        polyline.quantize(0.5);
+        assert_eq!(
+            polyline.points(),
+            &[(0.5, 0.5).into(), (0.5, 1.0).into(), (-0.5, 10.).into()]
+        );
+
+        polyline.quantize(0.0);
+        assert_eq!(polyline.points().len(), 3);
+    }
+
+    #[test]
+    fn test_polyline_jitter_wobble() {
+        let original: Polyline = (0..50).map(|i| (f64::from(i), 0.0)).collect();
+
+        let mut jittered = original.clone();
+        jittered.jitter(1.0, 7);
+        assert!(jittered
+            .points()
+            .iter()
+            .zip(original.points())
+            .all(|(p, o)| (p.x() - o.x()).abs() <= 1.0 && (p.y() - o.y()).abs() <= 1.0));
+        let mut again = original.clone();
+        again.jitter(1.0, 7);
+        assert_eq!(again, jittered);
+
+        let mut wobbled = original.clone();
+        wobbled.wobble(1.0, 10.0, 7);
+        assert_ne!(wobbled, original);
+        assert!(wobbled
+            .points()
+            .windows(2)
+            .all(|w| (w[1].y() - w[0].y()).abs() < 0.5));
+
+        // closed polylines stay closed
+        let square: Polyline = [(0., 0.), (10., 0.), (10., 10.), (0., 0.)]
+            .into_iter()
+            .collect();
+        let mut jittered = square.clone();
+        jittered.jitter(1.0, 7);
+        assert_eq!(jittered.points().first(), jittered.points().last());
+        let mut wobbled = square;
+        wobbled.wobble(1.0, 10.0, 7);
+        assert_eq!(wobbled.points().first(), wobbled.points().last());
+        assert_ne!(wobbled.points()[0], Point::new(0., 0.));
+    }
+
+    #[test]
+    fn test_polyline_resample() {
+        let mut polyline = Polyline::new(vec![Point::new(0., 0.), Point::new(10., 0.)]);
+        polyline.resample(2.0);
+        assert_eq!(polyline.points().len(), 6);
+        assert_abs_diff_eq!(polyline.points()[3].x(), 6.0, epsilon = 1e-9);
+
+        let mut single = Polyline::new(vec![Point::new(1., 1.)]);
+        single.resample(2.0);
+        assert_eq!(single.points(), &[Point::new(1., 1.)]);
+    }
 
     #[test]
     fn test_flattened_path_bounds() {
@@ -161,16 +351,15 @@ mod tests {
         let path = FlattenedPath::from(points);
         assert_eq!(
             path.bounds(),
-            kurbo::Rect::from_points((0.0, 0.0), (10.0, 2.0))
+            Some(kurbo::Rect::from_points((0.0, 0.0), (10.0, 2.0)))
         );
     }
 
     #[test]
-    #[should_panic]
     fn test_flattened_path_bounds_empty() {
         let points = Polyline::default();
         let path = FlattenedPath::from(points);
-        path.bounds();
+        assert_eq!(path.bounds(), None);
     }
 
     #[test]
@@ -179,8 +368,58 @@ mod tests {
         let path = FlattenedPath::from(points);
         assert_eq!(
             path.bounds(),
-            kurbo::Rect::from_points((10.0, 0.0), (100.0, 13.0))
+            Some(kurbo::Rect::from_points((10.0, 0.0), (100.0, 13.0)))
+        );
+    }
+
+    #[test]
+    fn test_polyline_length() {
+        let square = Polyline::from_iter([(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+        assert_abs_diff_eq!(square.length(), 4.0);
+
+        let open = FlattenedPath::from(vec![Point::new(0., 0.), Point::new(3., 4.)]);
+        assert_abs_diff_eq!(open.length(), 5.0);
+
+        assert_abs_diff_eq!(Polyline::default().length(), 0.0);
+        assert_abs_diff_eq!(Polyline::from_iter([(1., 1.)]).length(), 0.0);
+    }
+
+    #[test]
+    fn test_flattened_path_reverse() {
+        let mut path = FlattenedPath::from(vec![
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+        ]);
+        path.metadata_mut().color = crate::Color::RED;
+        path.metadata_mut().stroke_width = 3.0;
+
+        let reversed = path.reversed();
+        assert_eq!(
+            reversed.data.points(),
+            &[Point::new(1., 1.), Point::new(1., 0.), Point::new(0., 0.)]
+        );
+        assert_eq!(reversed.metadata(), path.metadata());
+
+        path.reverse();
+        assert_eq!(path, reversed);
+    }
+
+    #[test]
+    fn test_polyline_join() {
+        let mut p = Polyline::from_iter([(0., 0.), (1., 0.)]);
+        p.join(&Polyline::from_iter([(1., 0.), (1., 1.)]));
+        assert_eq!(p, Polyline::from_iter([(0., 0.), (1., 0.), (1., 1.)]));
+
+        p.join(&Polyline::from_iter([(2., 1.), (3., 1.)]));
+        assert_eq!(
+            p,
+            Polyline::from_iter([(0., 0.), (1., 0.), (1., 1.), (2., 1.), (3., 1.)])
         );
+
+        let mut p = Polyline::default();
+        p.join(&Polyline::from_iter([(2., 1.)]));
+        assert_eq!(p, Polyline::from_iter([(2., 1.)]));
     }
 
     #[test]
@@ -1,9 +1,18 @@
 use crate::Color;
+use std::collections::BTreeMap;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct PathMetadata {
     pub color: Color,
     pub stroke_width: f64,
+
+    /// Free-form tags, e.g. to select paths with [`crate::Document::filter_by_attr`].
+    ///
+    /// The SVG loader reads them from the `class` (as `class`) and `data-*` (without the prefix)
+    /// attributes, and the writer emits them back the same way.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attrs: BTreeMap<String, String>,
 }
 
 impl Default for PathMetadata {
@@ -11,6 +20,7 @@ impl Default for PathMetadata {
         Self {
             stroke_width: 1.0,
             color: Color::default(),
+            attrs: BTreeMap::new(),
         }
     }
 }
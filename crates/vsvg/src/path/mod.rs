@@ -3,6 +3,7 @@ pub(crate) mod into_bezpath;
 mod metadata;
 #[allow(clippy::module_inception)]
 mod path;
+mod path32;
 mod point;
 
 use crate::{SvgPathWriter, Transforms};
@@ -11,18 +12,93 @@ pub use flattened_path::{FlattenedPath, Polyline};
 pub use into_bezpath::{IntoBezPath, IntoBezPathTolerance};
 pub use metadata::PathMetadata;
 pub use path::Path;
+pub use path32::{BezPath32, Path32, PathEl32};
 pub use point::Point;
 
 pub const DEFAULT_TOLERANCE: f64 = 0.05;
 
+/// Round `value` to the nearest multiple of `step`.
+pub(crate) fn quantize(value: f64, step: f64) -> f64 {
+    (value / step).round() * step
+}
+
 pub trait PathDataTrait:
     Transforms + SvgPathWriter + Default + Clone + PartialEq + std::fmt::Debug
 {
-    fn bounds(&self) -> kurbo::Rect;
+    /// Bounding box of the path data, or `None` if it is empty.
+    fn bounds(&self) -> Option<kurbo::Rect>;
     fn start(&self) -> Option<Point>;
     fn end(&self) -> Option<Point>;
     fn is_point(&self) -> bool;
+
+    /// Returns `true` if the path data has fewer than two points, and thus draws nothing.
+    fn is_degenerate(&self) -> bool;
+
     fn flip(&mut self);
+
+    /// Geometric length of the path data.
+    ///
+    /// Closing segments are included, curves are measured along their arc.
+    fn length(&self) -> f64;
+
+    /// Simplify the path data using the Ramer–Douglas–Peucker algorithm.
+    ///
+    /// Vertices closer than `tolerance` to the simplified path are removed. The tolerance is
+    /// expressed in the same unit as the coordinates. End points are always kept.
+    fn simplify(&mut self, tolerance: f64);
+
+    /// Simplify the path data using the Visvalingam–Whyatt algorithm.
+    ///
+    /// Vertices are removed until all remaining vertices form, with their neighbours, a triangle
+    /// of area larger than `area_threshold`, expressed in squared coordinate units. End points
+    /// are always kept.
+    fn simplify_vw(&mut self, area_threshold: f64);
+
+    /// Resample the path data with points every `spacing` units along its length.
+    ///
+    /// The result is made of straight lines only. End points are always kept, so the last
+    /// interval of each sub-path may be shorter than `spacing`. This is a no-op if `spacing` is
+    /// not positive.
+    fn resample(&mut self, spacing: f64);
+
+    /// Smooth the path data with `iterations` rounds of Chaikin's corner-cutting algorithm.
+    ///
+    /// Closed paths (those ending on their starting point) are smoothed all around, including
+    /// the closing corner. Otherwise, end points are kept. Curved segments are left untouched.
+    fn smooth_chaikin(&mut self, iterations: usize);
+
+    /// Remove points within `epsilon` of the previous point, which would otherwise create
+    /// zero-length segments.
+    ///
+    /// The first point is always kept. Curved segments are only removed if all their control
+    /// points are within `epsilon` of the previous point.
+    fn dedup_points(&mut self, epsilon: f64);
+
+    /// Round all coordinates, including control points, to the nearest multiple of `step`.
+    ///
+    /// This may create duplicate points, which [`PathDataTrait::dedup_points`] removes. This is a
+    /// no-op if `step` is not positive.
+    fn quantize(&mut self, step: f64);
+
+    /// Displace each point, including control points, by a random offset of up to `amplitude`
+    /// along each axis.
+    ///
+    /// The offsets only depend on `seed` and on the position of the points in the path data, so
+    /// that the result is reproducible.
+    fn jitter(&mut self, amplitude: f64, seed: u64);
+
+    /// Displace each point by a smooth random offset of up to `amplitude` along each axis.
+    ///
+    /// Unlike [`PathDataTrait::jitter`], the offset varies continuously with the distance along
+    /// the path, with features about `wavelength` apart. Only existing points are moved, so long
+    /// straight segments should be resampled first (see [`PathDataTrait::resample`]). This is a
+    /// no-op if `wavelength` is not positive.
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64);
+
+    /// Append `other` to the end of this path data.
+    ///
+    /// If `other` doesn't start exactly where this path ends, a connecting line is added.
+    fn join(&mut self, other: &Self);
 }
 
 pub trait PathTrait<D: PathDataTrait>: Transforms + Clone + PartialEq + std::fmt::Debug {
@@ -32,7 +108,10 @@ pub trait PathTrait<D: PathDataTrait>: Transforms + Clone + PartialEq + std::fmt
 
     fn into_data(self) -> D;
 
-    fn bounds(&self) -> kurbo::Rect;
+    /// Bounding box of the path, or `None` if it is empty.
+    fn bounds(&self) -> Option<kurbo::Rect> {
+        self.data().bounds()
+    }
 
     fn start(&self) -> Option<Point> {
         self.data().start()
@@ -42,6 +121,80 @@ pub trait PathTrait<D: PathDataTrait>: Transforms + Clone + PartialEq + std::fmt
         self.data().end()
     }
 
+    /// Geometric length of the path.
+    fn length(&self) -> f64 {
+        self.data().length()
+    }
+
+    /// Simplify the path using the Ramer–Douglas–Peucker algorithm.
+    ///
+    /// See [`PathDataTrait::simplify`].
+    fn simplify(&mut self, tolerance: f64) {
+        self.data_mut().simplify(tolerance);
+    }
+
+    /// Simplify the path using the Visvalingam–Whyatt algorithm.
+    ///
+    /// See [`PathDataTrait::simplify_vw`].
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        self.data_mut().simplify_vw(area_threshold);
+    }
+
+    /// Resample the path with points every `spacing` units along its length.
+    ///
+    /// See [`PathDataTrait::resample`].
+    fn resample(&mut self, spacing: f64) {
+        self.data_mut().resample(spacing);
+    }
+
+    /// Smooth the path with Chaikin's corner-cutting algorithm.
+    ///
+    /// See [`PathDataTrait::smooth_chaikin`].
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        self.data_mut().smooth_chaikin(iterations);
+    }
+
+    /// Remove consecutive duplicate points.
+    ///
+    /// See [`PathDataTrait::dedup_points`].
+    fn dedup_points(&mut self, epsilon: f64) {
+        self.data_mut().dedup_points(epsilon);
+    }
+
+    /// Round all coordinates to the nearest multiple of `step`.
+    ///
+    /// See [`PathDataTrait::quantize`].
+    fn quantize(&mut self, step: f64) {
+        self.data_mut().quantize(step);
+    }
+
+    /// Displace each point by a random offset.
+    ///
+    /// See [`PathDataTrait::jitter`].
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        self.data_mut().jitter(amplitude, seed);
+    }
+
+    /// Displace each point by a smooth random offset.
+    ///
+    /// See [`PathDataTrait::wobble`].
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        self.data_mut().wobble(amplitude, wavelength, seed);
+    }
+
+    /// Reverse the direction of the path in place, keeping its metadata.
+    fn reverse(&mut self) {
+        self.data_mut().flip();
+    }
+
+    /// Return a reversed copy of the path, keeping its metadata.
+    #[must_use]
+    fn reversed(&self) -> Self {
+        let mut path = self.clone();
+        path.reverse();
+        path
+    }
+
     fn metadata(&self) -> &PathMetadata;
     fn metadata_mut(&mut self) -> &mut PathMetadata;
 }
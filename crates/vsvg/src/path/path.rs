@@ -1,10 +1,20 @@
-use super::{FlattenedPath, PathDataTrait, PathMetadata, PathTrait, Point, Polyline};
+use super::{
+    quantize, FlattenedPath, PathDataTrait, PathMetadata, PathTrait, Point, Polyline,
+    DEFAULT_TOLERANCE,
+};
 use crate::crop::{crop_quad_bezier, Crop, QuadCropResult};
+use crate::hatch::hatch_lines;
+use crate::hull::{convex_hull, hull_to_bezpath};
+use crate::noise::{jitter_offset, wobble_offset};
+use crate::offset::{offset_polyline, OffsetJoin};
 use crate::path::into_bezpath::{
     line_segment_to_bezpath, points_to_bezpath, IntoBezPath, IntoBezPathTolerance,
 };
+use crate::resample::resample_bezpath;
+use crate::simplify::{douglas_peucker, visvalingam_whyatt};
+use crate::smooth::chaikin;
 use crate::Transforms;
-use kurbo::{Affine, BezPath, PathEl};
+use kurbo::{Affine, BezPath, ParamCurveArclen, PathEl};
 use std::cell::RefCell;
 use std::error::Error;
 use std::fmt::Debug;
@@ -12,6 +22,114 @@ use std::fmt::Debug;
 // ======================================================================================
 // The path data for `Path` is `kurbo::BezPath`.
 
+/// Accuracy used when computing the arc length of curved segments.
+const ARCLEN_ACCURACY: f64 = 1e-6;
+
+/// Tolerance used to flatten curves when computing areas and centroids.
+const AREA_TOLERANCE: f64 = 1e-3;
+
+/// Smooth each straight-line sub-path of `path` with Chaikin's algorithm.
+///
+/// Sub-paths containing curves are left untouched. Sub-paths with a close command or ending on
+/// their start point are smoothed as loops, and remain closed.
+fn smooth_line_subpaths(path: &BezPath, iterations: usize) -> BezPath {
+    fn flush(out: &mut BezPath, subpath: &mut Vec<PathEl>, iterations: usize) {
+        let mut points: Vec<Point> = vec![];
+        let mut explicit_close = false;
+        for el in subpath.iter() {
+            match el {
+                PathEl::MoveTo(pt) | PathEl::LineTo(pt) => points.push(pt.into()),
+                PathEl::ClosePath => explicit_close = true,
+                PathEl::QuadTo(..) | PathEl::CurveTo(..) => {
+                    // curves are not smoothed
+                    subpath.drain(..).for_each(|el| out.push(el));
+                    return;
+                }
+            }
+        }
+        subpath.clear();
+
+        let closed = explicit_close || (points.len() > 3 && points.first() == points.last());
+        if closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        let smoothed = chaikin(&points, iterations, closed);
+        if let Some((first, rest)) = smoothed.split_first() {
+            out.move_to(*first);
+            for pt in rest {
+                out.line_to(*pt);
+            }
+            if explicit_close {
+                out.close_path();
+            } else if closed {
+                out.line_to(*first);
+            }
+        }
+    }
+
+    let mut out = BezPath::new();
+    let mut subpath: Vec<PathEl> = vec![];
+    for el in path.elements() {
+        if matches!(el, PathEl::MoveTo(_)) {
+            flush(&mut out, &mut subpath, iterations);
+        }
+        subpath.push(*el);
+    }
+    flush(&mut out, &mut subpath, iterations);
+
+    out
+}
+
+/// Apply a point-sequence simplification to each run of straight segments of a path.
+///
+/// Curved segments are left untouched, but their end points are kept as run boundaries.
+fn simplify_line_runs(path: &BezPath, simplify: impl Fn(&[Point]) -> Vec<Point>) -> BezPath {
+    fn flush(out: &mut BezPath, run: &mut Vec<Point>, simplify: &impl Fn(&[Point]) -> Vec<Point>) {
+        // the first point of the run is always already part of `out`
+        if run.len() > 1 {
+            for pt in simplify(run).into_iter().skip(1) {
+                out.line_to(pt);
+            }
+        }
+        run.clear();
+    }
+
+    let mut out = BezPath::new();
+    let mut run: Vec<Point> = vec![];
+    let mut subpath_start = Point::ZERO;
+
+    for el in path.elements() {
+        match *el {
+            PathEl::MoveTo(pt) => {
+                flush(&mut out, &mut run, &simplify);
+                out.move_to(pt);
+                subpath_start = pt.into();
+                run.push(subpath_start);
+            }
+            PathEl::LineTo(pt) => run.push(pt.into()),
+            PathEl::QuadTo(p1, p2) => {
+                flush(&mut out, &mut run, &simplify);
+                out.quad_to(p1, p2);
+                run.push(p2.into());
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                flush(&mut out, &mut run, &simplify);
+                out.curve_to(p1, p2, p3);
+                run.push(p3.into());
+            }
+            PathEl::ClosePath => {
+                flush(&mut out, &mut run, &simplify);
+                out.close_path();
+                run.push(subpath_start);
+            }
+        }
+    }
+    flush(&mut out, &mut run, &simplify);
+
+    out
+}
+
 impl Transforms for BezPath {
     fn transform(&mut self, affine: &Affine) -> &mut Self {
         self.apply_affine(*affine);
@@ -20,8 +138,21 @@ impl Transforms for BezPath {
 }
 
 impl PathDataTrait for BezPath {
-    fn bounds(&self) -> kurbo::Rect {
-        kurbo::Shape::bounding_box(self)
+    fn bounds(&self) -> Option<kurbo::Rect> {
+        // kurbo only accounts for segments, so lone points must be added separately
+        let bounds = self
+            .segments()
+            .next()
+            .map(|_| kurbo::Shape::bounding_box(self));
+        self.elements()
+            .iter()
+            .filter_map(|el| match el {
+                PathEl::MoveTo(pt) => Some(*pt),
+                _ => None,
+            })
+            .fold(bounds, |acc, pt| {
+                Some(acc.map_or(kurbo::Rect::from_points(pt, pt), |rect| rect.union_pt(pt)))
+            })
     }
 
     fn start(&self) -> Option<Point> {
@@ -55,16 +186,220 @@ impl PathDataTrait for BezPath {
         matches!(self.elements(), [PathEl::MoveTo(a), PathEl::LineTo(b)] if a == b)
     }
 
+    fn is_degenerate(&self) -> bool {
+        self.elements()
+            .iter()
+            .filter(|el| !matches!(el, PathEl::ClosePath))
+            .count()
+            < 2
+    }
+
     fn flip(&mut self) {
         let segs: Vec<kurbo::PathSeg> = self.segments().collect();
         *self = BezPath::from_path_segments(segs.into_iter().rev().map(|seg| seg.reverse()));
     }
+
+    fn resample(&mut self, spacing: f64) {
+        if spacing > 0.0 {
+            *self = resample_bezpath(self, spacing);
+        }
+    }
+
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        *self = smooth_line_subpaths(self, iterations);
+    }
+
+    fn dedup_points(&mut self, epsilon: f64) {
+        let close = |current: kurbo::Point, pts: &[kurbo::Point]| {
+            pts.iter().all(|pt| current.distance(*pt) <= epsilon)
+        };
+
+        let mut out = BezPath::new();
+        let (mut start, mut current) = (kurbo::Point::ZERO, kurbo::Point::ZERO);
+        for el in self.elements() {
+            let (duplicate, end) = match *el {
+                PathEl::MoveTo(pt) => {
+                    start = pt;
+                    (false, pt)
+                }
+                PathEl::LineTo(pt) => (close(current, &[pt]), pt),
+                PathEl::QuadTo(p1, p2) => (close(current, &[p1, p2]), p2),
+                PathEl::CurveTo(p1, p2, p3) => (close(current, &[p1, p2, p3]), p3),
+                PathEl::ClosePath => (false, start),
+            };
+
+            if !duplicate {
+                out.push(*el);
+                current = end;
+            }
+        }
+
+        *self = out;
+    }
+
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        let mut index = 0;
+        let mut jitter = |pt: kurbo::Point| {
+            let (dx, dy) = jitter_offset(seed, index, amplitude);
+            index += 1;
+            kurbo::Point::new(pt.x + dx, pt.y + dy)
+        };
+
+        // end points returning to the sub-path's start reuse its offset to keep it closed
+        let (mut start, mut new_start) = (kurbo::Point::ZERO, kurbo::Point::ZERO);
+        let mut out = BezPath::new();
+        for el in self.elements() {
+            let end = move |p: kurbo::Point, new_p| if p == start { new_start } else { new_p };
+
+            out.push(match *el {
+                PathEl::MoveTo(p) => {
+                    (start, new_start) = (p, jitter(p));
+                    PathEl::MoveTo(new_start)
+                }
+                PathEl::LineTo(p) => PathEl::LineTo(end(p, jitter(p))),
+                PathEl::QuadTo(p1, p2) => PathEl::QuadTo(jitter(p1), end(p2, jitter(p2))),
+                PathEl::CurveTo(p1, p2, p3) => {
+                    PathEl::CurveTo(jitter(p1), jitter(p2), end(p3, jitter(p3)))
+                }
+                PathEl::ClosePath => PathEl::ClosePath,
+            });
+        }
+
+        *self = out;
+    }
+
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        if wavelength <= 0.0 {
+            return;
+        }
+
+        // control points are displaced according to their approximate position along the curve
+        let wobble = |pt: kurbo::Point, s: f64| {
+            let (dx, dy) = wobble_offset(seed, s, amplitude, wavelength);
+            kurbo::Point::new(pt.x + dx, pt.y + dy)
+        };
+
+        let mut out = BezPath::new();
+        let (mut start, mut new_start) = (kurbo::Point::ZERO, kurbo::Point::ZERO);
+        let (mut current, mut s) = (kurbo::Point::ZERO, 0.0);
+        for el in self.elements() {
+            // end points returning to the sub-path's start reuse its offset to keep it closed
+            let end = move |p: kurbo::Point, s| {
+                if p == start {
+                    new_start
+                } else {
+                    wobble(p, s)
+                }
+            };
+
+            match *el {
+                PathEl::MoveTo(p) => {
+                    new_start = wobble(p, s);
+                    out.move_to(new_start);
+                    (start, current) = (p, p);
+                }
+                PathEl::LineTo(p) => {
+                    s += current.distance(p);
+                    out.line_to(end(p, s));
+                    current = p;
+                }
+                PathEl::QuadTo(p1, p2) => {
+                    let len = kurbo::QuadBez::new(current, p1, p2).arclen(ARCLEN_ACCURACY);
+                    out.quad_to(wobble(p1, s + len / 2.0), end(p2, s + len));
+                    s += len;
+                    current = p2;
+                }
+                PathEl::CurveTo(p1, p2, p3) => {
+                    let len = kurbo::CubicBez::new(current, p1, p2, p3).arclen(ARCLEN_ACCURACY);
+                    out.curve_to(
+                        wobble(p1, s + len / 3.0),
+                        wobble(p2, s + 2.0 * len / 3.0),
+                        end(p3, s + len),
+                    );
+                    s += len;
+                    current = p3;
+                }
+                PathEl::ClosePath => {
+                    out.close_path();
+                    s += current.distance(start);
+                    current = start;
+                }
+            }
+        }
+
+        *self = out;
+    }
+
+    fn quantize(&mut self, step: f64) {
+        if step <= 0.0 {
+            return;
+        }
+
+        let round =
+            |pt: kurbo::Point| kurbo::Point::new(quantize(pt.x, step), quantize(pt.y, step));
+        *self = self
+            .elements()
+            .iter()
+            .map(|el| match *el {
+                PathEl::MoveTo(p) => PathEl::MoveTo(round(p)),
+                PathEl::LineTo(p) => PathEl::LineTo(round(p)),
+                PathEl::QuadTo(p1, p2) => PathEl::QuadTo(round(p1), round(p2)),
+                PathEl::CurveTo(p1, p2, p3) => PathEl::CurveTo(round(p1), round(p2), round(p3)),
+                PathEl::ClosePath => PathEl::ClosePath,
+            })
+            .collect();
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        *self = simplify_line_runs(self, |points| douglas_peucker(points, tolerance));
+    }
+
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        *self = simplify_line_runs(self, |points| visvalingam_whyatt(points, area_threshold));
+    }
+
+    fn join(&mut self, other: &Self) {
+        let end = self.end();
+
+        // `other`'s first sub-path loses its `MoveTo`, so closing it must be done explicitly
+        let mut joined_start = None;
+        for (i, el) in other.elements().iter().enumerate() {
+            match el {
+                PathEl::MoveTo(pt) if i == 0 && end.is_some() => {
+                    if end != Some(pt.into()) {
+                        self.line_to(*pt);
+                    }
+                    joined_start = Some(*pt);
+                }
+                PathEl::MoveTo(_) => {
+                    joined_start = None;
+                    self.push(*el);
+                }
+                PathEl::ClosePath => match joined_start {
+                    Some(start) => self.line_to(start),
+                    None => self.push(*el),
+                },
+                _ => self.push(*el),
+            }
+        }
+    }
+
+    fn length(&self) -> f64 {
+        self.segments()
+            .map(|seg| kurbo::ParamCurveArclen::arclen(&seg, ARCLEN_ACCURACY))
+            .sum()
+    }
 }
 
 // ======================================================================================
 // `Path`
 
-#[derive(Clone, Debug, Default, PartialEq)]
+/// A path made of lines and quadratic/cubic Bézier curves, along with its metadata.
+///
+/// Curves are kept as such (SVG arcs are converted to cubic Béziers on import), so a path can be
+/// flattened at any tolerance with [`Path::flatten`].
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Path {
     pub data: BezPath,
     pub(crate) metadata: PathMetadata,
@@ -90,9 +425,6 @@ impl PathTrait<BezPath> for Path {
         self.data
     }
 
-    fn bounds(&self) -> kurbo::Rect {
-        self.data.bounds()
-    }
     fn metadata(&self) -> &PathMetadata {
         &self.metadata
     }
@@ -234,6 +566,151 @@ impl Path {
         self.data = new_bezpath;
         self
     }
+
+    /// Signed area and area-weighted centroid sum of each flattened sub-path, closed implicitly.
+    fn area_moments(&self) -> (f64, f64, f64) {
+        self.flatten(AREA_TOLERANCE)
+            .iter()
+            .flat_map(|path| {
+                let points = path.data().points();
+                points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(a, b)| {
+                        let cross = a.x() * b.y() - b.x() * a.y();
+                        (cross, (a.x() + b.x()) * cross, (a.y() + b.y()) * cross)
+                    })
+            })
+            .fold((0.0, 0.0, 0.0), |(a, cx, cy), (da, dcx, dcy)| {
+                (a + da, cx + dcx, cy + dcy)
+            })
+    }
+
+    /// Signed area of the path, computed with the shoelace formula.
+    ///
+    /// Curves are flattened with a fine tolerance and open sub-paths are implicitly closed.
+    /// With the Y axis pointing down, the area is positive for paths that run clockwise on
+    /// screen, and negative otherwise. Sub-paths are summed, so holes with opposite winding are
+    /// subtracted.
+    #[must_use]
+    pub fn signed_area(&self) -> f64 {
+        self.area_moments().0 / 2.0
+    }
+
+    /// Centroid of the area enclosed by the path, or `None` if the area is zero.
+    ///
+    /// See [`Path::signed_area`].
+    #[must_use]
+    pub fn centroid(&self) -> Option<Point> {
+        let (area2, cx, cy) = self.area_moments();
+        (area2.abs() > f64::EPSILON).then(|| Point::new(cx / (3.0 * area2), cy / (3.0 * area2)))
+    }
+
+    /// Reverse the path if needed such that it runs clockwise on screen (Y axis pointing down),
+    /// or counter-clockwise if `clockwise` is `false`.
+    ///
+    /// Paths already wound in the requested direction, or with zero area, are left untouched. See
+    /// [`Path::signed_area`].
+    pub fn set_winding(&mut self, clockwise: bool) {
+        let area = self.signed_area();
+        if (clockwise && area < 0.0) || (!clockwise && area > 0.0) {
+            self.reverse();
+        }
+    }
+
+    /// Convex hull of the path, as a closed polygon with the same metadata.
+    ///
+    /// Curves are flattened with [`DEFAULT_TOLERANCE`]. Collinear points yield a degenerate
+    /// closed path between the two extremities, a single point yields a zero-length closed path,
+    /// and an empty path yields an empty path.
+    #[must_use]
+    pub fn convex_hull(&self) -> Path {
+        let points = self
+            .flatten(DEFAULT_TOLERANCE)
+            .into_iter()
+            .flat_map(|path| path.into_data().into_points())
+            .collect();
+        Path {
+            data: hull_to_bezpath(&convex_hull(points)),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Fill the interior of the path with parallel lines, `spacing` apart and at `angle_rad`
+    /// radians from the X axis.
+    ///
+    /// Curves are flattened with [`DEFAULT_TOLERANCE`], sub-paths are implicitly closed and the
+    /// interior is determined with the even-odd rule, so that nested sub-paths make holes. The
+    /// first line is half a spacing away from the path's extremity. Each line is returned as a
+    /// separate path with the same metadata, in an order that minimizes pen-up travel.
+    #[must_use]
+    pub fn hatch(&self, angle_rad: f64, spacing: f64) -> Vec<Path> {
+        let flattened = self.flatten(DEFAULT_TOLERANCE);
+        let rings: Vec<_> = flattened.iter().map(|line| line.data().points()).collect();
+
+        hatch_lines(&rings, angle_rad, spacing)
+            .into_iter()
+            .map(|(a, b)| Path {
+                data: points_to_bezpath([a, b]),
+                metadata: self.metadata.clone(),
+            })
+            .collect()
+    }
+
+    /// Offset the path by `distance`, with round joins.
+    ///
+    /// See [`Path::offset_with_join`].
+    #[must_use]
+    pub fn offset(&self, distance: f64) -> Vec<Path> {
+        self.offset_with_join(distance, OffsetJoin::default())
+    }
+
+    /// Offset the path by `distance`, positive to the left of the direction of travel as seen on
+    /// screen (Y axis pointing down).
+    ///
+    /// For paths running clockwise on screen, positive distances offset outward. Curves are
+    /// flattened with [`DEFAULT_TOLERANCE`] and each sub-path is offset separately. Open
+    /// sub-paths are offset without end caps. Portions of the offset which come closer than
+    /// `distance` to the original path, e.g. at inner corners, are removed, which may split the
+    /// result into several paths, or remove it altogether. The resulting paths have the same
+    /// metadata.
+    #[must_use]
+    pub fn offset_with_join(&self, distance: f64, join: OffsetJoin) -> Vec<Path> {
+        self.flatten(DEFAULT_TOLERANCE)
+            .iter()
+            .flat_map(|line| {
+                offset_polyline(line.data().points(), distance, join, DEFAULT_TOLERANCE)
+            })
+            .map(|(points, closed)| Path {
+                data: if closed {
+                    hull_to_bezpath(&points[..points.len() - 1])
+                } else {
+                    points_to_bezpath(points)
+                },
+                metadata: self.metadata.clone(),
+            })
+            .collect()
+    }
+
+    /// Split a compound path into one path per sub-path, each with the same metadata.
+    #[must_use]
+    pub fn into_subpaths(self) -> Vec<Path> {
+        let mut subpaths: Vec<BezPath> = vec![];
+        for el in self.data {
+            match (el, subpaths.last_mut()) {
+                (PathEl::MoveTo(_), _) | (_, None) => subpaths.push(BezPath::from_vec(vec![el])),
+                (_, Some(subpath)) => subpath.push(el),
+            }
+        }
+
+        subpaths
+            .into_iter()
+            .map(|data| Path {
+                data,
+                metadata: self.metadata.clone(),
+            })
+            .collect()
+    }
 }
 
 impl<T: IntoBezPath> From<T> for Path {
@@ -248,6 +725,8 @@ impl<T: IntoBezPath> From<T> for Path {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::Color;
+    use approx::assert_abs_diff_eq;
     use kurbo::Line;
 
     #[test]
@@ -262,10 +741,226 @@ mod test {
         assert_eq!(it.next(), None);
     }
 
+    #[test]
+    fn test_path_into_subpaths() {
+        let mut path = Path::from_svg("M 0,0 L 1,1 Z M 5,5 L 6,6").unwrap();
+        path.metadata_mut().color = Color::RED;
+
+        let subpaths = path.into_subpaths();
+        assert_eq!(subpaths.len(), 2);
+        assert_eq!(
+            subpaths[0].data,
+            BezPath::from_svg("M 0,0 L 1,1 Z").unwrap()
+        );
+        assert_eq!(subpaths[1].data, BezPath::from_svg("M 5,5 L 6,6").unwrap());
+        assert!(subpaths.iter().all(|p| p.metadata().color == Color::RED));
+        assert!(Path::default().into_subpaths().is_empty());
+    }
+
+    #[test]
+    fn test_path_smooth_chaikin() {
+        let mut path = Path::from_svg("M 0,0 L 4,0 L 4,4 L 0,4 Z M 10,10 L 14,10 L 14,14").unwrap();
+        path.smooth_chaikin(1);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg(
+                "M 1,0 L 3,0 L 4,1 L 4,3 L 3,4 L 1,4 L 0,3 L 0,1 Z M 10,10 L 13,10 L 14,11 L 14,14"
+            )
+            .unwrap()
+        );
+
+        let curve = BezPath::from_svg("M 0,0 C 1,1 2,1 3,0 L 5,5").unwrap();
+        let mut path = Path::from(curve.clone());
+        path.smooth_chaikin(3);
+        assert_eq!(path.data, curve);
+    }
+
+    #[test]
+    fn test_path_convex_hull() {
+        let mut path = Path::from_svg("M 0,0 L 4,0 L 2,1 L 4,4 M 0,4 L 1,2").unwrap();
+        path.metadata_mut().color = Color::RED;
+        let hull = path.convex_hull();
+        assert_eq!(
+            hull.data,
+            BezPath::from_svg("M 0,0 L 4,0 L 4,4 L 0,4 Z").unwrap()
+        );
+        assert_eq!(hull.metadata().color, Color::RED);
+
+        let hull = Path::from(kurbo::Circle::new((0., 0.), 10.)).convex_hull();
+        let bounds = hull.bounds().unwrap();
+        assert_abs_diff_eq!(bounds.width(), 20., epsilon = 0.1);
+        assert_eq!(hull.data.elements().last(), Some(&PathEl::ClosePath));
+
+        assert!(Path::default().convex_hull().data.elements().is_empty());
+    }
+
+    #[test]
+    fn test_path_signed_area_centroid() {
+        let square = Path::from_svg("M 0,0 L 1,0 L 1,1 L 0,1 Z").unwrap();
+        assert_abs_diff_eq!(square.signed_area(), 1.0);
+        assert_eq!(square.centroid(), Some(Point::new(0.5, 0.5)));
+
+        let mut reversed = square.clone();
+        reversed.reverse();
+        assert_abs_diff_eq!(reversed.signed_area(), -1.0);
+        assert_eq!(reversed.centroid(), Some(Point::new(0.5, 0.5)));
+
+        // open paths are implicitly closed
+        let triangle = Path::from_points([(0., 0.), (3., 0.), (0., 3.)]);
+        assert_abs_diff_eq!(triangle.signed_area(), 4.5);
+        let centroid = triangle.centroid().unwrap();
+        assert_abs_diff_eq!(centroid.x(), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(centroid.y(), 1.0, epsilon = 1e-12);
+
+        // a hole with opposite winding is subtracted
+        let with_hole =
+            Path::from_svg("M 0,0 L 4,0 L 4,4 L 0,4 Z M 1,1 L 1,2 L 2,2 L 2,1 Z").unwrap();
+        assert_abs_diff_eq!(with_hole.signed_area(), 15.0);
+
+        let circle = Path::from(kurbo::Circle::new((5., 5.), 2.));
+        assert_abs_diff_eq!(
+            circle.signed_area().abs(),
+            std::f64::consts::PI * 4.,
+            epsilon = 1e-2
+        );
+
+        assert_eq!(Path::from(Line::new((0., 0.), (1., 1.))).centroid(), None);
+        assert_eq!(Path::default().centroid(), None);
+        assert_abs_diff_eq!(Path::default().signed_area(), 0.0);
+    }
+
+    #[test]
+    fn test_path_set_winding() {
+        let cw = Path::from_svg("M 0,0 L 1,0 L 1,1 L 0,1 Z").unwrap();
+        let mut path = cw.clone();
+        path.set_winding(true);
+        assert_eq!(path, cw);
+
+        path.set_winding(false);
+        assert_eq!(path, cw.reversed());
+        assert_abs_diff_eq!(path.signed_area(), -1.0);
+
+        path.set_winding(true);
+        assert_abs_diff_eq!(path.signed_area(), 1.0);
+
+        let line = Path::from(Line::new((0., 0.), (1., 1.)));
+        let mut path = line.clone();
+        path.set_winding(false);
+        assert_eq!(path, line);
+    }
+
+    #[test]
+    fn test_path_resample() {
+        let mut path = Path::from(Line::new((0.0, 0.0), (10.0, 0.0)));
+        path.resample(2.0);
+        assert_eq!(path.data.elements().len(), 6);
+        assert_eq!(path.end(), Some(Point::new(10.0, 0.0)));
+
+        path.resample(0.0);
+        assert_eq!(path.data.elements().len(), 6);
+    }
+
     #[test]
     fn test_path_bounds() {
         let path = Path::from(Line::new((0.0, 0.0), (1.0, 1.0)));
-        assert_eq!(path.bounds(), kurbo::Rect::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(path.bounds(), Some(kurbo::Rect::new(0.0, 0.0, 1.0, 1.0)));
+        assert_eq!(Path::default().bounds(), None);
+
+        let point = Path::from_svg("M 2,3").unwrap();
+        assert_eq!(point.bounds(), Some(kurbo::Rect::new(2.0, 3.0, 2.0, 3.0)));
+
+        let path = Path::from_svg("M 5,5 L 6,6 M -1,2").unwrap();
+        assert_eq!(path.bounds(), Some(kurbo::Rect::new(-1.0, 2.0, 6.0, 6.0)));
+    }
+
+    #[test]
+    fn test_path_length() {
+        let path = Path::from(kurbo::Rect::new(0.0, 0.0, 1.0, 1.0));
+        assert_abs_diff_eq!(path.length(), 4.0, epsilon = 1e-9);
+
+        let path = Path::from_svg("M 0,0 L 3,4 L 3,0").unwrap();
+        assert_abs_diff_eq!(path.length(), 9.0, epsilon = 1e-9);
+
+        let path = Path::from(kurbo::Circle::new((0.0, 0.0), 1.0));
+        assert_abs_diff_eq!(path.length(), std::f64::consts::TAU, epsilon = 1e-3);
+
+        assert_abs_diff_eq!(Path::default().length(), 0.0);
+    }
+
+    #[test]
+    fn test_path_simplify() {
+        let mut path = Path::from_svg("M 0,0 L 1,0.01 L 2,0 L 2,2 L 0,2 Z").unwrap();
+        path.simplify(0.1);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 L 2,0 L 2,2 L 0,2 Z").unwrap()
+        );
+
+        // curves are kept, and split line runs
+        let mut path =
+            Path::from_svg("M 0,0 L 1,0 L 2,0 C 3,0 3,1 2,1 L 1,1 L 0,1 M 5,5 L 6,5 L 7,5")
+                .unwrap();
+        path.simplify(0.1);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 L 2,0 C 3,0 3,1 2,1 L 0,1 M 5,5 L 7,5").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_simplify_vw() {
+        let mut path = Path::from_svg("M 0,0 L 1,0 L 2,0 L 3,0 C 4,0 4,1 3,1 L 0,1").unwrap();
+        path.simplify_vw(0.01);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 L 3,0 C 4,0 4,1 3,1 L 0,1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_join() {
+        let mut path = BezPath::from_svg("M 0,0 L 10,0").unwrap();
+        path.join(&BezPath::from_svg("M 10,0 C 20,0 20,10 10,10").unwrap());
+        assert_eq!(
+            path,
+            BezPath::from_svg("M 0,0 L 10,0 C 20,0 20,10 10,10").unwrap()
+        );
+
+        path.join(&BezPath::from_svg("M 10,11 L 0,11").unwrap());
+        assert_eq!(
+            path,
+            BezPath::from_svg("M 0,0 L 10,0 C 20,0 20,10 10,10 L 10,11 L 0,11").unwrap()
+        );
+
+        let mut empty = BezPath::new();
+        empty.join(&BezPath::from_svg("M 1,1 L 2,2").unwrap());
+        assert_eq!(empty, BezPath::from_svg("M 1,1 L 2,2").unwrap());
+    }
+
+    #[test]
+    fn test_path_join_closed() {
+        let mut path = BezPath::from_svg("M 0,0 L 10,0").unwrap();
+        path.join(&BezPath::from_svg("M 10,0 L 20,0 L 20,10 Z M 30,0 L 40,0 Z").unwrap());
+        assert_eq!(
+            path,
+            BezPath::from_svg("M 0,0 L 10,0 L 20,0 L 20,10 L 10,0 M 30,0 L 40,0 Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_reverse() {
+        let mut path = Path::from_svg("M 0,0 L 10,0 C 20,0 20,10 10,10").unwrap();
+        path.metadata_mut().stroke_width = 2.0;
+
+        let reversed = path.reversed();
+        assert_eq!(
+            reversed.data,
+            BezPath::from_svg("M 10,10 C 20,10 20,0 10,0 L 0,0").unwrap()
+        );
+        assert_eq!(reversed.metadata(), path.metadata());
+
+        path.reverse();
+        assert_eq!(path, reversed);
     }
 
     #[test]
@@ -295,4 +990,260 @@ mod test {
         let path = Path::from_svg("M 10,0 L 10,0").unwrap();
         assert!(path.data.is_point());
     }
+
+    #[test]
+    fn test_path_from_svg_arcs() {
+        let bounds = |d: &str| {
+            Path::from_svg(d).unwrap().flatten(0.001)[0]
+                .bounds()
+                .unwrap()
+        };
+        let assert_rect = |rect: kurbo::Rect, expected: (f64, f64, f64, f64)| {
+            assert_abs_diff_eq!(rect.x0, expected.0, epsilon = 0.01);
+            assert_abs_diff_eq!(rect.y0, expected.1, epsilon = 0.01);
+            assert_abs_diff_eq!(rect.x1, expected.2, epsilon = 0.01);
+            assert_abs_diff_eq!(rect.y1, expected.3, epsilon = 0.01);
+        };
+
+        // absolute and relative forms are equivalent
+        let abs = Path::from_svg("M 10,10 A 10,10 0 0 1 30,10").unwrap();
+        let rel = Path::from_svg("M 10,10 a 10,10 0 0 1 20,0").unwrap();
+        assert!(abs
+            .data
+            .elements()
+            .iter()
+            .any(|el| matches!(el, kurbo::PathEl::CurveTo(..))));
+        assert_eq!(abs.data.elements().len(), rel.data.elements().len());
+        assert_rect(abs.bounds().unwrap(), (10., 0., 30., 10.));
+        assert_rect(rel.bounds().unwrap(), (10., 0., 30., 10.));
+
+        // the sweep flag selects the side of the arc, the large-arc flag its length
+        assert_rect(bounds("M 0,0 A 10,10 0 0 0 20,0"), (0., 0., 20., 10.));
+        assert_rect(bounds("M 0,0 A 20,20 0 0 1 20,0"), (0., -2.68, 20., 0.));
+        assert_rect(bounds("M 0,0 A 20,20 0 1 1 20,0"), (-10., -37.32, 30., 0.));
+
+        // radii too small to reach the end point are scaled up to a half-circle
+        assert_rect(bounds("M 0,0 A 1,1 0 0 1 10,0"), (0., -5., 10., 0.));
+        assert_rect(bounds("M 0,0 A 1,2 0 0 1 10,0"), (0., -10., 10., 0.));
+
+        // x-axis rotation is in degrees
+        assert_rect(bounds("M 0,0 A 10,5 0 0 1 0,20"), (0., 0., 20., 20.));
+        assert_rect(bounds("M 0,0 A 10,5 90 0 1 0,20"), (0., 0., 5., 20.));
+    }
+
+    #[test]
+    fn test_path_from_svg_relative_commands() {
+        // implicit line-to after a move-to, repeated pairs, and H/V shorthands
+        let path = Path::from_svg("m 10,10 20,0 0,20 h -10 v -5 H 0 V 0 l 5,5 5,-5 z").unwrap();
+        assert_eq!(
+            path.data,
+            BezPath::from_svg(
+                "M 10,10 L 30,10 L 30,30 L 20,30 L 20,25 L 0,25 L 0,0 L 5,5 L 10,0 Z"
+            )
+            .unwrap()
+        );
+
+        // smooth cubic shortcuts reflect the previous control point
+        let path = Path::from_svg("M 0,0 c 0,10 10,10 10,0 s 10,-10 10,0 S 30,10 30,0").unwrap();
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 C 0,10 10,10 10,0 C 10,-10 20,-10 20,0 C 20,10 30,10 30,0")
+                .unwrap()
+        );
+
+        // smooth quadratic shortcuts, including repetition
+        let path = Path::from_svg("M 0,0 Q 5,10 10,0 t 10,0 10,0").unwrap();
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 Q 5,10 10,0 Q 15,-10 20,0 Q 25,10 30,0").unwrap()
+        );
+
+        // a relative move-to after a close command starts from the sub-path's start
+        let path = Path::from_svg("M 10,10 l 5,0 z m 5,5 l 1,1").unwrap();
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 10,10 L 15,10 Z M 15,15 L 16,16").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_dedup_points() {
+        let mut path =
+            Path::from_svg("M 0,0 L 0,0 L 0,0 L 5,0 L 5,0.01 C 5,0 5,0 5,0 L 5,5 Z L 0,0 L 1,1")
+                .unwrap();
+        path.dedup_points(0.1);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 L 5,0 L 5,5 Z L 1,1").unwrap()
+        );
+
+        // non-degenerate curves are kept, even if they end on their start point
+        let curve = BezPath::from_svg("M 0,0 C 5,5 -5,5 0,0").unwrap();
+        let mut path = Path::from(curve.clone());
+        path.dedup_points(0.1);
+        assert_eq!(path.data, curve);
+
+        let mut path = Path::from_svg("M 3,3 L 3,3 L 3,3").unwrap();
+        path.dedup_points(0.0);
+        assert_eq!(path.data, BezPath::from_svg("M 3,3").unwrap());
+    }
+
+    #[test]
+    fn test_path_quantize() {
+        let mut path = Path::from_svg("M 0.1,0.24 L 2.26,0.2 Q 3.1,3.9 4.8,0.1 Z").unwrap();
+        path.quantize(0.5);
+        assert_eq!(
+            path.data,
+            BezPath::from_svg("M 0,0 L 2.5,0 Q 3,4 5,0 Z").unwrap()
+        );
+
+        // duplicates created by the rounding are removed by dedup_points
+        let mut path = Path::from_svg("M 0,0 L 0.1,0.1 L 0.9,1.1 L 1,1").unwrap();
+        path.quantize(1.0);
+        path.dedup_points(0.0);
+        assert_eq!(path.data, BezPath::from_svg("M 0,0 L 1,1").unwrap());
+    }
+
+    #[test]
+    fn test_path_jitter() {
+        let original = Path::from_svg("M 0,0 L 10,0 C 10,5 15,5 15,0 Z").unwrap();
+        let mut path = original.clone();
+        path.jitter(0.5, 42);
+        assert_ne!(path, original);
+
+        let elements = path.data.elements();
+        assert_eq!(elements.len(), original.data.elements().len());
+        assert!(matches!(elements[3], PathEl::ClosePath));
+        for (el, orig) in elements.iter().zip(original.data.elements()) {
+            if let (PathEl::CurveTo(p1, p2, p3), PathEl::CurveTo(o1, o2, o3)) = (el, orig) {
+                for (p, o) in [(p1, o1), (p2, o2), (p3, o3)] {
+                    assert!((p.x - o.x).abs() <= 0.5 && (p.y - o.y).abs() <= 0.5);
+                }
+            }
+        }
+
+        // reproducible
+        let mut again = original.clone();
+        again.jitter(0.5, 42);
+        assert_eq!(again, path);
+        let mut other = original.clone();
+        other.jitter(0.5, 43);
+        assert_ne!(other, path);
+
+        // explicitly closed paths stay closed
+        let mut square = Path::from_points([(0., 0.), (10., 0.), (10., 10.), (0., 0.)]);
+        square.jitter(0.5, 42);
+        let elements = square.data.elements();
+        assert!(matches!(
+            (elements[0], elements[3]),
+            (PathEl::MoveTo(a), PathEl::LineTo(b)) if a == b && a != kurbo::Point::ZERO
+        ));
+    }
+
+    #[test]
+    fn test_path_wobble() {
+        let mut path = Path::from_svg("M 0,0 L 100,0").unwrap();
+        path.resample(1.0);
+        let original = path.clone();
+        path.wobble(2.0, 20.0, 42);
+
+        // displacement is smooth along the path
+        let offsets: Vec<_> = path
+            .data
+            .elements()
+            .iter()
+            .zip(original.data.elements())
+            .filter_map(|(el, orig)| match (el, orig) {
+                (PathEl::MoveTo(p) | PathEl::LineTo(p), PathEl::MoveTo(o) | PathEl::LineTo(o)) => {
+                    Some(*p - *o)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(offsets.len(), 101);
+        assert!(offsets.iter().all(|v| v.x.abs() <= 2.0 && v.y.abs() <= 2.0));
+        assert!(offsets.iter().any(|v| v.hypot() > 0.5));
+        assert!(offsets.windows(2).all(|w| (w[1] - w[0]).hypot() < 0.5));
+
+        let mut curve = Path::from_svg("M 0,0 C 0,10 10,10 10,0 Q 15,-5 20,0 Z").unwrap();
+        let before = curve.clone();
+        curve.wobble(1.0, 5.0, 1);
+        assert_ne!(curve, before);
+        assert_eq!(curve.data.elements().len(), 4);
+
+        let mut square = Path::from_points([(0., 0.), (10., 0.), (10., 10.), (0., 0.)]);
+        square.wobble(1.0, 5.0, 1);
+        let elements = square.data.elements();
+        assert!(matches!(
+            (elements[0], elements[3]),
+            (PathEl::MoveTo(a), PathEl::LineTo(b)) if a == b
+        ));
+
+        curve.wobble(1.0, 0.0, 1);
+        assert_eq!(curve.data.elements().len(), 4);
+    }
+
+    #[test]
+    fn test_path_offset() {
+        let mut circle = Path::from(kurbo::Circle::new((50., 50.), 20.));
+        circle.metadata_mut().color = Color::RED;
+        circle.set_winding(true);
+
+        for (distance, radius) in [(5., 25.), (-5., 15.)] {
+            let offset = circle.offset(distance);
+            assert_eq!(offset.len(), 1);
+            assert_eq!(offset[0].data.elements().last(), Some(&PathEl::ClosePath));
+            assert_eq!(offset[0].metadata().color, Color::RED);
+
+            let center = Point::new(50., 50.);
+            for pt in offset[0].flatten(DEFAULT_TOLERANCE)[0].data().points() {
+                assert_abs_diff_eq!(pt.distance(&center), radius, epsilon = 0.1);
+            }
+        }
+
+        // winding sets which side is outward
+        circle.set_winding(false);
+        let offset = circle.offset(5.);
+        assert_abs_diff_eq!(offset[0].bounds().unwrap().width(), 30., epsilon = 0.1);
+
+        // collapsed
+        assert!(circle.offset(25.).is_empty());
+
+        // open paths, with miter joins
+        let path = Path::from_svg("M 0,0 L 10,0 L 10,10").unwrap();
+        let offset = path.offset_with_join(2., OffsetJoin::Miter { limit: 4. });
+        assert_eq!(
+            offset[0].data,
+            BezPath::from_svg("M 0,-2 L 10,-2 L 12,-2 L 12,0 L 12,10").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_path_hatch() {
+        let mut square = Path::from(kurbo::Rect::new(0., 0., 10., 10.));
+        square.metadata_mut().color = Color::BLUE;
+
+        let hatch = square.hatch(0.0, 0.5);
+        assert_eq!(hatch.len(), 20);
+        assert!(hatch
+            .iter()
+            .all(|path| path.metadata().color == Color::BLUE));
+        assert_eq!(
+            hatch[0].data,
+            BezPath::from_svg("M 0,0.25 L 10,0.25").unwrap()
+        );
+        assert_abs_diff_eq!(
+            hatch.iter().map(PathTrait::length).sum::<f64>(),
+            200.,
+            epsilon = 1e-9
+        );
+
+        // curves are flattened
+        let circle = Path::from(kurbo::Circle::new((0., 0.), 10.));
+        let hatch = circle.hatch(1.0, 1.0);
+        assert_eq!(hatch.len(), 20);
+        assert!(hatch
+            .iter()
+            .all(|path| path.bounds().unwrap().width() <= 20.0));
+    }
 }
@@ -0,0 +1,295 @@
+use super::{Path, PathDataTrait, PathMetadata, PathTrait, Point};
+use crate::{SvgPathWriter, Transforms};
+use kurbo::{Affine, BezPath, PathEl};
+
+/// Path element with `f32` coordinates, see [`Path32`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathEl32 {
+    MoveTo([f32; 2]),
+    LineTo([f32; 2]),
+    QuadTo([f32; 2], [f32; 2]),
+    CurveTo([f32; 2], [f32; 2], [f32; 2]),
+    ClosePath,
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_f32(pt: kurbo::Point) -> [f32; 2] {
+    [pt.x as f32, pt.y as f32]
+}
+
+fn to_f64(pt: [f32; 2]) -> kurbo::Point {
+    kurbo::Point::new(f64::from(pt[0]), f64::from(pt[1]))
+}
+
+impl From<PathEl> for PathEl32 {
+    fn from(el: PathEl) -> Self {
+        match el {
+            PathEl::MoveTo(pt) => Self::MoveTo(to_f32(pt)),
+            PathEl::LineTo(pt) => Self::LineTo(to_f32(pt)),
+            PathEl::QuadTo(p1, p2) => Self::QuadTo(to_f32(p1), to_f32(p2)),
+            PathEl::CurveTo(p1, p2, p3) => Self::CurveTo(to_f32(p1), to_f32(p2), to_f32(p3)),
+            PathEl::ClosePath => Self::ClosePath,
+        }
+    }
+}
+
+impl From<PathEl32> for PathEl {
+    fn from(el: PathEl32) -> Self {
+        match el {
+            PathEl32::MoveTo(pt) => Self::MoveTo(to_f64(pt)),
+            PathEl32::LineTo(pt) => Self::LineTo(to_f64(pt)),
+            PathEl32::QuadTo(p1, p2) => Self::QuadTo(to_f64(p1), to_f64(p2)),
+            PathEl32::CurveTo(p1, p2, p3) => Self::CurveTo(to_f64(p1), to_f64(p2), to_f64(p3)),
+            PathEl32::ClosePath => Self::ClosePath,
+        }
+    }
+}
+
+/// Path data with `f32` coordinates, see [`Path32`].
+///
+/// The operations of [`PathDataTrait`] are carried out on a [`BezPath`] copy of the data, whose
+/// result is rounded back to `f32`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BezPath32(Vec<PathEl32>);
+
+impl BezPath32 {
+    #[must_use]
+    pub fn elements(&self) -> &[PathEl32] {
+        &self.0
+    }
+
+    /// Convert to a [`BezPath`], which is exact.
+    #[must_use]
+    pub fn to_bezpath(&self) -> BezPath {
+        self.0.iter().map(|el| PathEl::from(*el)).collect()
+    }
+
+    /// Apply `f` to a [`BezPath`] copy of the data and store the result.
+    fn modify(&mut self, f: impl FnOnce(&mut BezPath)) {
+        let mut bezpath = self.to_bezpath();
+        f(&mut bezpath);
+        *self = (&bezpath).into();
+    }
+}
+
+impl From<&BezPath> for BezPath32 {
+    fn from(bezpath: &BezPath) -> Self {
+        Self(bezpath.elements().iter().map(|el| (*el).into()).collect())
+    }
+}
+
+impl Transforms for BezPath32 {
+    fn transform(&mut self, affine: &Affine) -> &mut Self {
+        self.modify(|bezpath| {
+            bezpath.transform(affine);
+        });
+        self
+    }
+}
+
+impl SvgPathWriter for BezPath32 {
+    fn to_svg_path_data(&self, decimals: Option<usize>) -> svg::node::element::path::Data {
+        self.to_bezpath().to_svg_path_data(decimals)
+    }
+}
+
+impl PathDataTrait for BezPath32 {
+    fn bounds(&self) -> Option<kurbo::Rect> {
+        self.to_bezpath().bounds()
+    }
+
+    fn start(&self) -> Option<Point> {
+        self.to_bezpath().start()
+    }
+
+    fn end(&self) -> Option<Point> {
+        self.to_bezpath().end()
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn is_point(&self) -> bool {
+        matches!(self.0.as_slice(), [PathEl32::MoveTo(a), PathEl32::LineTo(b)] if a == b)
+    }
+
+    fn is_degenerate(&self) -> bool {
+        self.0
+            .iter()
+            .filter(|el| !matches!(el, PathEl32::ClosePath))
+            .count()
+            < 2
+    }
+
+    fn flip(&mut self) {
+        self.modify(PathDataTrait::flip);
+    }
+
+    fn length(&self) -> f64 {
+        self.to_bezpath().length()
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        self.modify(|bezpath| bezpath.simplify(tolerance));
+    }
+
+    fn simplify_vw(&mut self, area_threshold: f64) {
+        self.modify(|bezpath| bezpath.simplify_vw(area_threshold));
+    }
+
+    fn resample(&mut self, spacing: f64) {
+        self.modify(|bezpath| bezpath.resample(spacing));
+    }
+
+    fn smooth_chaikin(&mut self, iterations: usize) {
+        self.modify(|bezpath| bezpath.smooth_chaikin(iterations));
+    }
+
+    fn dedup_points(&mut self, epsilon: f64) {
+        self.modify(|bezpath| bezpath.dedup_points(epsilon));
+    }
+
+    fn quantize(&mut self, step: f64) {
+        self.modify(|bezpath| bezpath.quantize(step));
+    }
+
+    fn jitter(&mut self, amplitude: f64, seed: u64) {
+        self.modify(|bezpath| bezpath.jitter(amplitude, seed));
+    }
+
+    fn wobble(&mut self, amplitude: f64, wavelength: f64, seed: u64) {
+        self.modify(|bezpath| bezpath.wobble(amplitude, wavelength, seed));
+    }
+
+    fn join(&mut self, other: &Self) {
+        self.modify(|bezpath| bezpath.join(&other.to_bezpath()));
+    }
+}
+
+/// Compact storage for a [`Path`], with `f32` coordinates.
+///
+/// Elements take half the memory of their `f64` counterpart, which matters for very large
+/// documents. The conversion from [`Path`] rounds coordinates to the nearest `f32`, i.e. with a
+/// relative error below [`f32::EPSILON`] (about 0.006px for coordinates up to 100,000px), which
+/// is well below plotter tolerances. Converting back to [`Path`] is exact.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path32 {
+    pub data: BezPath32,
+    metadata: PathMetadata,
+}
+
+impl Path32 {
+    #[must_use]
+    pub fn elements(&self) -> &[PathEl32] {
+        self.data.elements()
+    }
+}
+
+impl Transforms for Path32 {
+    fn transform(&mut self, affine: &Affine) -> &mut Self {
+        self.data.transform(affine);
+        self
+    }
+}
+
+impl PathTrait<BezPath32> for Path32 {
+    fn data(&self) -> &BezPath32 {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut BezPath32 {
+        &mut self.data
+    }
+
+    fn into_data(self) -> BezPath32 {
+        self.data
+    }
+
+    fn metadata(&self) -> &PathMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut PathMetadata {
+        &mut self.metadata
+    }
+}
+
+impl From<&Path> for Path32 {
+    fn from(path: &Path) -> Self {
+        Self {
+            data: path.data().into(),
+            metadata: path.metadata().clone(),
+        }
+    }
+}
+
+impl From<&Path32> for Path {
+    fn from(path: &Path32) -> Self {
+        Path::from_metadata(path.data.to_bezpath(), path.metadata.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use approx::assert_abs_diff_eq;
+    use kurbo::Shape;
+
+    #[test]
+    fn test_path32_round_trip() {
+        let mut path =
+            Path::from(kurbo::Circle::new((12_345.678_9, -98_765.432_1), 1234.5).to_path(0.1));
+        path.metadata_mut().color = Color::RED;
+        let path32 = Path32::from(&path);
+        assert_eq!(path32.elements().len(), path.data().elements().len());
+        assert_eq!(path32.metadata().color, Color::RED);
+
+        let restored = Path::from(&path32);
+        assert_eq!(restored.metadata(), path.metadata());
+        let original = path.data().elements().iter().flat_map(|el| match *el {
+            PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+            PathEl::QuadTo(p1, p2) => vec![p1, p2],
+            PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+            PathEl::ClosePath => vec![],
+        });
+        let round_tripped = restored.data().elements().iter().flat_map(|el| match *el {
+            PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+            PathEl::QuadTo(p1, p2) => vec![p1, p2],
+            PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+            PathEl::ClosePath => vec![],
+        });
+        for (a, b) in original.zip(round_tripped) {
+            let bound = f64::from(f32::EPSILON);
+            assert!((a.x - b.x).abs() <= a.x.abs() * bound);
+            assert!((a.y - b.y).abs() <= a.y.abs() * bound);
+        }
+
+        // converting back and forth again is exact
+        assert_eq!(Path32::from(&restored), path32);
+        assert!(std::mem::size_of::<PathEl32>() * 2 <= std::mem::size_of::<PathEl>());
+    }
+
+    #[test]
+    fn test_path32_traits() {
+        let path = Path::from_svg("M 0,0 L 10,0 Q 20,0 20,10 L 20,20").unwrap();
+        let mut path32 = Path32::from(&path);
+        assert_eq!(path32.bounds(), path.bounds());
+        assert_eq!(path32.start(), path.start());
+        assert_eq!(path32.end(), path.end());
+        assert_abs_diff_eq!(path32.length(), path.length(), epsilon = 1e-9);
+        assert!(!path32.data().is_point() && !path32.data().is_degenerate());
+
+        path32.translate(5.0, -5.0).scale(2.0);
+        path32.reverse();
+        assert_eq!(path32.start(), Some(Point::new(50., 30.)));
+        assert_eq!(path32.end(), Some(Point::new(10., -10.)));
+
+        path32
+            .data_mut()
+            .join(&Path32::from(&Path::from_points([(10., -10.), (0., 0.)])).data);
+        assert_eq!(path32.end(), Some(Point::new(0., 0.)));
+        assert_eq!(
+            format!("{:?}", path32.data().to_svg_path_data(Some(1))),
+            format!("{:?}", path32.data().to_bezpath().to_svg_path_data(Some(1)))
+        );
+    }
+}
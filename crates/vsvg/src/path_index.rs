@@ -77,7 +77,7 @@ impl IndexBuilder {
     }
 
     #[must_use]
-    pub fn build<P: PathTrait<D>, D: PathDataTrait>(self, paths: &[P]) -> PathIndex<P, D> {
+    pub fn build<P: PathTrait<D>, D: PathDataTrait>(self, paths: &[P]) -> PathIndex<'_, P, D> {
         PathIndex::new(paths, self)
     }
 }
@@ -183,7 +183,7 @@ impl<'a, P: PathTrait<D>, D: PathDataTrait> PathIndex<'a, P, D> {
         }
     }
 
-    pub fn pop_first(&mut self) -> Option<PathItem<P, D>> {
+    pub fn pop_first(&mut self) -> Option<PathItem<'_, P, D>> {
         // since the paths were reversed upon insertion, the pop operation corresponds to pop_first
         let (idx, path_item) = self.paths.pop()?;
         self.occupancy.set(idx, false);
@@ -204,8 +204,30 @@ impl<'a, P: PathTrait<D>, D: PathDataTrait> PathIndex<'a, P, D> {
     ///
     /// This function may return `None` even if the `PathIndex` is not empty, as some paths may not
     /// be indexed.
+    ///
+    /// Ties are broken in favour of the path appearing first in the original list, and then in
+    /// favour of the non-reversed direction, such that the result is deterministic.
+    pub fn pop_nearest(&mut self, point: &Point) -> Option<(PathItem<'_, P, D>, bool)> {
+        self.pop_nearest_impl(point, f64::INFINITY)
+    }
+
+    /// Return the nearest path to the given point, if it lies within `max_distance`.
+    ///
+    /// See [`PathIndex::pop_nearest`] for details.
+    pub fn pop_nearest_within(
+        &mut self,
+        point: &Point,
+        max_distance: f64,
+    ) -> Option<(PathItem<'_, P, D>, bool)> {
+        self.pop_nearest_impl(point, max_distance * max_distance)
+    }
+
     #[allow(clippy::missing_panics_doc)]
-    pub fn pop_nearest(&mut self, point: &Point) -> Option<(PathItem<P, D>, bool)> {
+    fn pop_nearest_impl(
+        &mut self,
+        point: &Point,
+        max_sq_distance: f64,
+    ) -> Option<(PathItem<'_, P, D>, bool)> {
         if self.reindex_agent.should_reindex() {
             self.reindex();
         }
@@ -218,26 +240,49 @@ impl<'a, P: PathTrait<D>, D: PathDataTrait> PathIndex<'a, P, D> {
         // FIXME: error handling is not optimal here
         let iter = self.tree.iter_nearest(&pt, &squared_euclidean).ok()?;
 
-        for (_, &tree_idx) in iter {
+        // (squared distance, map index, reversed) of the best candidate so far
+        let mut best: Option<(f64, usize, bool)> = None;
+        for (sq_distance, &tree_idx) in iter {
+            if sq_distance > max_sq_distance {
+                break;
+            }
+
+            // candidates are visited by increasing distance, so we are done past the first one
+            if let Some((best_sq_distance, _, _)) = best {
+                if sq_distance > best_sq_distance {
+                    break;
+                }
+            }
+
             let (idx, reversed) = self.tree_to_map_index(tree_idx);
 
             if self.occupancy[idx] {
-                let path_item = if self.settings.strict_order {
-                    self.paths.shift_remove(&idx)
-                } else {
-                    self.paths.swap_remove(&idx)
+                // paths are stored in reverse order, so a larger index means an earlier path
+                let is_better = match best {
+                    None => true,
+                    Some((_, best_idx, best_reversed)) => {
+                        idx > best_idx || (idx == best_idx && best_reversed && !reversed)
+                    }
                 };
-                let path_item = path_item.expect("path cannot be in tree but not in map");
-
-                self.occupancy.set(idx, false);
-                return Some((path_item, reversed));
+                if is_better {
+                    best = Some((sq_distance, idx, reversed));
+                }
+            } else {
+                // register missed access
+                self.reindex_agent.missed_access();
             }
-
-            // register missed access
-            self.reindex_agent.missed_access();
         }
 
-        None
+        let (_, idx, reversed) = best?;
+        let path_item = if self.settings.strict_order {
+            self.paths.shift_remove(&idx)
+        } else {
+            self.paths.swap_remove(&idx)
+        };
+        let path_item = path_item.expect("path cannot be in tree but not in map");
+
+        self.occupancy.set(idx, false);
+        Some((path_item, reversed))
     }
 }
 
@@ -297,6 +342,40 @@ mod tests {
         assert!(pi.pop_first().is_none());
     }
 
+    #[test]
+    fn test_path_index_pop_nearest_within() {
+        let paths = vec![
+            FlattenedPath::from(vec![Point::new(1.0, 0.0), Point::new(2.0, 2.0)]),
+            FlattenedPath::from(vec![Point::new(5.0, 0.0), Point::new(6.0, 2.0)]),
+        ];
+        let mut pi = IndexBuilder::new().flip(true).build(&paths);
+        assert!(pi.pop_nearest_within(&Point::new(0.0, 0.0), 0.5).is_none());
+        assert_nearest(
+            pi.pop_nearest_within(&Point::new(2.0, 2.1), 0.5),
+            &paths[0],
+            true,
+        );
+        assert!(pi.pop_nearest_within(&Point::new(2.0, 2.1), 0.5).is_none());
+        assert_nearest(
+            pi.pop_nearest_within(&Point::new(0.0, 0.0), 10.0),
+            &paths[1],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_path_index_pop_nearest_tie() {
+        let paths = vec![
+            FlattenedPath::from(vec![Point::new(5.0, 0.0), Point::new(1.0, 0.0)]),
+            FlattenedPath::from(vec![Point::new(1.0, 0.0), Point::new(2.0, 2.0)]),
+            FlattenedPath::from(vec![Point::new(-1.0, 0.0), Point::new(2.0, 2.0)]),
+        ];
+        let mut pi = IndexBuilder::new().flip(true).build(&paths);
+        assert_nearest(pi.pop_nearest(&Point::new(0.0, 0.0)), &paths[0], true);
+        assert_nearest(pi.pop_nearest(&Point::new(0.0, 0.0)), &paths[1], false);
+        assert_nearest(pi.pop_nearest(&Point::new(0.0, 0.0)), &paths[2], false);
+    }
+
     #[test]
     fn test_path_index_pop_nearest_bidir() {
         let paths = vec![
@@ -0,0 +1,309 @@
+//! Software rasterization of documents, based on [`tiny_skia`].
+
+use crate::{
+    Color, Document, DocumentTrait, FlattenedDocument, FlattenedLayer, LayerID, PageSize,
+    PathTrait, Unit,
+};
+use tiny_skia::{LineCap, LineJoin, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+const PAGE_BACKGROUND_COLOR: Color = Color::WHITE;
+const PAGE_BORDER_COLOR: Color = Color::gray(168);
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RasterError {
+    #[error("invalid image size {0}x{1}")]
+    InvalidSize(u32, u32),
+
+    #[error("document has no page size and no content")]
+    Empty,
+
+    #[error("PNG encoding error: {0}")]
+    Png(String),
+
+    #[error("document has no page size to derive the image size from")]
+    NoPageSize,
+}
+
+/// Size of a rendered image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    /// Explicit image size, in pixels.
+    Pixels { width: u32, height: u32 },
+
+    /// Image size derived from the page size, with the given number of pixels per inch.
+    Dpi(f64),
+}
+
+impl Resolution {
+    /// Image size in pixels for a document with `page_size`.
+    ///
+    /// Returns an error for [`Resolution::Dpi`] if `page_size` is `None`.
+    pub fn pixel_size(&self, page_size: Option<PageSize>) -> Result<(u32, u32), RasterError> {
+        match *self {
+            Self::Pixels { width, height } => Ok((width, height)),
+            Self::Dpi(dpi) => {
+                let page_size = page_size.ok_or(RasterError::NoPageSize)?;
+                let scale = dpi / Unit::In.to_px();
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let to_pixels = |v: f64| (v * scale).round() as u32;
+                Ok((to_pixels(page_size.w()), to_pixels(page_size.h())))
+            }
+        }
+    }
+}
+
+fn skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+fn skia_paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(skia_color(color));
+    paint.anti_alias = true;
+    paint
+}
+
+impl FlattenedDocument {
+    /// Rasterize the document into a `width` x `height` pixmap.
+    ///
+    /// See [`FlattenedDocument::render_filtered`] for details.
+    pub fn render(&self, width: u32, height: u32) -> Result<Pixmap, RasterError> {
+        self.render_filtered(width, height, |_, _| true)
+    }
+
+    /// Rasterize the document into a pixmap whose size is given by `resolution`.
+    ///
+    /// See [`FlattenedDocument::render_filtered`] for details.
+    pub fn render_at(&self, resolution: Resolution) -> Result<Pixmap, RasterError> {
+        let (width, height) = resolution.pixel_size(self.metadata().page_size)?;
+        self.render(width, height)
+    }
+
+    /// Rasterize the layers for which `predicate` returns `true` into a `width` x `height`
+    /// pixmap.
+    ///
+    /// The page (or the content bounds, if the document has no page size) is scaled to fit the
+    /// image while preserving its aspect ratio, and centered. The page is drawn with a white
+    /// background and a gray border, and the rest of the image is left transparent. Paths are
+    /// drawn with their color and stroke width.
+    pub fn render_filtered<F: Fn(LayerID, &FlattenedLayer) -> bool>(
+        &self,
+        width: u32,
+        height: u32,
+        predicate: F,
+    ) -> Result<Pixmap, RasterError> {
+        let mut pixmap =
+            Pixmap::new(width, height).ok_or(RasterError::InvalidSize(width, height))?;
+
+        let page_rect = self
+            .metadata()
+            .page_dimensions()
+            .filter(|(w, h)| *w != 0.0 && *h != 0.0)
+            .map(|(w, h)| kurbo::Rect::new(0., 0., w, h));
+        let bounds = page_rect
+            .or_else(|| self.bounds_filtered(&predicate))
+            .filter(|bounds| bounds.area() > 0.0)
+            .ok_or(RasterError::Empty)?;
+
+        let scale = f64::min(
+            f64::from(width) / bounds.width(),
+            f64::from(height) / bounds.height(),
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        let transform = Transform::from_translate(-bounds.x0 as f32, -bounds.y0 as f32)
+            .post_scale(scale as f32, scale as f32)
+            .post_translate(
+                ((f64::from(width) - bounds.width() * scale) / 2.0) as f32,
+                ((f64::from(height) - bounds.height() * scale) / 2.0) as f32,
+            );
+
+        #[allow(clippy::cast_possible_truncation)]
+        if let Some(rect) = page_rect.and_then(|rect| {
+            Rect::from_ltrb(
+                rect.x0 as f32,
+                rect.y0 as f32,
+                rect.x1 as f32,
+                rect.y1 as f32,
+            )
+        }) {
+            pixmap.fill_rect(rect, &skia_paint(PAGE_BACKGROUND_COLOR), transform, None);
+
+            let border = PathBuilder::from_rect(rect);
+            let stroke = Stroke {
+                width: 1.0,
+                ..Stroke::default()
+            };
+            // the border is drawn in pixel space to keep it thin regardless of scale
+            if let Some(border) = border.transform(transform) {
+                pixmap.stroke_path(
+                    &border,
+                    &skia_paint(PAGE_BORDER_COLOR),
+                    &stroke,
+                    Transform::identity(),
+                    None,
+                );
+            }
+        }
+
+        for (_, layer) in self
+            .layers
+            .iter()
+            .filter(|(lid, layer)| predicate(**lid, layer))
+        {
+            for path in &layer.paths {
+                let mut builder = PathBuilder::new();
+                #[allow(clippy::cast_possible_truncation)]
+                for (i, pt) in path.data().points().iter().enumerate() {
+                    if i == 0 {
+                        builder.move_to(pt.x() as f32, pt.y() as f32);
+                    } else {
+                        builder.line_to(pt.x() as f32, pt.y() as f32);
+                    }
+                }
+
+                // single-point paths are not drawable by tiny-skia
+                let Some(skia_path) = builder.finish() else {
+                    continue;
+                };
+
+                let metadata = path.metadata();
+                #[allow(clippy::cast_possible_truncation)]
+                let stroke = Stroke {
+                    width: metadata.stroke_width as f32,
+                    line_cap: LineCap::Round,
+                    line_join: LineJoin::Round,
+                    ..Stroke::default()
+                };
+                pixmap.stroke_path(
+                    &skia_path,
+                    &skia_paint(metadata.color),
+                    &stroke,
+                    transform,
+                    None,
+                );
+            }
+        }
+
+        Ok(pixmap)
+    }
+}
+
+impl Document {
+    /// Flatten the document with `tolerance` and rasterize it into a PNG file whose size is given
+    /// by `resolution`.
+    ///
+    /// See [`FlattenedDocument::render_filtered`] for details.
+    pub fn render_png(
+        &self,
+        tolerance: f64,
+        resolution: Resolution,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), RasterError> {
+        self.flatten(tolerance)
+            .render_at(resolution)?
+            .save_png(path)
+            .map_err(|err| RasterError::Png(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(pixmap: &Pixmap, x: u32, y: u32) -> (u8, u8, u8, u8) {
+        let c = pixmap.pixel(x, y).unwrap();
+        (c.red(), c.green(), c.blue(), c.alpha())
+    }
+
+    fn test_document() -> FlattenedDocument {
+        let mut doc = Document::new_with_page_size(PageSize::new(100., 50.));
+        doc.push_path(1, &[(10., 25.), (90., 25.)][..]);
+        let path = &mut doc.get_mut(1).paths[0];
+        path.metadata_mut().color = Color::RED;
+        path.metadata_mut().stroke_width = 4.0;
+        doc.flatten(0.1)
+    }
+
+    #[test]
+    fn test_render() {
+        let pixmap = test_document().render(200, 200).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (200, 200));
+
+        // page is centered vertically: y in 50..150
+        assert_eq!(pixel(&pixmap, 100, 10), (0, 0, 0, 0));
+        assert_eq!(pixel(&pixmap, 10, 60), (255, 255, 255, 255));
+        assert_eq!(pixel(&pixmap, 100, 100), (255, 0, 0, 255));
+    }
+
+    #[test]
+    fn test_render_filtered() {
+        let pixmap = test_document()
+            .render_filtered(200, 200, |lid, _| lid != 1)
+            .unwrap();
+        assert_eq!(pixel(&pixmap, 100, 100), (255, 255, 255, 255));
+    }
+
+    #[test]
+    fn test_render_png() {
+        let mut doc = Document::new_with_page_size(PageSize::new(100., 50.));
+        doc.push_path(1, &[(10., 25.), (90., 25.)][..]);
+        doc.get_mut(1).paths[0].metadata_mut().stroke_width = 5.0;
+
+        let path = std::env::temp_dir().join("vsvg_test_render_png.png");
+        let resolution = Resolution::Pixels {
+            width: 64,
+            height: 64,
+        };
+        doc.render_png(0.1, resolution, &path).unwrap();
+        let loaded = Pixmap::load_png(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, doc.flatten(0.1).render(64, 64).unwrap());
+        assert_eq!(pixel(&loaded, 32, 32), (0, 0, 0, 255));
+
+        assert!(matches!(
+            doc.render_png(
+                0.1,
+                resolution,
+                std::env::temp_dir().join("missing_dir/out.png")
+            ),
+            Err(RasterError::Png(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_at_dpi() {
+        let a4 = Resolution::Dpi(300.0).pixel_size(Some(PageSize::A4V));
+        assert_eq!(a4, Ok((2480, 3508)));
+        assert_eq!(
+            Resolution::Dpi(96.0).pixel_size(Some(PageSize::new(100., 50.))),
+            Ok((100, 50))
+        );
+
+        let pixmap = test_document().render_at(Resolution::Dpi(192.0)).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (200, 100));
+        assert_eq!(pixel(&pixmap, 100, 50), (255, 0, 0, 255));
+
+        assert_eq!(
+            Resolution::Dpi(300.0).pixel_size(None),
+            Err(RasterError::NoPageSize)
+        );
+        let pixels = Resolution::Pixels {
+            width: 10,
+            height: 20,
+        };
+        assert_eq!(pixels.pixel_size(None), Ok((10, 20)));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        assert_eq!(
+            test_document().render(0, 10).unwrap_err(),
+            RasterError::InvalidSize(0, 10)
+        );
+        assert_eq!(
+            FlattenedDocument::default().render(10, 10).unwrap_err(),
+            RasterError::Empty
+        );
+    }
+}
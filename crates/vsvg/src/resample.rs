@@ -0,0 +1,146 @@
+//! Resampling of paths at a fixed spacing.
+
+use crate::Point;
+use kurbo::{BezPath, ParamCurve, ParamCurveArclen, PathEl, PathSeg};
+
+const ARCLEN_ACCURACY: f64 = 1e-6;
+
+/// Resample a continuous sequence of segments with points every `spacing` units along its length.
+///
+/// The first and last points are always included. The last interval is shorter than `spacing`
+/// unless the total length is a multiple of it. Returns an empty vector if there are no
+/// segments.
+pub(crate) fn resample_segments(segments: &[PathSeg], spacing: f64) -> Vec<Point> {
+    let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+        return vec![];
+    };
+
+    let mut points = vec![Point::from(first.eval(0.0))];
+    let mut offset = spacing; // distance from the current segment's start to the next point
+    for seg in segments {
+        let len = seg.arclen(ARCLEN_ACCURACY);
+        while offset < len - ARCLEN_ACCURACY {
+            let t = seg.inv_arclen(offset, ARCLEN_ACCURACY);
+            points.push(seg.eval(t).into());
+            offset += spacing;
+        }
+        offset -= len;
+    }
+    points.push(last.eval(1.0).into());
+
+    points
+}
+
+/// Resample each sub-path of `path` with [`resample_segments`].
+///
+/// The result only contains line segments. Closed sub-paths remain closed.
+pub(crate) fn resample_bezpath(path: &BezPath, spacing: f64) -> BezPath {
+    fn flush(out: &mut BezPath, segments: &mut Vec<PathSeg>, spacing: f64, closed: bool) {
+        let points = resample_segments(segments, spacing);
+        if let Some((first, rest)) = points.split_first() {
+            out.move_to(*first);
+            // a closed sub-path ends on its first point, which the close command takes care of
+            let rest = if closed {
+                &rest[..rest.len() - 1]
+            } else {
+                rest
+            };
+            for pt in rest {
+                out.line_to(*pt);
+            }
+            if closed {
+                out.close_path();
+            }
+        }
+        segments.clear();
+    }
+
+    let mut out = BezPath::new();
+    let mut segments = vec![];
+    let (mut start, mut last) = (kurbo::Point::ZERO, kurbo::Point::ZERO);
+    for el in path.elements() {
+        match *el {
+            PathEl::MoveTo(pt) => {
+                flush(&mut out, &mut segments, spacing, false);
+                start = pt;
+            }
+            PathEl::LineTo(pt) => segments.push(PathSeg::Line(kurbo::Line::new(last, pt))),
+            PathEl::QuadTo(p1, p2) => {
+                segments.push(PathSeg::Quad(kurbo::QuadBez::new(last, p1, p2)));
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                segments.push(PathSeg::Cubic(kurbo::CubicBez::new(last, p1, p2, p3)));
+            }
+            PathEl::ClosePath => {
+                if last != start {
+                    segments.push(PathSeg::Line(kurbo::Line::new(last, start)));
+                }
+                flush(&mut out, &mut segments, spacing, true);
+            }
+        }
+        last = match *el {
+            PathEl::MoveTo(pt)
+            | PathEl::LineTo(pt)
+            | PathEl::QuadTo(_, pt)
+            | PathEl::CurveTo(_, _, pt) => pt,
+            PathEl::ClosePath => start,
+        };
+    }
+    flush(&mut out, &mut segments, spacing, false);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_resample_segments_line() {
+        let line = [PathSeg::Line(kurbo::Line::new((0., 0.), (10., 0.)))];
+        let points = resample_segments(&line, 2.0);
+        assert_eq!(points.len(), 6);
+        for (pt, x) in points.iter().zip([0., 2., 4., 6., 8., 10.]) {
+            assert_abs_diff_eq!(pt.x(), x, epsilon = 1e-9);
+            assert_abs_diff_eq!(pt.y(), 0.0);
+        }
+
+        // the last interval is shorter
+        let points = resample_segments(&line, 3.0);
+        assert_eq!(points.len(), 5);
+        assert_abs_diff_eq!(points[3].x(), 9.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(points[4].x(), 10.0);
+
+        assert!(resample_segments(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_resample_segments_across_segments() {
+        let segments = [
+            PathSeg::Line(kurbo::Line::new((0., 0.), (3., 0.))),
+            PathSeg::Line(kurbo::Line::new((3., 0.), (3., 3.))),
+        ];
+        let points = resample_segments(&segments, 2.0);
+        assert_eq!(points.len(), 4);
+        assert_abs_diff_eq!(points[1].x(), 2.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(points[2].x(), 3.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(points[2].y(), 1.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_resample_bezpath() {
+        let path = BezPath::from_svg("M 0,0 L 4,0 L 4,4 Z M 10,0 C 10,5 15,5 15,0").unwrap();
+        let resampled = resample_bezpath(&path, 1.0);
+        let elements = resampled.elements();
+
+        // the closed triangle has a perimeter of 8 + 4√2, so 14 points (with the close command)
+        assert_eq!(elements[0], PathEl::MoveTo((0., 0.).into()));
+        assert_eq!(elements[14], PathEl::ClosePath);
+        assert!(matches!(elements[15], PathEl::MoveTo(pt) if pt == (10., 0.).into()));
+        assert!(matches!(elements.last(), Some(PathEl::LineTo(pt)) if *pt == (15., 0.).into()));
+        assert!(elements[15..]
+            .iter()
+            .all(|el| matches!(el, PathEl::MoveTo(_) | PathEl::LineTo(_))));
+    }
+}
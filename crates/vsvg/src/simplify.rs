@@ -0,0 +1,224 @@
+//! Polyline simplification algorithms.
+
+use crate::Point;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Distance from `pt` to the segment `[a, b]`.
+fn segment_distance(pt: &Point, a: &Point, b: &Point) -> f64 {
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let len2 = dx * dx + dy * dy;
+    if len2 == 0.0 {
+        return pt.distance(a);
+    }
+
+    let t = (((pt.x() - a.x()) * dx + (pt.y() - a.y()) * dy) / len2).clamp(0.0, 1.0);
+    pt.distance(&Point::new(a.x() + t * dx, a.y() + t * dy))
+}
+
+/// Simplify a sequence of points with the Ramer–Douglas–Peucker algorithm.
+///
+/// Points closer than `tolerance` (in coordinate units) to the simplified polyline are removed.
+/// The first and last points are always kept.
+pub(crate) fn douglas_peucker(points: &[Point], tolerance: f64) -> Vec<Point> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // explicit stack to avoid deep recursion on long polylines
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (&points[start], &points[end]);
+        let farthest = points[start + 1..end]
+            .iter()
+            .enumerate()
+            .map(|(i, pt)| (start + 1 + i, segment_distance(pt, a, b)))
+            .max_by(|(_, d1), (_, d2)| d1.total_cmp(d2));
+
+        if let Some((idx, dist)) = farthest {
+            if dist > tolerance {
+                keep[idx] = true;
+                stack.push((start, idx));
+                stack.push((idx, end));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(pt, keep)| keep.then_some(*pt))
+        .collect()
+}
+
+/// Area of the triangle `(a, b, c)`.
+fn triangle_area(a: &Point, b: &Point, c: &Point) -> f64 {
+    ((b.x() - a.x()) * (c.y() - a.y()) - (c.x() - a.x()) * (b.y() - a.y())).abs() / 2.0
+}
+
+/// Candidate vertex for removal, ordered such that the smallest area pops first.
+struct VwCandidate {
+    area: f64,
+    idx: usize,
+    version: usize,
+}
+
+impl PartialEq for VwCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VwCandidate {}
+
+impl PartialOrd for VwCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VwCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .total_cmp(&self.area)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+/// Simplify a sequence of points with the Visvalingam–Whyatt algorithm.
+///
+/// The vertex forming the smallest triangle with its neighbours is iteratively removed, until
+/// all remaining triangles have an area larger than `area_threshold` (in squared coordinate
+/// units). The first and last points are always kept.
+pub(crate) fn visvalingam_whyatt(points: &[Point], area_threshold: f64) -> Vec<Point> {
+    let n = points.len();
+    if n <= 2 {
+        return points.to_vec();
+    }
+
+    let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let mut version = vec![0_usize; n];
+    let mut removed = vec![false; n];
+
+    let area = |prev: &[usize], next: &[usize], i: usize| {
+        triangle_area(&points[prev[i]], &points[i], &points[next[i]])
+    };
+
+    let mut heap: BinaryHeap<VwCandidate> = (1..n - 1)
+        .map(|idx| VwCandidate {
+            area: area(&prev, &next, idx),
+            idx,
+            version: 0,
+        })
+        .collect();
+
+    while let Some(candidate) = heap.pop() {
+        if removed[candidate.idx] || candidate.version != version[candidate.idx] {
+            continue;
+        }
+        if candidate.area > area_threshold {
+            break;
+        }
+
+        let (p, nx) = (prev[candidate.idx], next[candidate.idx]);
+        removed[candidate.idx] = true;
+        next[p] = nx;
+        prev[nx] = p;
+
+        for idx in [p, nx] {
+            if idx != 0 && idx != n - 1 {
+                version[idx] += 1;
+                heap.push(VwCandidate {
+                    area: area(&prev, &next, idx),
+                    idx,
+                    version: version[idx],
+                });
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(removed)
+        .filter_map(|(pt, removed)| (!removed).then_some(*pt))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pts(coords: &[(f64, f64)]) -> Vec<Point> {
+        coords.iter().copied().map(Point::from).collect()
+    }
+
+    #[test]
+    fn test_douglas_peucker_collinear() {
+        let points = pts(&[(0., 0.), (1., 0.), (2., 0.), (3., 0.), (4., 0.)]);
+        assert_eq!(douglas_peucker(&points, 0.1), pts(&[(0., 0.), (4., 0.)]));
+    }
+
+    #[test]
+    fn test_douglas_peucker_tolerance() {
+        let points = pts(&[(0., 0.), (1., 0.05), (2., 1.), (3., 0.05), (4., 0.)]);
+        assert_eq!(
+            douglas_peucker(&points, 0.5),
+            pts(&[(0., 0.), (2., 1.), (4., 0.)])
+        );
+        assert_eq!(douglas_peucker(&points, 2.0), pts(&[(0., 0.), (4., 0.)]));
+        assert_eq!(douglas_peucker(&points, 0.01), points);
+    }
+
+    #[test]
+    fn test_douglas_peucker_closed() {
+        let points = pts(&[(0., 0.), (1., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+        assert_eq!(
+            douglas_peucker(&points, 0.1),
+            pts(&[(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)])
+        );
+    }
+
+    #[test]
+    fn test_douglas_peucker_short() {
+        assert!(douglas_peucker(&[], 1.0).is_empty());
+        let points = pts(&[(0., 0.), (1., 1.)]);
+        assert_eq!(douglas_peucker(&points, 10.0), points);
+    }
+
+    #[test]
+    fn test_visvalingam_whyatt_collinear() {
+        let points = pts(&[(0., 0.), (1., 1.), (2., 2.), (3., 3.), (4., 4.)]);
+        assert_eq!(
+            visvalingam_whyatt(&points, 0.01),
+            pts(&[(0., 0.), (4., 4.)])
+        );
+    }
+
+    #[test]
+    fn test_visvalingam_whyatt_threshold() {
+        // the triangle at (1, 0.1) has area 0.1, the one at (2, 2) has area 4
+        let points = pts(&[(0., 0.), (1., 0.1), (2., 0.), (2., 2.), (4., 2.)]);
+        assert_eq!(
+            visvalingam_whyatt(&points, 0.5),
+            pts(&[(0., 0.), (2., 0.), (2., 2.), (4., 2.)])
+        );
+        assert_eq!(visvalingam_whyatt(&points, 0.01), points);
+        assert_eq!(
+            visvalingam_whyatt(&points, 100.),
+            pts(&[(0., 0.), (4., 2.)])
+        );
+    }
+
+    #[test]
+    fn test_visvalingam_whyatt_short() {
+        assert!(visvalingam_whyatt(&[], 1.0).is_empty());
+        let points = pts(&[(0., 0.), (1., 1.)]);
+        assert_eq!(visvalingam_whyatt(&points, 10.0), points);
+    }
+}
@@ -0,0 +1,102 @@
+//! Polyline smoothing algorithms.
+
+use crate::Point;
+
+/// Smooth a sequence of points with Chaikin's corner-cutting algorithm.
+///
+/// Each iteration replaces every segment by two points at 1/4 and 3/4 of its length, which
+/// roughly doubles the point count and rounds the corners. If `closed` is `true`, the points
+/// form a loop (the first point should not be repeated at the end) and the closing corner is
+/// smoothed too. Otherwise, the first and last points are kept.
+pub(crate) fn chaikin(points: &[Point], iterations: usize, closed: bool) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let cut = |a: &Point, b: &Point, t: f64| {
+        Point::new(a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t)
+    };
+
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        let n = points.len();
+        let mut smoothed = Vec::with_capacity(2 * n);
+
+        if closed {
+            for i in 0..n {
+                let (a, b) = (&points[i], &points[(i + 1) % n]);
+                smoothed.push(cut(a, b, 0.25));
+                smoothed.push(cut(a, b, 0.75));
+            }
+        } else {
+            smoothed.push(points[0]);
+            for (i, w) in points.windows(2).enumerate() {
+                if i > 0 {
+                    smoothed.push(cut(&w[0], &w[1], 0.25));
+                }
+                if i < n - 2 {
+                    smoothed.push(cut(&w[0], &w[1], 0.75));
+                }
+            }
+            smoothed.push(points[n - 1]);
+        }
+
+        points = smoothed;
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pts(coords: &[(f64, f64)]) -> Vec<Point> {
+        coords.iter().copied().map(Point::from).collect()
+    }
+
+    #[test]
+    fn test_chaikin_open() {
+        let points = pts(&[(0., 0.), (4., 0.), (4., 4.)]);
+        assert_eq!(
+            chaikin(&points, 1, false),
+            pts(&[(0., 0.), (3., 0.), (4., 1.), (4., 4.)])
+        );
+        assert_eq!(chaikin(&points, 0, false), points);
+        assert_eq!(chaikin(&points, 3, false).len(), 10);
+    }
+
+    #[test]
+    fn test_chaikin_closed() {
+        let square = pts(&[(0., 0.), (4., 0.), (4., 4.), (0., 4.)]);
+        assert_eq!(
+            chaikin(&square, 1, true),
+            pts(&[
+                (1., 0.),
+                (3., 0.),
+                (4., 1.),
+                (4., 3.),
+                (3., 4.),
+                (1., 4.),
+                (0., 3.),
+                (0., 1.)
+            ])
+        );
+
+        // a square smoothed repeatedly approaches a circle
+        let smoothed = chaikin(&square, 6, true);
+        assert_eq!(smoothed.len(), 4 * 64);
+        let center = Point::new(2., 2.);
+        let radii: Vec<_> = smoothed.iter().map(|pt| pt.distance(&center)).collect();
+        let max = radii.iter().copied().fold(f64::MIN, f64::max);
+        let min = radii.iter().copied().fold(f64::MAX, f64::min);
+        assert!(max - min < 0.1 * max);
+    }
+
+    #[test]
+    fn test_chaikin_short() {
+        let points = pts(&[(0., 0.), (1., 1.)]);
+        assert_eq!(chaikin(&points, 5, false), points);
+        assert_eq!(chaikin(&points, 5, true), points);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{LayerTrait, PathDataTrait, PathTrait};
+use crate::{LayerTrait, PathDataTrait, PathTrait, Unit};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct LayerStats {
@@ -10,21 +11,77 @@ impl LayerStats {
     pub fn from_layer<L: LayerTrait<P, D>, P: PathTrait<D>, D: PathDataTrait>(layer: &L) -> Self {
         LayerStats {
             num_paths: layer.paths().len(),
-            pen_up_length: layer
-                .paths()
-                .windows(2)
-                .map(|w| {
-                    if let Some(ref start) = w[0].end() {
-                        if let Some(ref end) = w[1].start() {
-                            start.distance(end)
-                        } else {
-                            0.0
-                        }
-                    } else {
-                        0.0
-                    }
-                })
-                .sum(),
+            pen_up_length: layer.pen_up_length(),
         }
     }
 }
+
+/// Plotter characteristics used to estimate the plot time.
+///
+/// Speeds are in pixels per second. The default values are typical of a hobbyist plotter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotParams {
+    /// speed while the pen is down
+    pub draw_speed: f64,
+
+    /// speed while the pen is up
+    pub travel_speed: f64,
+
+    /// time to raise the pen, in seconds
+    pub pen_up_time: f64,
+
+    /// time to lower the pen, in seconds
+    pub pen_down_time: f64,
+}
+
+impl Default for PlotParams {
+    fn default() -> Self {
+        Self {
+            draw_speed: 25.0 * Unit::Mm.to_px(),
+            travel_speed: 75.0 * Unit::Mm.to_px(),
+            pen_up_time: 0.15,
+            pen_down_time: 0.15,
+        }
+    }
+}
+
+impl PlotParams {
+    /// Estimate the time needed to draw `length` with the pen down, travel `pen_up_length` with
+    /// the pen up, and lower and raise the pen once for each of `path_count` paths.
+    ///
+    /// Acceleration is not accounted for. Returns [`Duration::MAX`] if a speed is zero.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn estimate(&self, length: f64, pen_up_length: f64, path_count: usize) -> Duration {
+        let secs = length / self.draw_speed
+            + pen_up_length / self.travel_speed
+            + path_count as f64 * (self.pen_up_time + self.pen_down_time);
+
+        Duration::try_from_secs_f64(secs.max(0.0)).unwrap_or(Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plot_params_estimate() {
+        let params = PlotParams {
+            draw_speed: 10.0,
+            travel_speed: 20.0,
+            pen_up_time: 0.5,
+            pen_down_time: 0.25,
+        };
+
+        // 100 / 10 + 40 / 20 + 4 * 0.75
+        assert_eq!(params.estimate(100.0, 40.0, 4), Duration::from_secs(15));
+        assert_eq!(params.estimate(0.0, 0.0, 0), Duration::ZERO);
+
+        let stopped = PlotParams {
+            draw_speed: 0.0,
+            ..params
+        };
+        assert_eq!(stopped.estimate(100.0, 0.0, 1), Duration::MAX);
+    }
+}
@@ -0,0 +1,321 @@
+//! Conversion of stroked paths to their outline.
+
+use crate::{Path, PathMetadata, PathTrait, DEFAULT_TOLERANCE};
+use geo::{BooleanOps, Coord, LineString, MultiPolygon, Polygon};
+use kurbo::{Point, Vec2};
+use std::f64::consts::TAU;
+
+/// Shape of the ends of open paths, see [`Path::stroke_to_outline`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CapStyle {
+    /// The stroke ends exactly at the end points.
+    #[default]
+    Butt,
+
+    /// The stroke ends with a half circle.
+    Round,
+
+    /// The stroke extends beyond the end points by half its width.
+    Square,
+}
+
+/// Shape of the corners of a stroke, see [`Path::stroke_to_outline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinStyle {
+    /// Corners are extended to a sharp point, unless the ratio of the miter length to the stroke
+    /// width exceeds `limit`, in which case they are beveled (like SVG's `stroke-miterlimit`).
+    Miter { limit: f64 },
+
+    /// Corners are rounded with an arc centered on the vertex.
+    Round,
+
+    /// Corners are cut straight.
+    Bevel,
+}
+
+impl Default for JoinStyle {
+    /// Miter joins with a limit of 4, as in SVG.
+    fn default() -> Self {
+        Self::Miter { limit: 4.0 }
+    }
+}
+
+/// Left normal of a direction, as seen on screen with the Y axis pointing down.
+fn normal(dir: Vec2) -> Vec2 {
+    Vec2::new(dir.y, -dir.x)
+}
+
+fn polygon(points: impl IntoIterator<Item = Point>) -> MultiPolygon<f64> {
+    let ring: LineString<f64> = points
+        .into_iter()
+        .map(|pt| Coord { x: pt.x, y: pt.y })
+        .collect();
+    MultiPolygon::new(vec![Polygon::new(ring, vec![])])
+}
+
+/// A circle approximated by a polygon whose edges are within `tolerance` of the circle.
+fn circle(center: Point, radius: f64, tolerance: f64) -> MultiPolygon<f64> {
+    let step = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        TAU / 8.0
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = ((TAU / step).ceil() as usize).max(8);
+
+    #[allow(clippy::cast_precision_loss)]
+    polygon((0..count).map(|i| center + Vec2::from_angle(TAU * i as f64 / count as f64) * radius))
+}
+
+/// Union of all `regions`, computed pairwise in a balanced way.
+fn union_all(mut regions: Vec<MultiPolygon<f64>>) -> MultiPolygon<f64> {
+    while regions.len() > 1 {
+        regions = regions
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => a.union(b),
+                [a] => a.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    regions.pop().unwrap_or_else(|| MultiPolygon::new(vec![]))
+}
+
+/// Region covered by the stroke of a polyline, as a list of overlapping pieces.
+///
+/// Polylines whose first and last points are the same are considered closed.
+fn stroke_pieces(
+    points: &[crate::Point],
+    half_width: f64,
+    cap: CapStyle,
+    join: JoinStyle,
+    tolerance: f64,
+) -> Vec<MultiPolygon<f64>> {
+    let mut points: Vec<Point> = points.iter().map(Into::into).collect();
+    points.dedup();
+    let closed = points.len() > 3 && points.first() == points.last();
+    if closed {
+        points.pop();
+    }
+
+    let mut pieces = vec![];
+    let Some(&first) = points.first() else {
+        return pieces;
+    };
+    if points.len() == 1 {
+        match cap {
+            CapStyle::Butt => {}
+            CapStyle::Round => pieces.push(circle(first, half_width, tolerance)),
+            CapStyle::Square => pieces.push(polygon(
+                [(-1., -1.), (1., -1.), (1., 1.), (-1., 1.)]
+                    .map(|(x, y)| first + Vec2::new(x, y) * half_width),
+            )),
+        }
+        return pieces;
+    }
+
+    let count = points.len();
+    let segment_count = if closed { count } else { count - 1 };
+    let segment = |i: usize| (points[i], points[(i + 1) % count]);
+    let dir = |i: usize| {
+        let (a, b) = segment(i);
+        (b - a).normalize()
+    };
+
+    // segments, extended at the ends for square caps
+    for i in 0..segment_count {
+        let (mut a, mut b) = segment(i);
+        let d = dir(i);
+        if !closed && cap == CapStyle::Square {
+            if i == 0 {
+                a -= d * half_width;
+            }
+            if i == segment_count - 1 {
+                b += d * half_width;
+            }
+        }
+        let n = normal(d) * half_width;
+        pieces.push(polygon([a + n, b + n, b - n, a - n]));
+    }
+
+    // joins, on the outer side of the corners
+    let corners = if closed { 0..count } else { 1..count - 1 };
+    for i in corners {
+        let vertex = points[i];
+        let (prev, next) = (dir((i + segment_count - 1) % segment_count), dir(i));
+        let cross = prev.cross(next);
+        if cross.abs() < 1e-9 && prev.dot(next) > 0.0 {
+            continue;
+        }
+
+        let side = if cross < 0.0 { -half_width } else { half_width };
+        let (n_prev, n_next) = (normal(prev), normal(next));
+        let (o_prev, o_next) = (n_prev * side, n_next * side);
+        match join {
+            JoinStyle::Round => pieces.push(circle(vertex, half_width, tolerance)),
+            JoinStyle::Bevel => pieces.push(polygon([vertex, vertex + o_prev, vertex + o_next])),
+            JoinStyle::Miter { limit } => {
+                let ratio = (2.0 / (1.0 + n_prev.dot(n_next))).sqrt();
+                if ratio.is_finite() && ratio <= limit {
+                    let tip = vertex + (n_prev + n_next) / (1.0 + n_prev.dot(n_next)) * side;
+                    pieces.push(polygon([vertex, vertex + o_prev, tip, vertex + o_next]));
+                } else {
+                    pieces.push(polygon([vertex, vertex + o_prev, vertex + o_next]));
+                }
+            }
+        }
+    }
+
+    if !closed && cap == CapStyle::Round {
+        pieces.push(circle(first, half_width, tolerance));
+        pieces.push(circle(points[count - 1], half_width, tolerance));
+    }
+
+    pieces
+}
+
+impl Path {
+    /// Compute the outline of the path stroked with `width`, with the given cap and join styles.
+    ///
+    /// Curves are flattened with [`DEFAULT_TOLERANCE`], which is also used to approximate round
+    /// caps and joins. Sub-paths ending on their start point are considered closed and have no
+    /// caps. Returns one closed path per connected region of the stroke, with its holes as
+    /// additional sub-paths (e.g. a ring for a stroked circle), suitable for
+    /// [`Path::hatch`]. The metadata is kept.
+    #[must_use]
+    pub fn stroke_to_outline(&self, width: f64, cap: CapStyle, join: JoinStyle) -> Vec<Path> {
+        if width <= 0.0 || !width.is_finite() {
+            return vec![];
+        }
+
+        let pieces = self
+            .flatten(DEFAULT_TOLERANCE)
+            .iter()
+            .flat_map(|line| {
+                stroke_pieces(
+                    line.data().points(),
+                    width / 2.0,
+                    cap,
+                    join,
+                    DEFAULT_TOLERANCE,
+                )
+            })
+            .collect();
+
+        union_all(pieces)
+            .into_iter()
+            .map(|polygon| Path::from_metadata(polygon, PathMetadata::clone(self.metadata())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use approx::assert_abs_diff_eq;
+    use geo::Area;
+
+    fn area(paths: &[Path]) -> f64 {
+        paths
+            .iter()
+            .flat_map(|path| path.flatten(DEFAULT_TOLERANCE))
+            .map(|line| polygon(line.data().points().iter().map(Into::into)).signed_area())
+            .sum::<f64>()
+            .abs()
+    }
+
+    #[test]
+    fn test_stroke_butt() {
+        let mut path = Path::from_points([(0., 0.), (10., 0.)]);
+        path.metadata_mut().color = Color::RED;
+        let outline = path.stroke_to_outline(2.0, CapStyle::Butt, JoinStyle::default());
+
+        // a rectangle
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].metadata().color, Color::RED);
+        assert_eq!(
+            outline[0].bounds(),
+            Some(kurbo::Rect::new(0., -1., 10., 1.))
+        );
+        let lines = outline[0].flatten(DEFAULT_TOLERANCE);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].data().points().len(), 5);
+        assert_abs_diff_eq!(area(&outline), 20.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_stroke_caps() {
+        let path = Path::from_points([(0., 0.), (10., 0.)]);
+
+        let square = path.stroke_to_outline(2.0, CapStyle::Square, JoinStyle::default());
+        assert_eq!(
+            square[0].bounds(),
+            Some(kurbo::Rect::new(-1., -1., 11., 1.))
+        );
+
+        let round = path.stroke_to_outline(2.0, CapStyle::Round, JoinStyle::default());
+        let bounds = round[0].bounds().unwrap();
+        assert_abs_diff_eq!(bounds.x0, -1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.x1, 11.0, epsilon = 1e-9);
+        // arcs are inscribed polygons within the tolerance
+        assert_abs_diff_eq!(area(&round), 20.0 + std::f64::consts::PI, epsilon = 0.3);
+
+        // a single point only shows with caps
+        let point = Path::from_points([(5., 5.)]);
+        assert!(point
+            .stroke_to_outline(2.0, CapStyle::Butt, JoinStyle::default())
+            .is_empty());
+        assert_abs_diff_eq!(
+            area(&point.stroke_to_outline(2.0, CapStyle::Square, JoinStyle::default())),
+            4.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_stroke_joins() {
+        // right angle: the outer corner is at (10, -1)
+        let path = Path::from_points([(0., 0.), (10., 0.), (10., 10.)]);
+        let stroke = |join| path.stroke_to_outline(2.0, CapStyle::Butt, join);
+
+        let miter = stroke(JoinStyle::Miter { limit: 4.0 });
+        assert_eq!(miter.len(), 1);
+        assert_eq!(miter[0].bounds(), Some(kurbo::Rect::new(0., -1., 11., 10.)));
+        assert_abs_diff_eq!(area(&miter), 40.0, epsilon = 1e-9);
+
+        let bevel = stroke(JoinStyle::Bevel);
+        assert_abs_diff_eq!(area(&bevel), 39.5, epsilon = 1e-9);
+
+        // miter ratio is √2 for right angles
+        let limited = stroke(JoinStyle::Miter { limit: 1.2 });
+        assert_abs_diff_eq!(area(&limited), 39.5, epsilon = 1e-9);
+
+        let round = stroke(JoinStyle::Round);
+        assert_abs_diff_eq!(
+            area(&round),
+            39.0 + std::f64::consts::PI / 4.0,
+            epsilon = 0.1
+        );
+    }
+
+    #[test]
+    fn test_stroke_closed() {
+        // a stroked square is a square ring
+        let path = Path::from(kurbo::Rect::new(0., 0., 10., 10.));
+        let outline = path.stroke_to_outline(2.0, CapStyle::Round, JoinStyle::default());
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].flatten(DEFAULT_TOLERANCE).len(), 2);
+        assert_eq!(
+            outline[0].bounds(),
+            Some(kurbo::Rect::new(-1., -1., 11., 11.))
+        );
+        assert_abs_diff_eq!(area(&outline), 144.0 - 64.0, epsilon = 1e-9);
+
+        assert!(path
+            .stroke_to_outline(0.0, CapStyle::Butt, JoinStyle::Round)
+            .is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Color;
+use quick_xml::events::attributes::{AttrError, Attribute};
+use quick_xml::events::BytesStart;
+use quick_xml::name::QName;
+
+/// Rewrite the `stroke` declarations of a `style` attribute which use a CSS color name into
+/// their hex equivalent.
+///
+/// Returns `None` if no declaration was rewritten.
+fn resolve_style_stroke_names(style: &str) -> Option<String> {
+    let mut changed = false;
+    let declarations: Vec<_> = style
+        .split(';')
+        .map(|declaration| {
+            let color = declaration
+                .split_once(':')
+                .filter(|(property, _)| property.trim().eq_ignore_ascii_case("stroke"))
+                .and_then(|(_, value)| Color::from_name(value.trim()));
+
+            if let Some(color) = color {
+                changed = true;
+                format!("stroke:{}", color.to_hex_string())
+            } else {
+                declaration.to_owned()
+            }
+        })
+        .collect();
+
+    changed.then(|| declarations.join(";"))
+}
+
+/// Rewrite `stroke` attributes and `style` declarations that use a CSS color name into their hex
+/// equivalent.
+///
+/// `usvg` only knows about lower-case (CSS3) names, so anything that [`Color::from_name`] accepts
+/// is resolved here before the SVG is parsed.
+pub(crate) fn resolve_stroke_names(elem: &BytesStart) -> Result<BytesStart<'static>, AttrError> {
+    let mut new_elem = BytesStart::new(String::from_utf8_lossy(elem.name().as_ref()).into_owned());
+    for attr in elem.attributes() {
+        let attr = attr?;
+        let value = std::str::from_utf8(&attr.value).ok();
+        let color = (attr.key == QName(b"stroke"))
+            .then(|| value.and_then(Color::from_name))
+            .flatten();
+        let style = (attr.key == QName(b"style"))
+            .then(|| value.and_then(resolve_style_stroke_names))
+            .flatten();
+
+        if let Some(color) = color {
+            new_elem.push_attribute((b"stroke".as_slice(), color.to_hex_string().as_bytes()));
+        } else if let Some(style) = style {
+            new_elem.push_attribute((b"style".as_slice(), style.as_bytes()));
+        } else {
+            new_elem.push_attribute(Attribute {
+                key: attr.key,
+                value: attr.value,
+            });
+        }
+    }
+
+    Ok(new_elem)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::svg::preprocessor::preprocess_svg;
+
+    #[test]
+    fn test_preprocess_color_names() {
+        let xml = r#"<svg xmlns="http://www.w3.org/2000/svg"><g stroke="RebeccaPurple"><path d="M 0,0 L 1,1" stroke="Tomato" fill="red"/><path stroke="url(#grad)"/></g></svg>"#;
+
+        assert_eq!(
+            preprocess_svg(xml).unwrap().svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg"><g stroke="#663399"><path d="M 0,0 L 1,1" stroke="#ff6347" fill="red"/><path stroke="url(#grad)"/></g></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_preprocess_style_color_names() {
+        let xml = r#"<svg xmlns="http://www.w3.org/2000/svg"><path style="fill:none; Stroke: CornflowerBlue ;stroke-width:2" d="M 0,0 L 1,1"/><path style="stroke:url(#grad)"/></svg>"#;
+
+        assert_eq!(
+            preprocess_svg(xml).unwrap().svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><path style="fill:none;stroke:#6495ed;stroke-width:2" d="M 0,0 L 1,1"/><path style="stroke:url(#grad)"/></svg>"#
+        );
+    }
+}
@@ -28,6 +28,11 @@ pub(crate) struct GroupInfo {
     pub(crate) id: Option<String>,
     pub(crate) groupmode: Option<String>,
     pub(crate) label: Option<String>,
+
+    /// The group is hidden with `display:none` or `visibility:hidden`. Only set for top-level
+    /// groups.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) hidden: bool,
 }
 
 impl GroupInfo {
@@ -90,8 +95,7 @@ impl GroupInfo {
 
             GroupInfo {
                 id,
-                groupmode: None,
-                label: None,
+                ..Default::default()
             }
         }))
     }
@@ -105,17 +109,68 @@ impl GroupInfo {
     }
 }
 
+fn is_hidden(property: &[u8], value: &[u8]) -> bool {
+    match property {
+        b"display" => value == b"none",
+        b"visibility" => value == b"hidden" || value == b"collapse",
+        _ => false,
+    }
+}
+
+/// Remove the `display:none` and `visibility:hidden` declarations from a `style` attribute.
+///
+/// Returns the remaining style and whether any declaration was removed.
+fn strip_hidden_style(style: &str) -> (String, bool) {
+    let mut hidden = false;
+    let remaining: Vec<_> = style
+        .split(';')
+        .filter(|decl| {
+            let Some((property, value)) = decl.split_once(':') else {
+                return !decl.trim().is_empty();
+            };
+            let decl_hidden = is_hidden(property.trim().as_bytes(), value.trim().as_bytes());
+            hidden |= decl_hidden;
+            !decl_hidden
+        })
+        .collect();
+
+    (remaining.join(";"), hidden)
+}
+
+/// Encode group attributes into their `id` attribute, see [`GroupInfo`].
+///
+/// Top-level groups are made visible and their hidden state recorded in [`GroupInfo::hidden`]
+/// instead, such that `usvg` doesn't drop them and the corresponding layer can be hidden.
 pub(crate) fn preprocess_inkscape_layer(xml: &str) -> Result<String, InkscapeExtPreprocessorError> {
     let mut reader = Reader::from_str(xml);
     reader.trim_text(true);
     let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let mut depth = 0_usize;
     loop {
         match reader.read_event() {
             Ok(Event::Start(e)) if e.name().as_ref() == b"g" => {
+                let top_level = depth == 1;
+                depth += 1;
+
                 let mut elem = BytesStart::new("g");
                 let mut group_info = GroupInfo::default();
                 for attr in e.attributes() {
                     match attr {
+                        Ok(Attribute { key, value })
+                            if top_level && is_hidden(key.as_ref(), &value) =>
+                        {
+                            group_info.hidden = true;
+                        }
+                        Ok(Attribute {
+                            key: QName(b"style"),
+                            value,
+                        }) if top_level => {
+                            let (style, hidden) = strip_hidden_style(std::str::from_utf8(&value)?);
+                            group_info.hidden |= hidden;
+                            if !style.is_empty() {
+                                elem.push_attribute(("style", style.as_str()));
+                            }
+                        }
                         Ok(Attribute {
                             key: QName(b"id"),
                             value,
@@ -140,6 +195,14 @@ pub(crate) fn preprocess_inkscape_layer(xml: &str) -> Result<String, InkscapeExt
 
                 writer.write_event(Event::Start(elem))?;
             }
+            Ok(Event::Start(e)) => {
+                depth += 1;
+                writer.write_event(Event::Start(e))?;
+            }
+            Ok(Event::End(e)) => {
+                depth = depth.saturating_sub(1);
+                writer.write_event(Event::End(e))?;
+            }
             Ok(Event::Eof) => break,
             // we can either move or borrow the event to write, depending on your use-case
             Ok(e) => writer.write_event(e)?,
@@ -240,7 +303,7 @@ mod tests {
 <g id="__vsvg_encoded__eyJpZCI6IiIsImdyb3VwbW9kZSI6bnVsbCwibGFiZWwiOm51bGx9"></g>
 <g id="__vsvg_encoded__eyJpZCI6bnVsbCwiZ3JvdXBtb2RlIjpudWxsLCJsYWJlbCI6IjMifQ"></g>
 <g stroke="white" id="__vsvg_encoded__eyJpZCI6ImhlbGxvIiwiZ3JvdXBtb2RlIjoibGF5ZXIiLCJsYWJlbCI6IjMifQ"></g>
-</svg>"#.to_string().replace("\n", "");
+</svg>"#.to_string().replace('\n', "");
 
         // force unique ID to 0 to account for other tests bumping UNIQUE_ID
         let idx = preprocessed.find("__vsvg_missing__").unwrap();
@@ -248,4 +311,59 @@ mod tests {
 
         assert_eq!(preprocessed, expected);
     }
+
+    #[test]
+    fn test_strip_hidden_style() {
+        assert_eq!(
+            strip_hidden_style("fill:none; display : none;stroke:red"),
+            ("fill:none;stroke:red".to_owned(), true)
+        );
+        assert_eq!(
+            strip_hidden_style("visibility:hidden"),
+            (String::new(), true)
+        );
+        assert_eq!(
+            strip_hidden_style("display:inline;stroke:red;"),
+            ("display:inline;stroke:red".to_owned(), false)
+        );
+    }
+
+    #[test]
+    fn test_preprocess_hidden_groups() {
+        let xml = r#"<svg xmlns="http://www.w3.org/2000/svg"><g id="a" style="display:none;stroke:red"><g display="none"></g></g><g id="b" visibility="hidden"></g><g id="c"></g></svg>"#;
+
+        let preprocessed = preprocess_inkscape_layer(xml).unwrap();
+        let mut reader = Reader::from_str(&preprocessed);
+        let mut groups = vec![];
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(e) if e.name().as_ref() == b"g" => groups.push(e.into_owned()),
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let group_info = |e: &BytesStart| {
+            let id = e.try_get_attribute("id").unwrap().unwrap();
+            GroupInfo::decode(std::str::from_utf8(&id.value).unwrap()).unwrap()
+        };
+        let attr = |e: &BytesStart, name: &str| {
+            e.try_get_attribute(name)
+                .unwrap()
+                .map(|a| String::from_utf8(a.value.into_owned()).unwrap())
+        };
+
+        assert_eq!(groups.len(), 4);
+
+        // top-level groups are made visible, and their hidden state is recorded
+        assert!(group_info(&groups[0]).hidden);
+        assert_eq!(attr(&groups[0], "style").as_deref(), Some("stroke:red"));
+        assert!(group_info(&groups[2]).hidden);
+        assert_eq!(attr(&groups[2], "visibility"), None);
+        assert!(!group_info(&groups[3]).hidden);
+
+        // nested groups are left untouched
+        assert!(!group_info(&groups[1]).hidden);
+        assert_eq!(attr(&groups[1], "display").as_deref(), Some("none"));
+    }
 }
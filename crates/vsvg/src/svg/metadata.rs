@@ -0,0 +1,184 @@
+//! Free-form document metadata, stored as Dublin Core elements in the SVG `<metadata>` element.
+
+use quick_xml::events::Event;
+use std::collections::BTreeMap;
+
+/// Metadata keys which are stored with a different Dublin Core element name.
+const KEY_ELEMENTS: [(&str, &str); 1] = [("author", "creator")];
+
+/// Dublin Core elements which the SVG writer derives from other data, and are thus not part of
+/// the free-form metadata.
+const RESERVED_ELEMENTS: [&str; 3] = ["format", "date", "source"];
+
+/// Name of the Dublin Core element (without the `dc:` prefix) used to store `key`.
+///
+/// Returns `None` if `key` is reserved or isn't a valid XML name.
+pub(crate) fn element_name(key: &str) -> Option<&str> {
+    let name = KEY_ELEMENTS
+        .iter()
+        .find_map(|(k, element)| (*k == key).then_some(*element))
+        .unwrap_or(key);
+
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    (valid && !RESERVED_ELEMENTS.contains(&name)).then_some(name)
+}
+
+/// Metadata key for the Dublin Core element `name` (without the `dc:` prefix).
+fn key_name(name: &str) -> &str {
+    KEY_ELEMENTS
+        .iter()
+        .find_map(|(key, element)| (*element == name).then_some(*key))
+        .unwrap_or(name)
+}
+
+/// Extract the free-form metadata from the events of an SVG document.
+///
+/// Entries are read from the Dublin Core elements of the `cc:Work` in `<metadata>`, with the
+/// text content of nested elements concatenated (e.g. Inkscape's `dc:creator/cc:Agent/dc:title`).
+/// The top-level `<title>` and `<desc>` elements are used as `title` and `description` if these
+/// are missing.
+#[derive(Debug, Default)]
+pub(crate) struct MetadataReader {
+    metadata: BTreeMap<String, String>,
+    fallbacks: BTreeMap<String, String>,
+
+    /// name of the open elements
+    stack: Vec<Vec<u8>>,
+
+    /// key and text content of the entry being read, and its depth in the stack
+    current: Option<(String, String, usize)>,
+}
+
+impl MetadataReader {
+    /// Process the next event of the document.
+    pub(crate) fn feed(&mut self, event: &Event) -> Result<(), quick_xml::Error> {
+        match event {
+            Event::Start(e) => {
+                let name = e.name().as_ref().to_vec();
+                if self.current.is_none() {
+                    let in_work = self.stack.iter().any(|name| name == b"metadata")
+                        && self.stack.last().is_some_and(|name| name == b"cc:Work");
+                    let top_level = self.stack.len() == 1;
+
+                    if let Some(element) = in_work.then(|| name.strip_prefix(b"dc:")).flatten() {
+                        let element = String::from_utf8_lossy(element);
+                        if !RESERVED_ELEMENTS.contains(&element.as_ref()) {
+                            self.current = Some((
+                                key_name(&element).to_owned(),
+                                String::new(),
+                                self.stack.len(),
+                            ));
+                        }
+                    } else if top_level && (name == b"title" || name == b"desc") {
+                        let key = if name == b"title" {
+                            "title"
+                        } else {
+                            "description"
+                        };
+                        self.current = Some((key.to_owned(), String::new(), self.stack.len()));
+                    }
+                }
+                self.stack.push(name);
+            }
+            Event::End(_) => {
+                self.stack.pop();
+                if let Some((key, value, depth)) = self.current.take() {
+                    if self.stack.len() == depth {
+                        let value = value.trim().to_owned();
+                        if depth == 1 {
+                            self.fallbacks.insert(key, value);
+                        } else {
+                            self.metadata.insert(key, value);
+                        }
+                    } else {
+                        self.current = Some((key, value, depth));
+                    }
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, value, _)) = &mut self.current {
+                    value.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(data) => {
+                if let Some((_, value, _)) = &mut self.current {
+                    value.push_str(&String::from_utf8_lossy(data));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Metadata read so far.
+    pub(crate) fn finish(self) -> BTreeMap<String, String> {
+        let mut metadata = self.metadata;
+        for (key, value) in self.fallbacks {
+            if !value.is_empty() {
+                metadata.entry(key).or_insert(value);
+            }
+        }
+
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::Reader;
+
+    fn read_metadata(xml: &str) -> BTreeMap<String, String> {
+        let mut reader = Reader::from_str(xml);
+        let mut metadata = MetadataReader::default();
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Eof => break,
+                event => metadata.feed(&event).unwrap(),
+            }
+        }
+        metadata.finish()
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let xml = r#"<svg xmlns="http://www.w3.org/2000/svg">
+            <title>Fallback</title>
+            <desc>A &amp; B</desc>
+            <metadata>
+                <rdf:RDF>
+                    <cc:Work>
+                        <dc:format>image/svg+xml</dc:format>
+                        <dc:title>Plot #1</dc:title>
+                        <dc:creator><cc:Agent><dc:title>Jane</dc:title></cc:Agent></dc:creator>
+                        <dc:subject>tests</dc:subject>
+                    </cc:Work>
+                </rdf:RDF>
+            </metadata>
+            <g><title>not the document title</title></g>
+        </svg>"#;
+
+        let metadata = read_metadata(xml);
+        assert_eq!(
+            metadata,
+            BTreeMap::from([
+                ("author".to_owned(), "Jane".to_owned()),
+                ("description".to_owned(), "A & B".to_owned()),
+                ("subject".to_owned(), "tests".to_owned()),
+                ("title".to_owned(), "Plot #1".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_element_name() {
+        assert_eq!(element_name("title"), Some("title"));
+        assert_eq!(element_name("author"), Some("creator"));
+        assert_eq!(element_name("date"), None);
+        assert_eq!(element_name("two words"), None);
+        assert_eq!(element_name(""), None);
+    }
+}
@@ -1,4 +1,8 @@
+mod color_name_preprocessor;
 mod inkscape_layer_preprocessor;
+mod metadata;
+mod path_attrs_preprocessor;
+mod preprocessor;
 mod reader;
 mod writer;
 
@@ -0,0 +1,153 @@
+use quick_xml::events::BytesStart;
+use quick_xml::name::QName;
+use std::collections::{BTreeMap, HashMap};
+
+/// Prefix of the `id` given to shape elements which have attributes to keep but no `id`.
+const GENERATED_ID_PREFIX: &str = "__vsvg_path_";
+
+/// Elements which `usvg` converts to paths.
+const SHAPE_ELEMENTS: [&[u8]; 7] = [
+    b"path",
+    b"rect",
+    b"circle",
+    b"ellipse",
+    b"line",
+    b"polyline",
+    b"polygon",
+];
+
+/// Name of the path attribute for the SVG attribute `key`, if it is one to keep.
+fn attr_name(key: &[u8]) -> Option<String> {
+    if key == b"class" {
+        Some("class".to_owned())
+    } else {
+        key.strip_prefix(b"data-")
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+    }
+}
+
+/// Side table of the `class` and `data-*` attributes of shape elements, which `usvg` would
+/// otherwise drop, keyed by the element's `id`.
+///
+/// `usvg` preserves the `id` of paths, so the attributes can be looked up from the tree with
+/// [`PathAttrs::get`].
+#[derive(Debug, Default)]
+pub(crate) struct PathAttrs {
+    attrs: HashMap<String, BTreeMap<String, String>>,
+}
+
+impl PathAttrs {
+    /// Record the attributes of `elem`, if it is a shape element with attributes to keep.
+    ///
+    /// The element is returned unchanged, unless it needs an `id` to be identified in the tree:
+    /// shapes without `id`, or with one already used by another shape, are given a generated one.
+    /// The original attributes are kept, since `class` may be used by style sheets.
+    pub(crate) fn collect(
+        &mut self,
+        elem: BytesStart<'static>,
+    ) -> Result<BytesStart<'static>, quick_xml::Error> {
+        if !SHAPE_ELEMENTS.contains(&elem.name().as_ref()) {
+            return Ok(elem);
+        }
+
+        let mut id = None;
+        let mut attrs = BTreeMap::new();
+        for attr in elem.attributes() {
+            let attr = attr?;
+            if attr.key == QName(b"id") {
+                id = Some(String::from_utf8_lossy(&attr.value).into_owned());
+            } else if let Some(name) = attr_name(attr.key.as_ref()) {
+                attrs.insert(name, attr.unescape_value()?.into_owned());
+            }
+        }
+
+        if attrs.is_empty() {
+            return Ok(elem);
+        }
+
+        if let Some(id) = id.filter(|id| !id.is_empty() && !self.attrs.contains_key(id)) {
+            self.attrs.insert(id, attrs);
+            return Ok(elem);
+        }
+
+        let generated_id = format!("{GENERATED_ID_PREFIX}{}", self.attrs.len());
+        let mut new_elem =
+            BytesStart::new(String::from_utf8_lossy(elem.name().as_ref()).into_owned());
+        for attr in elem.attributes() {
+            let attr = attr?;
+            if attr.key != QName(b"id") {
+                new_elem.push_attribute(attr);
+            }
+        }
+        new_elem.push_attribute(("id", generated_id.as_str()));
+        self.attrs.insert(generated_id, attrs);
+
+        Ok(new_elem)
+    }
+
+    /// Attributes recorded for the path with this `id`, if any.
+    pub(crate) fn get(&self, id: &str) -> BTreeMap<String, String> {
+        self.attrs.get(id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::{Reader, Writer};
+
+    fn collect_all(xml: &str) -> (String, PathAttrs) {
+        let mut path_attrs = PathAttrs::default();
+        let mut reader = Reader::from_str(xml);
+        let mut writer = Writer::new(Vec::new());
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Empty(e) => writer
+                    .write_event(Event::Empty(path_attrs.collect(e.into_owned()).unwrap()))
+                    .unwrap(),
+                Event::Eof => break,
+                e => writer.write_event(e).unwrap(),
+            }
+        }
+        (String::from_utf8(writer.into_inner()).unwrap(), path_attrs)
+    }
+
+    #[test]
+    fn test_path_attrs() {
+        let xml = r#"<svg><path id="p1" class="border" data-pen="fine &amp; black" d="M 0,0 L 1,1"/><path d="M 0,0 L 1,1"/><circle data-role="dot" r="1"/></svg>"#;
+        let (result, path_attrs) = collect_all(xml);
+
+        // only shapes without id are rewritten
+        assert_eq!(
+            result,
+            r#"<svg><path id="p1" class="border" data-pen="fine &amp; black" d="M 0,0 L 1,1"/><path d="M 0,0 L 1,1"/><circle data-role="dot" r="1" id="__vsvg_path_1"/></svg>"#
+        );
+
+        assert_eq!(
+            path_attrs.get("p1"),
+            BTreeMap::from([
+                ("class".to_owned(), "border".to_owned()),
+                ("pen".to_owned(), "fine & black".to_owned()),
+            ])
+        );
+        assert_eq!(
+            path_attrs.get("__vsvg_path_1"),
+            BTreeMap::from([("role".to_owned(), "dot".to_owned())])
+        );
+        assert!(path_attrs.get("p2").is_empty());
+    }
+
+    #[test]
+    fn test_path_attrs_duplicate_id() {
+        let (result, path_attrs) =
+            collect_all(r#"<svg><path id="a" class="x"/><path id="a" class="y"/></svg>"#);
+        assert_eq!(
+            result,
+            r#"<svg><path id="a" class="x"/><path class="y" id="__vsvg_path_1"/></svg>"#
+        );
+        assert_eq!(path_attrs.get("a")["class"], "x");
+        assert_eq!(path_attrs.get("__vsvg_path_1")["class"], "y");
+    }
+}
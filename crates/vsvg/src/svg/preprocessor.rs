@@ -0,0 +1,89 @@
+use crate::svg::color_name_preprocessor::resolve_stroke_names;
+use crate::svg::metadata::MetadataReader;
+use crate::svg::path_attrs_preprocessor::PathAttrs;
+use quick_xml::events::attributes::AttrError;
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+#[derive(thiserror::Error, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum PreprocessorError {
+    #[error("XML error: {0}")]
+    XmlError(#[from] quick_xml::Error),
+
+    #[error("XML attribute error: {0}")]
+    XmlAttrError(#[from] AttrError),
+
+    #[error("UTF8 decode error: {0}")]
+    UTF8DecodeError(#[from] std::str::Utf8Error),
+}
+
+/// Result of [`preprocess_svg`].
+#[derive(Debug)]
+pub(crate) struct PreprocessedSvg {
+    /// SVG to be parsed by `usvg`
+    pub(crate) svg: String,
+
+    /// free-form document metadata, see [`MetadataReader`]
+    pub(crate) properties: BTreeMap<String, String>,
+
+    /// attributes of the shape elements
+    pub(crate) path_attrs: PathAttrs,
+}
+
+/// Prepare an SVG document for `usvg`, in a single pass.
+///
+/// Stroke color names are resolved (see [`resolve_stroke_names`]), and the document metadata and
+/// shape attributes (see [`PathAttrs`]) are collected along the way. Invalid metadata is ignored with a warning, so it never prevents the
+/// document from loading.
+pub(crate) fn preprocess_svg(xml: &str) -> Result<PreprocessedSvg, PreprocessorError> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let mut metadata = Some(MetadataReader::default());
+    let mut path_attrs = PathAttrs::default();
+    loop {
+        let event = reader.read_event()?;
+        if let Some(reader) = &mut metadata {
+            if let Err(err) = reader.feed(&event) {
+                log::warn!("ignoring invalid SVG metadata: {err}");
+                metadata = None;
+            }
+        }
+
+        match event {
+            Event::Start(e) => {
+                let e = path_attrs.collect(resolve_stroke_names(&e)?)?;
+                writer.write_event(Event::Start(e))?;
+            }
+            Event::Empty(e) => {
+                let e = path_attrs.collect(resolve_stroke_names(&e)?)?;
+                writer.write_event(Event::Empty(e))?;
+            }
+            Event::Eof => break,
+            e => writer.write_event(e)?,
+        }
+    }
+
+    let result = writer.into_inner().into_inner();
+    Ok(PreprocessedSvg {
+        svg: std::str::from_utf8(&result)?.to_owned(),
+        properties: metadata.map(MetadataReader::finish).unwrap_or_default(),
+        path_attrs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preprocess_svg_invalid_metadata() {
+        let xml = r#"<svg xmlns="http://www.w3.org/2000/svg"><title>&unknown;</title><path d="M 0,0 L 1,1" stroke="Tomato"/></svg>"#;
+
+        let preprocessed = preprocess_svg(xml).unwrap();
+        assert!(preprocessed.properties.is_empty());
+        assert!(preprocessed.svg.contains(r##"stroke="#ff6347""##));
+    }
+}
@@ -1,12 +1,14 @@
 use crate::svg::inkscape_layer_preprocessor::{preprocess_inkscape_layer, GroupInfo};
+use crate::svg::path_attrs_preprocessor::PathAttrs;
+use crate::svg::preprocessor::preprocess_svg;
 use crate::{
     Color, Document, DocumentTrait, IntoBezPath, Layer, LayerID, LayerTrait, PageSize, Path,
-    PathTrait,
+    PathTrait, Unit,
 };
 use kurbo::{BezPath, PathEl};
-use lazy_static::lazy_static;
 use regex::Regex;
-use std::{error::Error, fs, path};
+use std::sync::LazyLock;
+use std::{error::Error, fs, io::Read, path};
 use usvg::{
     tiny_skia_path::PathSegment, utils::view_box_to_transform, NodeExt, Transform, Tree,
     TreeParsing,
@@ -30,12 +32,63 @@ impl IntoBezPath for usvg::tiny_skia_path::Path {
     }
 }
 
+/// How the SVG loader handles paths whose coordinates are not finite, e.g. due to overflowing
+/// transforms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonFiniteMode {
+    /// Drop the offending paths.
+    #[default]
+    Drop,
+
+    /// Fail with [`NonFiniteError`].
+    Error,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("{0} path(s) with non-finite coordinates")]
+pub struct NonFiniteError(pub usize);
+
+/// Options for [`Document::from_string_with_options`] and [`Document::from_svg_with_options`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SvgLoadOptions {
+    /// Load all content into layer 0, see [`Document::from_string`].
+    pub single_layer: bool,
+
+    pub non_finite: NonFiniteMode,
+
+    /// Remove consecutive points closer than this distance, see
+    /// [`DocumentTrait::dedup_points`].
+    pub dedup_points: Option<f64>,
+}
+
+/// Accumulates the paths of a layer, dropping those with non-finite coordinates.
+struct PathSink<'a> {
+    layer: &'a mut Layer,
+    dropped: &'a mut usize,
+    path_attrs: &'a PathAttrs,
+}
+
+impl PathSink<'_> {
+    fn push(&mut self, svg_path: &usvg::Path, transform: &Transform) {
+        match Path::from_usvg(svg_path, transform) {
+            Some(mut path) => {
+                path.metadata_mut().attrs = self.path_attrs.get(&svg_path.id);
+                self.layer.paths.push(path);
+            }
+            None => *self.dropped += 1,
+        }
+    }
+}
+
 impl Path {
+    /// Convert a `usvg` path, returning `None` if its transformed coordinates are not finite.
     #[must_use]
-    fn from_usvg(svg_path: &usvg::Path, transform: &Transform) -> Self {
-        let mut skia_path = (*svg_path.data).clone();
-        skia_path = skia_path.transform(*transform).unwrap();
-        skia_path = skia_path.transform(svg_path.transform).unwrap();
+    fn from_usvg(svg_path: &usvg::Path, transform: &Transform) -> Option<Self> {
+        // tiny-skia refuses to create paths with non-finite bounds
+        let skia_path = (*svg_path.data)
+            .clone()
+            .transform(*transform)?
+            .transform(svg_path.transform)?;
 
         let mut res = Self {
             data: skia_path.into_bezpath(),
@@ -55,30 +108,54 @@ impl Path {
             res.metadata_mut().stroke_width = f64::from(stroke.width.get());
         }
 
-        res
+        Some(res)
     }
 }
 
-fn parse_group(group: &usvg::Node, transform: &Transform, layer: &mut Layer) {
+fn parse_group(group: &usvg::Node, transform: &Transform, sink: &mut PathSink) {
     group.children().for_each(|node| {
         let child_transform = transform.pre_concat(node.transform());
 
         match *node.borrow() {
-            usvg::NodeKind::Path(ref path) => {
-                layer.paths.push(Path::from_usvg(path, &child_transform));
+            usvg::NodeKind::Path(ref path) if path.visibility == usvg::Visibility::Visible => {
+                sink.push(path, &child_transform);
             }
             usvg::NodeKind::Group(_) => {
-                parse_group(&node, &child_transform, layer);
+                parse_group(&node, &child_transform, sink);
             }
             _ => {}
         }
     });
 }
 
-lazy_static! {
-    static ref DIGITS_RE: Regex = Regex::new(r"\d+").unwrap();
+/// Unit of the root element's `width` attribute, or pixels if unitless or missing.
+///
+/// Returns `None` for relative units (e.g. `%`) or if the root element can't be parsed.
+fn source_unit(svg: &str) -> Option<Unit> {
+    let mut reader = quick_xml::Reader::from_str(svg);
+    let root = loop {
+        match reader.read_event().ok()? {
+            quick_xml::events::Event::Start(e) | quick_xml::events::Event::Empty(e) => break e,
+            quick_xml::events::Event::Eof => return None,
+            _ => {}
+        }
+    };
+
+    let Some(width) = root.try_get_attribute("width").ok()? else {
+        return Some(Unit::Px);
+    };
+    let width = std::str::from_utf8(&width.value).ok()?.trim();
+    let unit =
+        width.trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'));
+    if unit.is_empty() {
+        Some(Unit::Px)
+    } else {
+        Unit::from(unit)
+    }
 }
 
+static DIGITS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
 /// Interpret the attributes of a top-level group to determine its layer ID.
 ///
 /// See <https://github.com/abey79/vsvg/issues/7> for the strategy used here.
@@ -108,14 +185,49 @@ fn layer_id_from_attribute(id: &str, label: Option<&str>) -> Option<LayerID> {
 }
 
 impl Document {
+    /// Create a `Document` based on a path to an SVG file, with each top-level group loaded as a
+    /// layer.
+    ///
+    /// This is equivalent to [`Document::from_svg`] with `single_layer` set to `false`, see
+    /// [`Document::from_string`] for more details on layer handling.
+    pub fn from_svg_file(path: impl AsRef<path::Path>) -> Result<Self, Box<dyn Error>> {
+        Self::from_svg(path, false)
+    }
+
     /// Create a `Document` based on a path to an SVG file.
     ///
     /// See [`Document::from_string`] for more details on layer handling.
     pub fn from_svg<P: AsRef<path::Path>>(
         path: P,
         single_layer: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::from_svg_with_options(
+            path,
+            &SvgLoadOptions {
+                single_layer,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a `Document` based on a path to an SVG file, with options.
+    pub fn from_svg_with_options<P: AsRef<path::Path>>(
+        path: P,
+        options: &SvgLoadOptions,
     ) -> Result<Self, Box<dyn Error>> {
         let svg = fs::read_to_string(path)?;
+        Document::from_string_with_options(&svg, options)
+    }
+
+    /// Create a `Document` based on a reader providing SVG data.
+    ///
+    /// See [`Document::from_string`] for more details on layer handling.
+    pub fn from_svg_reader<R: Read>(
+        mut reader: R,
+        single_layer: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut svg = String::new();
+        reader.read_to_string(&mut svg)?;
         Document::from_string(&svg, single_layer)
     }
 
@@ -132,18 +244,39 @@ impl Document {
     ///    if any.
     /// 3. If neither of the above rules apply, then the layer ID is determined by the top-level
     ///    group's order of appearance in the SVG file.
+    ///
+    /// Top-level groups hidden with `display:none` or `visibility:hidden` are loaded as hidden
+    /// layers (see [`crate::LayerMetadata::hidden`]). Other hidden elements are not loaded at all.
+    ///
+    /// Paths with non-finite coordinates are dropped, see [`Document::from_string_with_options`]
+    /// to fail instead.
     pub fn from_string(svg: &str, single_layer: bool) -> Result<Self, Box<dyn Error>> {
-        let preprocessed_svg;
-
-        let tree = Tree::from_str(
-            if single_layer {
-                svg
-            } else {
-                preprocessed_svg = preprocess_inkscape_layer(svg)?;
-                preprocessed_svg.as_str()
+        Self::from_string_with_options(
+            svg,
+            &SvgLoadOptions {
+                single_layer,
+                ..Default::default()
             },
-            &usvg::Options::default(),
-        )?;
+        )
+    }
+
+    /// Create a `Document` based on a string containing SVG data, with options.
+    ///
+    /// Depending on [`SvgLoadOptions::non_finite`], paths with non-finite coordinates are either
+    /// dropped or cause a [`NonFiniteError`]. Either way, the returned document only ever holds
+    /// finite coordinates. Points are deduplicated last, after cropping to the page.
+    pub fn from_string_with_options(
+        svg: &str,
+        options: &SvgLoadOptions,
+    ) -> Result<Self, Box<dyn Error>> {
+        let single_layer = options.single_layer;
+        let preprocessed = preprocess_svg(svg)?;
+        let mut preprocessed_svg = preprocessed.svg;
+        if !single_layer {
+            preprocessed_svg = preprocess_inkscape_layer(&preprocessed_svg)?;
+        }
+
+        let tree = Tree::from_str(&preprocessed_svg, &usvg::Options::default())?;
 
         let viewbox_transform =
             view_box_to_transform(tree.view_box.rect, tree.view_box.aspect, tree.size);
@@ -151,39 +284,71 @@ impl Document {
         // add frame for the page
         let (w, h) = (f64::from(tree.size.width()), f64::from(tree.size.height()));
         let mut doc = Document::new_with_page_size(PageSize::new(w, h));
+        doc.metadata_mut().properties = preprocessed.properties;
+        doc.metadata_mut().source_unit = source_unit(svg);
 
-        if single_layer {
-            doc.load_tree(&tree, viewbox_transform);
+        let dropped = if single_layer {
+            doc.load_tree(&tree, viewbox_transform, &preprocessed.path_attrs)
         } else {
-            doc.load_tree_multilayer(&tree, viewbox_transform);
+            doc.load_tree_multilayer(&tree, viewbox_transform, &preprocessed.path_attrs)
+        };
+        if dropped > 0 {
+            if options.non_finite == NonFiniteMode::Error {
+                return Err(NonFiniteError(dropped).into());
+            }
+            log::warn!("dropped {dropped} path(s) with non-finite coordinates");
         }
 
         doc.crop(0., 0., w, h);
+        if let Some(epsilon) = options.dedup_points {
+            doc.dedup_points(epsilon);
+        }
         Ok(doc)
     }
 
     /// Load a [Tree] into this document. All content is added to layer 0.
-    fn load_tree(&mut self, tree: &Tree, viewbox_transform: Transform) {
-        let layer = self.get_mut(0);
+    ///
+    /// Returns the number of paths dropped because of non-finite coordinates.
+    fn load_tree(
+        &mut self,
+        tree: &Tree,
+        viewbox_transform: Transform,
+        path_attrs: &PathAttrs,
+    ) -> usize {
+        let mut dropped = 0;
+        let mut sink = PathSink {
+            layer: self.get_mut(0),
+            dropped: &mut dropped,
+            path_attrs,
+        };
         for child in tree.root.children() {
             let transform = viewbox_transform.pre_concat(child.transform());
 
             match *child.borrow() {
                 usvg::NodeKind::Group(_) => {
-                    parse_group(&child, &transform, layer);
+                    parse_group(&child, &transform, &mut sink);
                 }
-                usvg::NodeKind::Path(ref path) => {
-                    layer.paths.push(Path::from_usvg(path, &transform));
+                usvg::NodeKind::Path(ref path) if path.visibility == usvg::Visibility::Visible => {
+                    sink.push(path, &transform);
                 }
                 _ => {}
             }
         }
+
+        dropped
     }
 
     /// Load a [Tree] into this document, splitting the content into multiple layers.
     ///
-    /// See [`Document::from_string`] for more details on layer handling.
-    fn load_tree_multilayer(&mut self, tree: &Tree, viewbox_transform: Transform) {
+    /// See [`Document::from_string`] for more details on layer handling. Returns the number of
+    /// paths dropped because of non-finite coordinates.
+    fn load_tree_multilayer(
+        &mut self,
+        tree: &Tree,
+        viewbox_transform: Transform,
+        path_attrs: &PathAttrs,
+    ) -> usize {
+        let mut dropped = 0;
         let mut top_level_index = 0;
         for child in tree.root.children() {
             let transform = viewbox_transform.pre_concat(child.transform());
@@ -193,6 +358,7 @@ impl Document {
                     let group_info = GroupInfo::decode(&group_data.id);
                     let layer_id;
                     let layer_name;
+                    let hidden = group_info.as_ref().is_some_and(|info| info.hidden);
                     match group_info {
                         // top-level group with inkscape layer information
                         Some(group_info) if group_info.groupmode.as_deref() == Some("layer") => {
@@ -230,27 +396,46 @@ impl Document {
                     }
 
                     let layer = self.get_mut(layer_id.unwrap_or(top_level_index));
-                    parse_group(&child, &transform, layer);
+                    parse_group(
+                        &child,
+                        &transform,
+                        &mut PathSink {
+                            layer,
+                            dropped: &mut dropped,
+                            path_attrs,
+                        },
+                    );
 
                     // set layer name
                     if let Some(name) = layer_name {
-                        layer.metadata_mut().name = name.clone();
+                        layer.metadata_mut().name = name;
                     }
+                    layer.metadata_mut().hidden |= hidden;
                 }
-                usvg::NodeKind::Path(ref path) => {
-                    self.get_mut(0)
-                        .paths
-                        .push(Path::from_usvg(path, &transform));
+                usvg::NodeKind::Path(ref path) if path.visibility == usvg::Visibility::Visible => {
+                    PathSink {
+                        layer: self.get_mut(0),
+                        dropped: &mut dropped,
+                        path_attrs,
+                    }
+                    .push(path, &transform);
                 }
                 _ => {}
             }
         }
+
+        dropped
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{test_file, Document, DocumentTrait, LayerTrait, PathDataTrait};
+    use super::*;
+    use crate::{
+        test_file, Color, Document, DocumentTrait, LayerTrait, PageSize, PathDataTrait, PathTrait,
+        Point, Unit,
+    };
+    use approx::assert_abs_diff_eq;
     use kurbo::BezPath;
 
     #[test]
@@ -262,6 +447,18 @@ mod tests {
         assert_eq!(doc.try_get(3).unwrap().paths.len(), 1);
     }
 
+    #[test]
+    fn test_from_svg_file() {
+        let doc = Document::from_svg_file(test_file!("multilayer.svg")).unwrap();
+        assert_eq!(
+            doc,
+            Document::from_svg(test_file!("multilayer.svg"), false).unwrap()
+        );
+        assert!(doc.layers.len() > 1);
+
+        assert!(Document::from_svg_file(test_file!("missing.svg")).is_err());
+    }
+
     #[test]
     fn test_one_layer() {
         let doc = Document::from_svg(test_file!("singlelayer.svg"), false).unwrap();
@@ -320,6 +517,8 @@ mod tests {
         assert_eq!(doc.try_get(3).unwrap().metadata().name, "Hello");
         assert_eq!(doc.try_get(4).unwrap().metadata().name, "world");
         assert_eq!(doc.try_get(5).unwrap().metadata().name, "layer_name");
+        assert_eq!(doc.layer_by_name("Hello"), Some(3));
+        assert_eq!(doc.layer_by_name("hello"), None);
     }
 
     #[test]
@@ -369,7 +568,7 @@ mod tests {
         assert_eq!(doc.layers.len(), 0);
     }
 
-    #[ignore] //TODO: this needs to be fixed in usvg
+    #[ignore = "this needs to be fixed in usvg"]
     #[test]
     fn test_point_path() {
         let doc = Document::from_string(
@@ -388,6 +587,328 @@ mod tests {
         assert!(doc.try_get(0).unwrap().paths[0].data.is_point());
     }
 
+    #[test]
+    fn test_source_unit() {
+        let unit = |svg: &str| {
+            Document::from_string(svg, false)
+                .unwrap()
+                .metadata()
+                .source_unit
+        };
+        let svg = |width: &str| {
+            format!(
+                r#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="10" viewBox="0 0 10 10"/>"#
+            )
+        };
+
+        assert_eq!(unit(&svg("210mm")), Some(Unit::Mm));
+        assert_eq!(unit(&svg(" 8.5in")), Some(Unit::In));
+        assert_eq!(unit(&svg("100")), Some(Unit::Px));
+        assert_eq!(unit(&svg("12pt")), Some(Unit::Pt));
+        assert_eq!(unit(&svg("100%")), None);
+        assert_eq!(
+            unit(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"/>"#),
+            Some(Unit::Px)
+        );
+
+        // coordinates are converted to pixels anyway
+        let doc = Document::from_string(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="100mm" viewBox="0 0 100 100">
+                <path d="M 0,0 L 25.4,0" />
+            </svg>"#,
+            false,
+        )
+        .unwrap();
+        assert_abs_diff_eq!(doc.bounds().unwrap().width(), 96.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_path_attrs() {
+        let doc = Document::from_string(
+            r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <style>.border { stroke: red; }</style>
+                <path id="outline" class="border" data-pass="2" d="M 10,10 L 20,20" />
+                <circle cx="50" cy="50" r="10" data-role="registration" />
+                <path d="M 0,0 L 5,5" />
+            </svg>"#,
+            false,
+        )
+        .unwrap();
+
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].metadata().attrs.len(), 2);
+        assert_eq!(paths[0].metadata().attrs["class"], "border");
+        assert_eq!(paths[0].metadata().attrs["pass"], "2");
+        assert_eq!(paths[0].metadata().color, Color::RED); // style sheet still applies
+        assert_eq!(paths[1].metadata().attrs["role"], "registration");
+        assert!(paths[2].metadata().attrs.is_empty());
+
+        // the writer emits them back (layer 0 is reloaded as layer 1)
+        let doc2 = Document::from_string(&doc.to_svg_string().unwrap(), false).unwrap();
+        let paths2 = &doc2.try_get(1).unwrap().paths;
+        assert_eq!(paths2.len(), 3);
+        for (path, path2) in paths.iter().zip(paths2) {
+            assert_eq!(path2.metadata().attrs, path.metadata().attrs);
+        }
+        assert_eq!(doc2.filter_by_attr("class", "border").paths().count(), 1);
+    }
+
+    #[test]
+    fn test_path_attrs_keep_id() {
+        // style sheets and references to shapes with attributes still resolve their `id`
+        let doc = Document::from_string(
+            r##"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="100">
+                <style>#outline { stroke: red; }</style>
+                <defs><path id="mark" data-role="mark" d="M 0,0 L 5,0" stroke="blue" /></defs>
+                <path id="outline" data-pass="2" d="M 10,10 L 20,20" />
+                <use xlink:href="#mark" x="20" y="20" />
+            </svg>"##,
+            true,
+        )
+        .unwrap();
+
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].metadata().color, Color::RED);
+        assert_eq!(paths[0].metadata().attrs["pass"], "2");
+        assert_eq!(paths[1].metadata().color, Color::BLUE);
+        assert_eq!(paths[1].data().start(), Some(Point::new(20., 20.)));
+    }
+
+    #[test]
+    fn test_path_metadata() {
+        let doc = Document::from_string(
+            r##"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <path d="M 10,10 L 20,20" stroke="#ff0000" stroke-width="3" />
+                <polyline points="0,0 10,0 10,10" stroke="blue" stroke-opacity="0.5" />
+                <path d="M 0,0 L 5,5" stroke="RebeccaPurple" />
+                <path d="M 0,0 L 5,5" style="stroke:CornflowerBlue" />
+            </svg>"##,
+            false,
+        )
+        .unwrap();
+
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[0].metadata().color, Color::RED);
+        assert_abs_diff_eq!(paths[0].metadata().stroke_width, 3.0);
+        assert_eq!(paths[1].metadata().color, Color::new(0, 0, 255, 128));
+        assert_abs_diff_eq!(paths[1].metadata().stroke_width, 1.0);
+        assert_eq!(
+            Some(paths[2].metadata().color),
+            Color::from_name("rebeccapurple")
+        );
+        assert_eq!(
+            Some(paths[3].metadata().color),
+            Color::from_name("cornflowerblue")
+        );
+        assert_eq!(
+            paths[1].data,
+            BezPath::from_svg("M 0 0 L 10 0 L 10 10").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_primitives() {
+        let doc = Document::from_svg(test_file!("primitives.svg"), false).unwrap();
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 7);
+
+        let is_closed =
+            |i: usize| paths[i].data().elements().last() == Some(&kurbo::PathEl::ClosePath);
+        let has_curves = |i: usize| {
+            paths[i]
+                .data()
+                .elements()
+                .iter()
+                .any(|el| matches!(el, kurbo::PathEl::CurveTo(..)))
+        };
+
+        // rect
+        assert!(is_closed(0) && !has_curves(0));
+        assert_eq!(
+            paths[0].bounds(),
+            Some(kurbo::Rect::new(10., 10., 40., 30.))
+        );
+
+        // rounded rect, with stroke attributes
+        assert!(is_closed(1) && has_curves(1));
+        assert_eq!(paths[1].metadata().color, Color::RED);
+        assert_abs_diff_eq!(paths[1].metadata().stroke_width, 2.0);
+
+        // circle and ellipse
+        assert!(is_closed(2) && has_curves(2));
+        assert!(is_closed(3) && has_curves(3));
+        let bounds = paths[3].bounds().unwrap();
+        assert_abs_diff_eq!(bounds.width(), 40., epsilon = 1e-9);
+        assert_abs_diff_eq!(bounds.height(), 20., epsilon = 1e-9);
+
+        // line
+        assert_eq!(
+            paths[4].data,
+            BezPath::from_svg("M 10,110 L 90,110").unwrap()
+        );
+        assert_eq!(paths[4].metadata().color, Color::BLUE);
+
+        // polygon is closed, polyline is not
+        assert!(is_closed(5));
+        assert!(!is_closed(6));
+    }
+
+    #[test]
+    fn test_relative_commands() {
+        let doc = Document::from_svg(test_file!("relative_commands.svg"), false).unwrap();
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 2);
+
+        assert_eq!(
+            paths[0].data,
+            BezPath::from_svg(
+                "M 10,10 L 30,10 L 30,30 L 20,30 L 20,25 L 5,25 L 5,5 L 10,10 L 15,5 Z"
+            )
+            .unwrap()
+        );
+
+        let points: Vec<_> = paths[1]
+            .data()
+            .elements()
+            .iter()
+            .filter_map(|el| match *el {
+                kurbo::PathEl::MoveTo(pt)
+                | kurbo::PathEl::LineTo(pt)
+                | kurbo::PathEl::QuadTo(_, pt)
+                | kurbo::PathEl::CurveTo(_, _, pt) => Some(pt),
+                kurbo::PathEl::ClosePath => None,
+            })
+            .collect();
+        assert_eq!(
+            points,
+            [
+                (10., 60.),
+                (20., 60.),
+                (30., 60.),
+                (40., 60.),
+                (50., 60.),
+                (60., 60.),
+                (70., 60.)
+            ]
+            .map(kurbo::Point::from)
+        );
+
+        // smooth curve shortcuts reflect the previous control point
+        assert_eq!(
+            paths[1].data().elements()[2],
+            kurbo::PathEl::CurveTo((20., 50.).into(), (30., 50.).into(), (30., 60.).into())
+        );
+    }
+
+    #[test]
+    fn test_hidden_elements() {
+        let doc = Document::from_svg(test_file!("hidden_layer.svg"), false).unwrap();
+
+        // hidden elements are dropped
+        let layer = doc.try_get(1).unwrap();
+        assert!(!layer.metadata().hidden);
+        assert_eq!(layer.paths.len(), 1);
+
+        // hidden layers are loaded but marked as such
+        let layer = doc.try_get(2).unwrap();
+        assert!(layer.metadata().hidden);
+        assert_eq!(layer.metadata().name, "construction");
+        assert_eq!(layer.paths.len(), 1);
+
+        // in single layer mode, hidden groups are dropped as well
+        let doc = Document::from_svg(test_file!("hidden_layer.svg"), true).unwrap();
+        assert_eq!(doc.layers().len(), 1);
+        assert_eq!(doc.try_get(0).unwrap().paths.len(), 1);
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let doc = Document::from_svg(test_file!("non_finite.svg"), false).unwrap();
+        let paths = &doc.try_get(0).unwrap().paths;
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].data, BezPath::from_svg("M 10,10 L 90,10").unwrap());
+
+        let mut options = SvgLoadOptions {
+            single_layer: true,
+            non_finite: NonFiniteMode::Error,
+            ..Default::default()
+        };
+        let err =
+            Document::from_svg_with_options(test_file!("non_finite.svg"), &options).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&NonFiniteError(1)));
+
+        options.non_finite = NonFiniteMode::Drop;
+        let doc = Document::from_svg_with_options(test_file!("non_finite.svg"), &options).unwrap();
+        assert_eq!(doc.try_get(0).unwrap().paths.len(), 1);
+    }
+
+    #[test]
+    fn test_dedup_points() {
+        let svg = r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <path d="M 10,10 L 10.001,10 L 50,10 L 50,50" />
+            </svg>"#;
+
+        let doc = Document::from_string(svg, true).unwrap();
+        assert_eq!(doc.try_get(0).unwrap().paths[0].data.elements().len(), 4);
+
+        let options = SvgLoadOptions {
+            single_layer: true,
+            dedup_points: Some(0.01),
+            ..Default::default()
+        };
+        let doc = Document::from_string_with_options(svg, &options).unwrap();
+        assert_eq!(doc.try_get(0).unwrap().paths[0].data.elements().len(), 3);
+    }
+
+    #[test]
+    fn test_page_size_units() {
+        let doc = Document::from_string(
+            r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" width="210mm" height="297mm">
+            </svg>"#,
+            false,
+        )
+        .unwrap();
+
+        // usvg works with f32, so the page size is only approximately A4
+        let page_size = doc.metadata().page_size.unwrap();
+        assert_abs_diff_eq!(page_size.w(), PageSize::A4V.w(), epsilon = 1e-3);
+        assert_abs_diff_eq!(page_size.h(), PageSize::A4V.h(), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_from_svg_reader() {
+        let svg = r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <g id="layer1"><line x1="10" y1="10" x2="20" y2="20" /></g>
+                <g id="layer2"><line x1="30" y1="30" x2="40" y2="40" /></g>
+            </svg>"#;
+
+        let doc_str = Document::from_string(svg, false).unwrap();
+        let doc_reader = Document::from_svg_reader(svg.as_bytes(), false).unwrap();
+
+        assert_eq!(doc_reader.layers.len(), 2);
+        assert_eq!(doc_str.metadata(), doc_reader.metadata());
+        for (lid, layer) in &doc_str.layers {
+            let other = doc_reader.try_get(*lid).unwrap();
+            assert_eq!(layer.metadata(), other.metadata());
+            assert_eq!(layer.paths, other.paths);
+        }
+    }
+
+    #[test]
+    fn test_malformed_svg() {
+        assert!(Document::from_string("<svg><g></svg>", false).is_err());
+        assert!(Document::from_svg_reader("not svg at all".as_bytes(), true).is_err());
+    }
+
     #[test]
     fn test_viewbox() {
         let doc = Document::from_string(
@@ -401,8 +922,7 @@ mod tests {
         .unwrap();
 
         let page_size = doc.metadata().page_size.unwrap();
-        assert_eq!(page_size.w(), 100.);
-        assert_eq!(page_size.h(), 100.);
+        assert_eq!(page_size, PageSize::Custom(100., 100., Unit::Px));
         assert_eq!(doc.try_get(0).unwrap().paths.len(), 1);
         assert_eq!(
             doc.try_get(0).unwrap().paths[0].data,
@@ -434,6 +954,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transforms_fixture() {
+        let doc = Document::from_svg(test_file!("transforms.svg"), true).unwrap();
+        let paths = &doc.try_get(0).unwrap().paths;
+
+        let expected = [
+            [(10., 20.), (20., 20.)], // translate
+            [(5., 5.), (15., 5.)],    // matrix + nested scale
+            [(50., 50.), (50., 60.)], // translate + rotate, in document order
+            [(10., 10.), (20., 20.)], // skewX
+            [(10., 10.), (20., 20.)], // skewY
+        ];
+
+        assert_eq!(paths.len(), expected.len());
+        for (path, expected) in paths.iter().zip(expected) {
+            let points = path.flatten(0.01)[0].data().points().to_vec();
+            assert_eq!(points.len(), 2);
+            for (pt, (x, y)) in points.iter().zip(expected) {
+                assert_abs_diff_eq!(pt.x(), x, epsilon = 1e-4);
+                assert_abs_diff_eq!(pt.y(), y, epsilon = 1e-4);
+            }
+        }
+    }
+
     #[test]
     fn test_transforms_viewbox() {
         let doc = Document::from_string(
@@ -458,6 +1002,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reader_keeps_curves() {
+        let doc = Document::from_string(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <path d="M 10,50 A 40,40 0 1 1 90,50 A 40,40 0 1 1 10,50" />
+            </svg>"#,
+            false,
+        )
+        .unwrap();
+
+        // arcs are stored as cubic Béziers...
+        let path = &doc.try_get(0).unwrap().paths[0];
+        assert!(path
+            .data()
+            .elements()
+            .iter()
+            .any(|el| matches!(el, kurbo::PathEl::CurveTo(..))));
+
+        // ...so they can be flattened at any tolerance
+        let coarse = path.flatten(1.0);
+        let fine = path.flatten(0.001);
+        assert!(fine[0].data().points().len() > 10 * coarse[0].data().points().len());
+
+        // the cubic approximation of arcs deviates from the circle by up to ~0.03% of the radius
+        let center = Point::new(50., 50.);
+        for pt in fine[0].data().points() {
+            assert_abs_diff_eq!(pt.distance(&center), 40., epsilon = 0.02);
+        }
+    }
+
+    #[test]
+    fn test_reader_rounded_rect() {
+        let doc = Document::from_svg(test_file!("rounded_rect.svg"), false).unwrap();
+        let path = &doc.try_get(0).unwrap().paths[0];
+
+        // four straight sides and four arcs (relative and absolute) for the corners
+        let elements = path.data().elements();
+        assert_eq!(
+            elements
+                .iter()
+                .filter(|el| matches!(el, kurbo::PathEl::LineTo(..)))
+                .count(),
+            4
+        );
+        assert!(
+            elements
+                .iter()
+                .filter(|el| matches!(el, kurbo::PathEl::CurveTo(..)))
+                .count()
+                >= 4
+        );
+
+        // corners are smooth: every flattened point of a corner lies on its circle
+        let flattened = path.flatten(0.001);
+        let corner_points: Vec<_> = flattened[0]
+            .data()
+            .points()
+            .iter()
+            .filter(|pt| (pt.x() - 50.).abs() > 30. && (pt.y() - 50.).abs() > 30.)
+            .collect();
+        assert!(corner_points.len() > 20);
+        for pt in corner_points {
+            let center = Point::new(
+                50. + 30. * (pt.x() - 50.).signum(),
+                50. + 30. * (pt.y() - 50.).signum(),
+            );
+            assert_abs_diff_eq!(pt.distance(&center), 10., epsilon = 0.01);
+        }
+        assert_eq!(path.bounds(), Some(kurbo::Rect::new(10., 10., 90., 90.)));
+    }
+
     #[test]
     fn test_reader_quad_beziers() {
         let doc = Document::from_string(
@@ -469,10 +1084,10 @@ mod tests {
             false,
         ).unwrap();
 
-        //TODO: the crop removes the Close command, which is not ideal...
+        // the path is within the page, so the crop leaves it (and its Close command) untouched
         assert_eq!(
             doc.try_get(3).unwrap().paths[0].data,
-            BezPath::from_svg("M 0 0 L 0 200 Q 300 100 0 0").unwrap()
+            BezPath::from_svg("M 0 0 L 0 200 Q 300 100 0 0 Z").unwrap()
         );
     }
 }
@@ -3,27 +3,67 @@
 
 use svg::Node;
 
+use super::metadata::element_name;
 use crate::{DocumentTrait, LayerTrait, PathDataTrait, PathTrait, Polyline};
 
+/// Options for [`DocumentTrait::to_svg_with_options`] and related functions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// Number of decimals of the path coordinates, or `None` for full precision.
+    ///
+    /// Trailing zeros are trimmed (e.g. `1.5` rather than `1.50`).
+    pub decimals: Option<usize>,
+}
+
+/// Decimals beyond which rounding has no effect on `f64` coordinates.
+const MAX_DECIMALS: usize = 15;
+
+/// Round `value` to `decimals`, avoiding `-0` in the output.
+///
+/// `decimals` is capped to [`MAX_DECIMALS`], and values too large to be scaled are returned as
+/// is.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn round(value: f64, decimals: Option<usize>) -> f64 {
+    let Some(decimals) = decimals else {
+        return value;
+    };
+
+    let factor = 10f64.powi(decimals.min(MAX_DECIMALS) as i32);
+    let scaled = value * factor;
+    if !scaled.is_finite() {
+        return value;
+    }
+    let rounded = scaled.round() / factor;
+    if rounded == 0.0 {
+        0.0
+    } else {
+        rounded
+    }
+}
+
 // private trait in public types, see https://github.com/rust-lang/rust/issues/34537
 
 pub trait SvgPathWriter {
-    fn to_svg_path_data(&self) -> svg::node::element::path::Data;
+    /// Path data with coordinates rounded to `decimals`, see [`SvgOptions::decimals`].
+    fn to_svg_path_data(&self, decimals: Option<usize>) -> svg::node::element::path::Data;
 }
 
 impl SvgPathWriter for kurbo::BezPath {
-    fn to_svg_path_data(&self) -> svg::node::element::path::Data {
+    fn to_svg_path_data(&self, decimals: Option<usize>) -> svg::node::element::path::Data {
         let mut data = svg::node::element::path::Data::new();
+        let pt = |pt: &kurbo::Point| (round(pt.x, decimals), round(pt.y, decimals));
 
         for el in self.elements() {
             match el {
-                kurbo::PathEl::MoveTo(pt) => data = data.move_to((pt.x, pt.y)),
-                kurbo::PathEl::LineTo(pt) => data = data.line_to((pt.x, pt.y)),
+                kurbo::PathEl::MoveTo(pt0) => data = data.move_to(pt(pt0)),
+                kurbo::PathEl::LineTo(pt0) => data = data.line_to(pt(pt0)),
                 kurbo::PathEl::QuadTo(pt1, pt2) => {
-                    data = data.quadratic_curve_to((pt1.x, pt1.y, pt2.x, pt2.y));
+                    let ((x1, y1), (x2, y2)) = (pt(pt1), pt(pt2));
+                    data = data.quadratic_curve_to((x1, y1, x2, y2));
                 }
                 kurbo::PathEl::CurveTo(pt1, pt2, pt3) => {
-                    data = data.cubic_curve_to((pt1.x, pt1.y, pt2.x, pt2.y, pt3.x, pt3.y));
+                    let ((x1, y1), (x2, y2), (x3, y3)) = (pt(pt1), pt(pt2), pt(pt3));
+                    data = data.cubic_curve_to((x1, y1, x2, y2, x3, y3));
                 }
                 kurbo::PathEl::ClosePath => data = data.close(),
             }
@@ -34,8 +74,9 @@ impl SvgPathWriter for kurbo::BezPath {
 }
 
 impl SvgPathWriter for Polyline {
-    fn to_svg_path_data(&self) -> svg::node::element::path::Data {
+    fn to_svg_path_data(&self, decimals: Option<usize>) -> svg::node::element::path::Data {
         let mut data = svg::node::element::path::Data::new();
+        let xy = |pt: &crate::Point| (round(pt.x(), decimals), round(pt.y(), decimals));
 
         if self.points().len() < 2 {
             return data;
@@ -43,12 +84,12 @@ impl SvgPathWriter for Polyline {
 
         for (i, pt) in self.points().iter().enumerate() {
             if i == 0 {
-                data = data.move_to((pt.x(), pt.y()));
+                data = data.move_to(xy(pt));
             } else if i == self.points().len() - 1 && self.points().first() == self.points().last()
             {
                 data = data.close();
             } else {
-                data = data.line_to((pt.x(), pt.y()));
+                data = data.line_to(xy(pt));
             }
         }
 
@@ -56,12 +97,15 @@ impl SvgPathWriter for Polyline {
     }
 }
 
-fn path_to_svg_path<P: PathTrait<D>, D: PathDataTrait>(path: &P) -> svg::node::element::Path {
+fn path_to_svg_path<P: PathTrait<D>, D: PathDataTrait>(
+    path: &P,
+    options: &SvgOptions,
+) -> svg::node::element::Path {
     let mut elem = svg::node::element::Path::new()
         .set("fill", "none")
         .set("stroke", path.metadata().color.to_rgb_string())
         .set("stroke-width", path.metadata().stroke_width)
-        .set("d", path.data().to_svg_path_data());
+        .set("d", path.data().to_svg_path_data(options.decimals));
 
     if path.metadata().color.opacity() < 1.0 {
         elem = elem.set(
@@ -70,6 +114,15 @@ fn path_to_svg_path<P: PathTrait<D>, D: PathDataTrait>(path: &P) -> svg::node::e
         );
     }
 
+    for (key, value) in &path.metadata().attrs {
+        let name = if key == "class" {
+            key.clone()
+        } else {
+            format!("data-{key}")
+        };
+        elem = elem.set(name, quick_xml::escape::escape(value).into_owned());
+    }
+
     elem
 
     // TODO: do not add metadata if it is the default
@@ -78,13 +131,18 @@ fn path_to_svg_path<P: PathTrait<D>, D: PathDataTrait>(path: &P) -> svg::node::e
 
 fn layer_to_svg_group<L: LayerTrait<P, D>, P: PathTrait<D>, D: PathDataTrait + SvgPathWriter>(
     layer: &L,
+    options: &SvgOptions,
 ) -> svg::node::element::Group {
-    let mut group = svg::node::element::Group::new()
-        .set("inkscape:groupmode", "layer")
-        .set("inkscape:label", layer.metadata().name.as_str());
+    let mut group = svg::node::element::Group::new().set("inkscape:groupmode", "layer");
+    if !layer.metadata().name.is_empty() {
+        group = group.set("inkscape:label", layer.metadata().name.as_str());
+    }
+    if layer.metadata().hidden {
+        group = group.set("style", "display:none");
+    }
 
     for path in layer.paths() {
-        group = group.add(path_to_svg_path(path));
+        group = group.add(path_to_svg_path(path, options));
     }
 
     group
@@ -97,6 +155,7 @@ pub(crate) fn document_to_svg_doc<
     D: PathDataTrait,
 >(
     document: &T,
+    options: &SvgOptions,
 ) -> svg::Document {
     let mut doc = svg::Document::new()
         .set(
@@ -108,8 +167,8 @@ pub(crate) fn document_to_svg_doc<
         .set("xmlns:rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns");
 
     // dimensions, ensuring minimum size of 1x1
-    let mut dims = if let Some(page_size) = document.metadata().page_size {
-        kurbo::Rect::from_points((0.0, 0.0), page_size.to_pixels())
+    let mut dims = if let Some(page_dimensions) = document.metadata().page_dimensions() {
+        kurbo::Rect::from_points((0.0, 0.0), page_dimensions)
     } else if let Some(bounds) = document.bounds() {
         bounds
     } else {
@@ -117,9 +176,11 @@ pub(crate) fn document_to_svg_doc<
     };
     dims = dims.union(kurbo::Rect::from_origin_size(dims.origin(), (1.0, 1.0)));
 
+    // the view box is in document units, while the size is in pixels
+    let px_per_unit = document.metadata().unit.to_px();
     doc = doc
-        .set("width", format!("{:.5}", dims.width()))
-        .set("height", format!("{:.5}", dims.height()))
+        .set("width", format!("{:.5}", dims.width() * px_per_unit))
+        .set("height", format!("{:.5}", dims.height() * px_per_unit))
         .set(
             "viewBox",
             format!(
@@ -154,18 +215,32 @@ pub(crate) fn document_to_svg_doc<
 
     if let Some(source) = document.metadata().source.as_ref() {
         let mut dc_source = svg::node::element::Element::new("dc:source");
-        dc_source.append(svg::node::Text::new(source));
+        dc_source.append(svg::node::Text::new(quick_xml::escape::escape(source)));
         cc.append(dc_source);
     }
+    for (key, value) in &document.metadata().properties {
+        let Some(name) = element_name(key) else {
+            log::warn!("metadata key {key:?} is not a valid element name, skipping");
+            continue;
+        };
+        let mut element = svg::node::element::Element::new(format!("dc:{name}"));
+        element.append(svg::node::Text::new(quick_xml::escape::escape(value)));
+        cc.append(element);
+    }
     let mut rdf = svg::node::element::Element::new("rdf:RDF");
     rdf.append(cc);
     let mut metadata = svg::node::element::Element::new("metadata");
     metadata.append(rdf);
+    if let Some(title) = document.metadata().title() {
+        let mut element = svg::node::element::Title::new();
+        element.append(svg::node::Text::new(quick_xml::escape::escape(title)));
+        doc.append(element);
+    }
     doc.append(metadata);
 
     // append layers
     for (lid, layer) in document.layers() {
-        let group = layer_to_svg_group(layer).set("id", format!("layer{lid}"));
+        let group = layer_to_svg_group(layer, options).set("id", format!("layer{lid}"));
 
         doc = doc.add(group);
     }
@@ -195,4 +270,122 @@ mod tests {
 
         assert!(svg.contains("path d=\"M10,0 L20,0\""));
     }
+
+    #[test]
+    fn test_svg_decimals() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(1.234_567, -0.001), (10.5, 2.0)][..]);
+        doc.push_path(
+            1,
+            kurbo::CubicBez::new((0.0, 0.0), (1.0 / 3.0, 0.0), (2.0 / 3.0, 1.0), (1.0, 1.0)),
+        );
+
+        let options = SvgOptions { decimals: Some(2) };
+        let svg = doc.to_svg_string_with_options(&options).unwrap();
+        assert!(svg.contains("d=\"M1.23,0 L10.5,2\""));
+        assert!(svg.contains("d=\"M0,0 C0.33,0,0.67,1,1,1\""));
+
+        let svg = doc
+            .to_svg_string_with_options(&SvgOptions { decimals: Some(0) })
+            .unwrap();
+        assert!(svg.contains("d=\"M1,0 L11,2\""));
+
+        let svg = doc.to_svg_string().unwrap();
+        assert!(svg.contains("d=\"M1.234567,-0.001 L10.5,2\""));
+
+        // large decimals are capped rather than overflowing
+        let svg = doc
+            .to_svg_string_with_options(&SvgOptions {
+                decimals: Some(usize::MAX),
+            })
+            .unwrap();
+        assert!(svg.contains("d=\"M1.234567,-0.001 L10.5,2\""));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_round() {
+        assert_eq!(round(1.25, Some(1)), 1.3);
+        assert_eq!(round(-0.01, Some(1)), 0.0);
+        assert_eq!(round(1e300, Some(15)), 1e300);
+        assert_eq!(round(0.1, Some(400)), 0.1);
+    }
+
+    #[test]
+    fn test_svg_metadata_round_trip() {
+        let mut doc = Document::default();
+        doc.push_path(1, &[(0., 0.), (10., 10.)][..]);
+        doc.metadata_mut()
+            .set_title("Series #3: <waves> & \"lines\"");
+        doc.metadata_mut().set_author("Jane Doe");
+        doc.metadata_mut()
+            .properties
+            .insert("pen".to_owned(), "Micron 0.3".to_owned());
+        doc.metadata_mut()
+            .properties
+            .insert("not valid".to_owned(), "skipped".to_owned());
+
+        let svg = doc.to_svg_string().unwrap();
+        assert!(svg.contains("<title>"));
+        assert!(svg.contains("<dc:creator>"));
+
+        let doc2 = Document::from_string(&svg, false).unwrap();
+        assert_eq!(
+            doc2.metadata().title(),
+            Some("Series #3: <waves> & \"lines\"")
+        );
+        assert_eq!(doc2.metadata().author(), Some("Jane Doe"));
+        assert_eq!(doc2.metadata().properties.len(), 3);
+        assert_eq!(doc2.metadata().properties["pen"], "Micron 0.3");
+    }
+
+    #[test]
+    fn test_svg_round_trip() {
+        let doc = Document::from_string(
+            r#"<?xml version="1.0"?>
+            <svg xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+               xmlns="http://www.w3.org/2000/svg"
+               width="100" height="100" >
+                <g id="layer1" inkscape:groupmode="layer" inkscape:label="first">
+                    <path d="M 10,10 L 20,10 L 20,20" stroke="red" stroke-width="2" />
+                </g>
+                <g id="layer3" style="display:none">
+                    <path d="M 30,30 C 40,30 50,40 50,50" />
+                    <path d="M 60,60 L 70,70 Z" />
+                </g>
+            </svg>"#,
+            false,
+        )
+        .unwrap();
+
+        let svg = doc.to_svg_string().unwrap();
+        assert!(svg.contains("id=\"layer1\""));
+        assert!(svg.contains("id=\"layer3\""));
+
+        let doc2 = Document::from_string(&svg, false).unwrap();
+        assert_eq!(doc2.metadata().page_size, doc.metadata().page_size);
+        assert_eq!(
+            doc2.layers().keys().collect::<Vec<_>>(),
+            doc.layers().keys().collect::<Vec<_>>()
+        );
+
+        assert!(svg.contains("inkscape:label=\"first\""));
+        assert_eq!(doc2.layer_by_name("first"), Some(1));
+        assert!(doc2.try_get(3).unwrap().metadata().hidden);
+
+        for (lid, layer) in doc.layers() {
+            let layer2 = doc2.try_get(*lid).unwrap();
+            assert_eq!(layer2.metadata(), layer.metadata());
+            assert_eq!(layer2.paths.len(), layer.paths.len());
+            for (path, path2) in layer.paths.iter().zip(&layer2.paths) {
+                assert_eq!(path2.metadata(), path.metadata());
+                for (a, b) in path.flatten(0.01).iter().zip(&path2.flatten(0.01)) {
+                    assert_eq!(a.data().points().len(), b.data().points().len());
+                    for (pa, pb) in a.data().points().iter().zip(b.data().points()) {
+                        assert!(pa.distance(pb) < 1e-4);
+                    }
+                }
+            }
+        }
+    }
 }
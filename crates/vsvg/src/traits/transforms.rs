@@ -14,6 +14,13 @@ pub trait Transforms: Sized {
     /// Apply a 2D affine transform
     fn transform(&mut self, affine: &Affine) -> &mut Self;
 
+    /// Apply a 2D affine transform given by its SVG-style `[a, b, c, d, e, f]` coefficients.
+    ///
+    /// This is the same as the SVG `matrix(a, b, c, d, e, f)` transform.
+    fn transform_matrix(&mut self, matrix: [f64; 6]) -> &mut Self {
+        self.transform(&Affine::new(matrix))
+    }
+
     /// Translate the geometry by `dx` and `dy`.
     fn translate(&mut self, dx: f64, dy: f64) -> &mut Self {
         self.transform(&Affine::translate((dx, dy)));
@@ -33,6 +40,8 @@ pub trait Transforms: Sized {
     }
 
     /// Scale the geometry by `sx` and `sy` around the point `(cx, cy)`.
+    ///
+    /// The point `(cx, cy)` is left unchanged. Use `sx == sy` for uniform scaling.
     fn scale_around(&mut self, sx: f64, sy: f64, cx: f64, cy: f64) -> &mut Self {
         let transform = Affine::translate((cx, cy))
             * Affine::scale_non_uniform(sx, sy)
@@ -65,6 +74,16 @@ pub trait Transforms: Sized {
         self.rotate_around(theta.to_radians(), cx, cy)
     }
 
+    /// Mirror the geometry horizontally around the vertical axis `x = axis`.
+    fn flip_horizontal_around(&mut self, axis: f64) -> &mut Self {
+        self.scale_around(-1.0, 1.0, axis, 0.0)
+    }
+
+    /// Mirror the geometry vertically around the horizontal axis `y = axis`.
+    fn flip_vertical_around(&mut self, axis: f64) -> &mut Self {
+        self.scale_around(1.0, -1.0, 0.0, axis)
+    }
+
     /// Skew the geometry by `kx` and `ky` radians around the origin.
     fn skew(&mut self, kx: f64, ky: f64) -> &mut Self {
         self.transform(&skew_affine(kx, ky));
@@ -80,4 +99,130 @@ pub trait Transforms: Sized {
     }
 }
 
-// TODO: tests
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Document, DocumentTrait, FlattenedDocument, Point};
+    use approx::assert_abs_diff_eq;
+
+    fn doc_with_line(p0: (f64, f64), p1: (f64, f64)) -> Document {
+        let mut doc = Document::default();
+        doc.push_path(1, kurbo::Line::new(p0, p1));
+        doc
+    }
+
+    fn points(doc: &FlattenedDocument) -> Vec<Point> {
+        doc.layers()
+            .values()
+            .flat_map(|layer| layer.paths.iter())
+            .flat_map(|path| path.data.points().iter().copied())
+            .collect()
+    }
+
+    fn assert_points(doc: &FlattenedDocument, expected: &[(f64, f64)]) {
+        let actual = points(doc);
+        assert_eq!(actual.len(), expected.len());
+        for (pt, (x, y)) in actual.iter().zip(expected) {
+            assert_abs_diff_eq!(pt.x(), x, epsilon = 1e-10);
+            assert_abs_diff_eq!(pt.y(), y, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_transform_matrix() {
+        let rot90 = [0., 1., -1., 0., 0., 0.];
+
+        let mut doc = doc_with_line((1., 0.), (2., 0.));
+        doc.transform_matrix(rot90);
+        assert_points(&doc.flatten(0.1), &[(0., 1.), (0., 2.)]);
+
+        let mut flat_doc = doc_with_line((1., 0.), (2., 0.)).flatten(0.1);
+        flat_doc.transform_matrix(rot90);
+        assert_points(&flat_doc, &[(0., 1.), (0., 2.)]);
+
+        // translation components
+        let mut doc = doc_with_line((1., 0.), (2., 0.));
+        doc.transform_matrix([2., 0., 0., 2., 10., 20.]);
+        assert_points(&doc.flatten(0.1), &[(12., 20.), (14., 20.)]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut doc = doc_with_line((1., 0.), (2., 0.));
+        doc.rotate_deg(90.);
+        assert_points(&doc.flatten(0.1), &[(0., 1.), (0., 2.)]);
+
+        let mut flat_doc = doc_with_line((1., 0.), (2., 0.)).flatten(0.1);
+        flat_doc.rotate(std::f64::consts::FRAC_PI_2);
+        assert_points(&flat_doc, &[(0., 1.), (0., 2.)]);
+    }
+
+    #[test]
+    fn test_rotate_full_turn() {
+        let mut doc = doc_with_line((3., 4.), (-5., 7.));
+        doc.rotate(std::f64::consts::TAU);
+        assert_points(&doc.flatten(0.1), &[(3., 4.), (-5., 7.)]);
+    }
+
+    #[test]
+    fn test_rotate_around() {
+        let mut doc = doc_with_line((11., 10.), (12., 10.));
+        doc.rotate_around_deg(90., 10., 10.);
+        assert_points(&doc.flatten(0.1), &[(10., 11.), (10., 12.)]);
+
+        let mut flat_doc = doc_with_line((11., 10.), (12., 10.)).flatten(0.1);
+        flat_doc.rotate_around(std::f64::consts::PI, 10., 10.);
+        assert_points(&flat_doc, &[(9., 10.), (8., 10.)]);
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut doc = doc_with_line((1., 2.), (3., 4.));
+        doc.translate(10., -20.);
+        assert_points(&doc.flatten(0.1), &[(11., -18.), (13., -16.)]);
+
+        doc.translate(-10., 20.);
+        assert_points(&doc.flatten(0.1), &[(1., 2.), (3., 4.)]);
+    }
+
+    #[test]
+    fn test_translate_scale_compose() {
+        let mut flat_doc = doc_with_line((1., 2.), (3., 4.)).flatten(0.1);
+        flat_doc.translate(1., 1.).scale_non_uniform(2., -1.);
+        assert_points(&flat_doc, &[(4., -3.), (8., -5.)]);
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut doc = doc_with_line((1., 2.), (3., 4.));
+        doc.scale(2.);
+        assert_points(&doc.flatten(0.1), &[(2., 4.), (6., 8.)]);
+
+        let mut flat_doc = doc_with_line((1., 2.), (3., 4.)).flatten(0.1);
+        flat_doc.scale(0.5);
+        assert_points(&flat_doc, &[(0.5, 1.), (1.5, 2.)]);
+    }
+
+    #[test]
+    fn test_scale_around() {
+        // center of the line stays in place
+        let mut doc = doc_with_line((10., 10.), (20., 20.));
+        doc.scale_around(2., 2., 15., 15.);
+        assert_points(&doc.flatten(0.1), &[(5., 5.), (25., 25.)]);
+
+        let mut flat_doc = doc_with_line((10., 10.), (20., 20.)).flatten(0.1);
+        flat_doc.scale_around(0.5, 2., 10., 10.);
+        assert_points(&flat_doc, &[(10., 10.), (15., 30.)]);
+    }
+
+    #[test]
+    fn test_flip_around() {
+        let mut doc = doc_with_line((1., 2.), (3., 4.));
+        doc.flip_horizontal_around(5.);
+        assert_points(&doc.flatten(0.1), &[(9., 2.), (7., 4.)]);
+
+        let mut flat_doc = doc_with_line((1., 2.), (3., 4.)).flatten(0.1);
+        flat_doc.flip_vertical_around(0.);
+        assert_points(&flat_doc, &[(1., -2.), (3., -4.)]);
+    }
+}
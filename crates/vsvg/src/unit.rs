@@ -2,8 +2,9 @@
 ///
 /// Doc TODO:
 /// - Mul
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum Unit {
+    #[default]
     Px,
     In,
     Ft,
@@ -121,7 +121,7 @@ impl SaveUI {
                         {
                             if let Some(sketch) = sketch {
                                 if let Some(path) = self.get_output_path() {
-                                    self.last_error = Some(sketch.save(&path).map(|_| {
+                                    self.last_error = Some(sketch.save(&path).map(|()| {
                                         path.file_name().map_or("<unknown>".to_string(), |s| {
                                             s.to_string_lossy().to_string()
                                         })
@@ -132,7 +132,7 @@ impl SaveUI {
 
                         if let Some(last_error) = &self.last_error {
                             let txt = match last_error {
-                                Ok(file_name) => file_name.to_string(),
+                                Ok(file_name) => file_name.clone(),
                                 Err(err) => format!("Error: {err}"),
                             };
                             let label =